@@ -11,10 +11,12 @@ use crate::engine::core::internal::{add_internal_entities, add_internal_systems}
 use crate::engine::core::lifecycle::{Lifecycle, LifecycleArgs};
 use crate::engine::core::scene::{Scene, SceneManager, SceneTransition, TransitionState};
 use crate::engine::core::subsystem::Subsystem;
+use crate::engine::ecs::spatial::SpatialIndex;
 use crate::engine::ecs::system::{Schedule, SysArgs, SystemManager, SystemTag};
 use crate::engine::ecs::world::World;
 use crate::engine::geometry::shape::Vec2;
 use crate::engine::render::camera::{Camera, CameraBounds};
+use crate::engine::render::debug::DebugDraw;
 use crate::engine::render::renderer::Properties;
 use crate::engine::utility::alias::{DeltaMS, Size2};
 use crate::engine::utility::color::{RGBA, unit_to_alpha};
@@ -32,6 +34,8 @@ struct Engine<'a> {
   scenes: SceneManager,
   camera: Camera,
   world: World,
+  spatial: SpatialIndex,
+  debug: DebugDraw,
   lifecycle: Lifecycle,
   last_frame: Frame,
   state: State,
@@ -39,14 +43,16 @@ struct Engine<'a> {
 
 impl<'a> Engine<'a> {
   /// Instantiate a new application using `subsystem` with `actions`
-  fn new(subsystem: &'a mut Subsystem, dimensions: Size2, lifecycle: Lifecycle, scene: impl Scene + 'static) -> Self {
+  fn new(subsystem: &'a mut Subsystem, dimensions: Size2, lifecycle: Lifecycle, scene: impl Scene + 'static, loading: impl Scene + 'static) -> Self {
     Self {
       subsystem,
       events: EventStore::new(),
-      scenes: SceneManager::new(scene),
+      scenes: SceneManager::new(scene).with_loading(loading),
       camera: Camera::new(CameraBounds::new(Vec2::default(), dimensions)),
       state: State::default(),
       world: World::new(),
+      spatial: SpatialIndex::default(),
+      debug: DebugDraw::default(),
       lifecycle,
       last_frame: Frame::build(SIMULATION_FPS).expect("Failed to build frame"),
     }
@@ -61,7 +67,7 @@ impl<'a> Engine<'a> {
 
     let mut transition = SceneTransition::new(Duration::from_millis(TRANSITION_TIME_MS / 2));
 
-    (self.lifecycle.setup)(LifecycleArgs::new(&mut self.world, &mut systems, &mut self.camera, &mut self.state, assets));
+    (self.lifecycle.setup)(LifecycleArgs::new(&mut self.world, &mut systems, &mut self.camera, &mut self.subsystem.renderer, &mut self.state, assets));
 
     loop {
       // start frame
@@ -86,21 +92,28 @@ impl<'a> Engine<'a> {
         self.subsystem.events.resume(&mut self.events);
       }
 
+      // preload the assets of the next scene while the loading scene is shown
+      self.scenes.preload(&mut LifecycleArgs::new(&mut self.world, &mut systems, &mut self.camera, &mut self.subsystem.renderer, &mut self.state, assets))?;
+
       // process physics
       self.last_frame.process_accumulated(|fixed_time| {
-        let mut args = SysArgs::new(fixed_time, &mut self.world, &mut self.subsystem.renderer, &mut self.events, &mut self.camera, &mut self.scenes, &mut self.state, assets);
+        let mut args = SysArgs::new(fixed_time, &mut self.world, &mut self.subsystem.renderer, &mut self.events, &mut self.camera, &mut self.scenes, &mut self.state, assets, &mut self.spatial, &mut self.debug);
         systems.update(Schedule::FixedUpdate, &mut args)
       })?;
-      let mut args = SysArgs::new(delta, &mut self.world, &mut self.subsystem.renderer, &mut self.events, &mut self.camera, &mut self.scenes, &mut self.state, assets);
+      let mut args = SysArgs::new(delta, &mut self.world, &mut self.subsystem.renderer, &mut self.events, &mut self.camera, &mut self.scenes, &mut self.state, assets, &mut self.spatial, &mut self.debug);
       systems.update(Schedule::FrameUpdate, &mut args)?;
       systems.update(Schedule::PostUpdate, &mut args)?;
 
+      // draw debug shapes above every layer
+      self.debug.flush(&mut self.subsystem.renderer, &self.camera, assets)?;
+
       if self.scenes.is_queue() && !transition.active() {
         self.events.queue_pause();
         transition.start();
       }
 
       if transition.active() {
+        let mut loaded = Ok(());
         transition.integrate(|state, interpolation| {
           match (state, interpolation) {
             (TransitionState::Idle, ..) => {
@@ -117,7 +130,7 @@ impl<'a> Engine<'a> {
                 systems.remove_suspended();
                 self.events.clear_held_keys();
 
-                self.scenes.next(&mut LifecycleArgs::new(&mut self.world, &mut systems, &mut self.camera, &mut self.state, assets));
+                loaded = self.scenes.next(&mut LifecycleArgs::new(&mut self.world, &mut systems, &mut self.camera, &mut self.subsystem.renderer, &mut self.state, assets));
                 self.events.queue_pause();
               }
             }
@@ -131,6 +144,7 @@ impl<'a> Engine<'a> {
             }
           }
         });
+        loaded?;
       }
 
       self.subsystem.renderer.present();
@@ -147,17 +161,20 @@ pub struct Application;
 
 impl Application {
   /// Build subsystems and start application of `Properties` with `Actions`
+  ///
+  /// The loading scene is shown while the assets of a scene are preloaded.
   pub fn build(
     properties: Properties,
     actions: Lifecycle,
     initial_scene: impl Scene + 'static,
+    loading_scene: impl Scene + 'static,
   ) -> Result<(), String> {
     let dimensions = properties.logical.unwrap_or(properties.dimensions);
     let mut subsystem = Subsystem::build(properties)?;
     let ttf_context = sdl2::ttf::init().map_err(|e| e.to_string())?;
     let mut assets = AssetManager::new(&subsystem.renderer, &ttf_context);
 
-    let mut engine = Engine::new(&mut subsystem, dimensions, actions, initial_scene);
+    let mut engine = Engine::new(&mut subsystem, dimensions, actions, initial_scene, loading_scene);
     engine.start(&mut assets)?;
 
     Ok(())
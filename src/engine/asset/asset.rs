@@ -4,6 +4,7 @@
 
 use sdl2::ttf::Sdl2TtfContext;
 use crate::engine::asset::audio::{AudioPlayer, SoundType};
+use crate::engine::asset::sheet::SheetLoader;
 use crate::engine::asset::texture::TextureLoader;
 use crate::engine::asset::typography::TypefaceLoader;
 use crate::engine::render::renderer::Renderer;
@@ -18,6 +19,7 @@ pub enum AssetType {
 /// Manages the loading and storage of game assets
 pub struct AssetManager<'ttf> {
   pub texture: TextureLoader,
+  pub sheet: SheetLoader,
   pub audio: AudioPlayer,
   pub typeface: TypefaceLoader<'ttf, 'ttf>,
 }
@@ -27,6 +29,7 @@ impl<'ttf> AssetManager<'ttf> {
   pub fn new(renderer: &Renderer, ttf_context: &'ttf Sdl2TtfContext) -> Self {
     Self {
       texture: TextureLoader::new(renderer.new_texture_creator()),
+      sheet: SheetLoader::new(),
       audio: AudioPlayer::new(),
       typeface: TypefaceLoader::new(&ttf_context),
     }
@@ -2,12 +2,27 @@
  * Loading and playing music and sfx
  */
 
+use std::collections::HashMap;
 use std::path::Path;
-use crate::engine::utility::store::Store;
+use std::time::Duration;
+
+use sdl2::mixer::{Channel, Fading, LoaderRWops, MAX_VOLUME};
+use sdl2::rwops::RWops;
+use serde::{Deserialize, Serialize};
 
+use crate::engine::asset::synth::{encode_wav, load_synth_presets, synthesize, synthesize_sequence, SynthParams, SynthSequence};
+use crate::engine::asset::vfs;
+use crate::engine::math::random::random;
+use crate::engine::utility::store::Store;
 use crate::game::utility::path::get_filename;
 
+/// Instances of one effect that may play at once unless set otherwise
+pub const DEFAULT_MAX_INSTANCES: usize = 4;
+/// Resampled copies of an effect played at random to vary its pitch
+pub const PITCH_VARIANTS: usize = 5;
+
 /// Type of sound
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum SoundType {
   Music,
   Effect,
@@ -16,19 +31,42 @@ pub enum SoundType {
 /// Sound data
 pub enum Sound {
   Music { data: sdl2::mixer::Music<'static> },
-  Effect { data: sdl2::mixer::Chunk },
+  /// effects with pitch variation play one of their variants rather than `data`
+  Effect { data: sdl2::mixer::Chunk, variants: Vec<sdl2::mixer::Chunk> },
+}
+
+impl Sound {
+  /// Get the type of the sound
+  pub fn get_type(&self) -> SoundType {
+    match self {
+      Sound::Music { .. } => SoundType::Music,
+      Sound::Effect { .. } => SoundType::Effect,
+    }
+  }
 }
 
 /// Looping behavior
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Loop {
   Forever,
   Once,
 }
 
+impl Loop {
+  /// Get the number of times SDL_mixer repeats the sound
+  fn get_loops(self) -> i32 {
+    match self {
+      Loop::Forever => -1,
+      Loop::Once => 0,
+    }
+  }
+}
+
 /// Audio data
 pub struct Audio {
   pub sound: Sound,
   pub name: String,
+  /// file the sound was loaded or synthesized from
   pub path: Box<Path>,
 }
 
@@ -37,9 +75,181 @@ type AudioStore = Store<String, Audio>;
 
 type AudioKey = String;
 
+// Volume //
+
+/// Groups of sounds with a shared volume
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum VolumeGroup {
+  /// every sound
+  Master,
+  Music,
+  Sfx,
+}
+
+/// Volume of each group in the unit interval
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Volumes {
+  pub master: f32,
+  pub music: f32,
+  pub sfx: f32,
+}
+
+impl Default for Volumes {
+  /// Play every group at full volume
+  fn default() -> Self {
+    Self { master: 1.0, music: 1.0, sfx: 1.0 }
+  }
+}
+
+impl Volumes {
+  /// Get the volume of a group
+  pub fn get(&self, group: VolumeGroup) -> f32 {
+    match group {
+      VolumeGroup::Master => self.master,
+      VolumeGroup::Music => self.music,
+      VolumeGroup::Sfx => self.sfx,
+    }
+  }
+  /// Set the volume of a group, clamped to the unit interval
+  pub fn set(&mut self, group: VolumeGroup, volume: f32) {
+    let volume = volume.clamp(0.0, 1.0);
+    match group {
+      VolumeGroup::Master => self.master = volume,
+      VolumeGroup::Music => self.music = volume,
+      VolumeGroup::Sfx => self.sfx = volume,
+    }
+  }
+  /// Mix the volume of a sound with the volume of its groups into an SDL_mixer volume
+  pub fn mix(&self, sound_type: SoundType, volume: f32) -> i32 {
+    let group = match sound_type {
+      SoundType::Music => self.music,
+      SoundType::Effect => self.sfx,
+    };
+    (self.master * group * volume.clamp(0.0, 1.0) * MAX_VOLUME as f32).round() as i32
+  }
+}
+
+// Instances //
+
+/// Refers to a playing sound, and becomes stale once the sound ends or is replaced
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SoundHandle {
+  Music { id: u64 },
+  Effect { channel: i32, id: u64 },
+}
+
+/// A sound started by the player
+#[derive(Clone, Debug, PartialEq)]
+struct Instance {
+  name: AudioKey,
+  id: u64,
+  volume: f32,
+}
+
+/// Bookkeeping of the sounds playing on each channel
+#[derive(Debug, Default)]
+struct Instances {
+  effects: HashMap<i32, Instance>,
+  music: Option<Instance>,
+  next_id: u64,
+}
+
+impl Instances {
+  /// Track a new instance of a sound
+  fn next(&mut self, name: &str, volume: f32) -> Instance {
+    self.next_id += 1;
+    Instance { name: name.to_string(), id: self.next_id, volume }
+  }
+  /// Track an effect started on a channel
+  fn start_effect(&mut self, channel: i32, name: &str, volume: f32) -> SoundHandle {
+    let instance = self.next(name, volume);
+    let handle = SoundHandle::Effect { channel, id: instance.id };
+    self.effects.insert(channel, instance);
+    handle
+  }
+  /// Track music that replaces the current music
+  fn start_music(&mut self, name: &str, volume: f32) -> SoundHandle {
+    let instance = self.next(name, volume);
+    let handle = SoundHandle::Music { id: instance.id };
+    self.music = Some(instance);
+    handle
+  }
+  /// Get the instance a handle refers to if it is still playing
+  fn get(&self, handle: SoundHandle) -> Option<&Instance> {
+    match handle {
+      SoundHandle::Music { id } => self.music.as_ref().filter(|music| music.id == id),
+      SoundHandle::Effect { channel, id } => self.effects.get(&channel).filter(|effect| effect.id == id),
+    }
+  }
+  /// Count the playing instances of an effect
+  fn count(&self, name: &str) -> usize {
+    self.effects.values().filter(|effect| effect.name == name).count()
+  }
+  /// Get the channel of the first started instance of an effect
+  fn oldest(&self, name: &str) -> Option<i32> {
+    self.effects
+      .iter()
+      .filter(|(_, effect)| effect.name == name)
+      .min_by_key(|(_, effect)| effect.id)
+      .map(|(channel, ..)| *channel)
+  }
+  /// Forget the effects on channels that are no longer active
+  fn prune(&mut self, is_active: impl Fn(i32) -> bool) {
+    self.effects.retain(|channel, _| is_active(*channel));
+  }
+}
+
+/// Music waiting for the current music to fade out
+#[derive(Debug)]
+struct QueuedMusic {
+  name: AudioKey,
+  volume: f32,
+  fade: Duration,
+}
+
+/// Check if a channel is playing or paused
+fn is_channel_active(channel: i32) -> bool {
+  Channel(channel).is_playing() || Channel(channel).is_paused()
+}
+
+/// Resample interleaved samples to play at `pitch` times their speed, interpolating between frames
+fn resample(samples: &[i16], channels: usize, pitch: f32) -> Vec<i16> {
+  let frames = samples.len() / channels;
+  if frames == 0 { return Vec::new(); }
+  let length = (frames as f32 / pitch) as usize;
+  let mut resampled = Vec::with_capacity(length * channels);
+  for frame in 0..length {
+    let position = frame as f32 * pitch;
+    let index = (position as usize).min(frames - 1);
+    let next = (index + 1).min(frames - 1);
+    let fraction = position - index as f32;
+    for channel in 0..channels {
+      let from = samples[index * channels + channel] as f32;
+      let to = samples[next * channels + channel] as f32;
+      resampled.push((from + (to - from) * fraction).round() as i16);
+    }
+  }
+  resampled
+}
+
+/// Convert a duration to SDL_mixer milliseconds
+fn to_ms(duration: Duration) -> i32 {
+  duration.as_millis().min(i32::MAX as u128) as i32
+}
+
+// Player //
+
 /// load and play music and sfx
+///
+/// Effects play on any free channel, and the first started instance of an effect is stopped when starting another
+/// would exceed its instance cap. Only one music track plays at a time.
 pub struct AudioPlayer {
   store: AudioStore,
+  instances: Instances,
+  queued: Option<QueuedMusic>,
+  volumes: Volumes,
+  max_instances: HashMap<AudioKey, usize>,
 }
 
 impl AudioPlayer {
@@ -48,76 +258,292 @@ impl AudioPlayer {
     initialize_audio_subsystem().expect("Failed to initialize audio subsystem");
     Self {
       store: AudioStore::new(),
+      instances: Instances::default(),
+      queued: None,
+      volumes: Volumes::default(),
+      max_instances: HashMap::new(),
     }
   }
 
   /// Load a sfx or music file
   pub fn load(&mut self, sound_type: SoundType, filepath: impl AsRef<Path>) -> Result<AudioKey, String> {
     let basename = get_filename(&filepath)?;
+    let sound = match sound_type {
+      SoundType::Music => Sound::Music {
+        // music streams from its data while it plays, so the data lives as long as the game
+        data: sdl2::mixer::Music::from_static_bytes(Box::leak(vfs::read(&filepath)?.into_boxed_slice()))
+          .map_err(|e| format!("Failed to load music {}: {}", basename, e))?,
+      },
+      SoundType::Effect => Sound::Effect {
+        data: RWops::from_bytes(&vfs::read(&filepath)?)?
+          .load_wav()
+          .map_err(|e| format!("Failed to load sound effect {}: {}", basename, e))?,
+        variants: Vec::new(),
+      },
+    };
+    let audio = Audio {
+      sound,
+      name: basename,
+      path: filepath
+        .as_ref()
+        .to_path_buf()
+        .into_boxed_path(),
+    };
+    Ok(self.store.add(audio.name.clone(), audio).name.clone())
+  }
 
-    match sound_type {
-      SoundType::Music => {
-        let music =
-          sdl2::mixer::Music::from_file(&filepath).expect("Failed to load music");
-        let audio = Audio {
-          sound: Sound::Music { data: music },
-          name: basename.clone(),
-          path: filepath
-            .as_ref()
-            .to_path_buf()
-            .into_boxed_path(),
-        };
-        Ok(self.store.add(audio.name.clone(), audio).name.clone())
-      }
-      SoundType::Effect => {
-        let effect = sdl2::mixer::Chunk::from_file(&filepath)
-          .expect("Failed to load sound effect");
-        let audio = Audio {
-          sound: Sound::Effect { data: effect },
-          name: String::from(basename),
-          path: filepath
-            .as_ref()
-            .to_path_buf()
-            .into_boxed_path(),
-        };
-        Ok(self.store.add(audio.name.clone(), audio).name.clone())
-      }
+  /// Synthesize an effect and store it under `name`, replacing any sound of the same name
+  pub fn synthesize(&mut self, name: &str, params: &SynthParams, path: impl AsRef<Path>) -> Result<AudioKey, String> {
+    let (frequency, .., channels) = sdl2::mixer::query_spec()?;
+    let samples = synthesize(params, frequency as u32, channels as usize);
+    let data = sdl2::mixer::Chunk::from_raw_buffer(samples.into_boxed_slice())
+      .map_err(|e| format!("Failed to synthesize sound effect {}: {}", name, e))?;
+    let audio = Audio {
+      sound: Sound::Effect { data, variants: Vec::new() },
+      name: name.to_string(),
+      path: path.as_ref().to_path_buf().into_boxed_path(),
+    };
+    Ok(self.store.set(audio.name.clone(), audio).name.clone())
+  }
+  /// Synthesize a looping sequence as music and store it under `name`, replacing any sound of the same name
+  pub fn synthesize_music(&mut self, name: &str, sequence: &SynthSequence, presets: &HashMap<String, SynthParams>, path: impl AsRef<Path>) -> Result<AudioKey, String> {
+    let (frequency, .., channels) = sdl2::mixer::query_spec()?;
+    let samples = synthesize_sequence(sequence, presets, frequency as u32, channels as usize)
+      .map_err(|e| format!("Failed to synthesize music {}: {}", name, e))?;
+    let wav = encode_wav(&samples, frequency as u32, channels as u16);
+    let audio = Audio {
+      // music streams from its data while it plays, so the data lives as long as the game
+      sound: Sound::Music {
+        data: sdl2::mixer::Music::from_static_bytes(Box::leak(wav.into_boxed_slice()))
+          .map_err(|e| format!("Failed to synthesize music {}: {}", name, e))?,
+      },
+      name: name.to_string(),
+      path: path.as_ref().to_path_buf().into_boxed_path(),
+    };
+    Ok(self.store.set(audio.name.clone(), audio).name.clone())
+  }
+  /// Synthesize every effect of a synth preset file, stored under their preset names
+  pub fn load_synths(&mut self, filepath: impl AsRef<Path>) -> Result<Vec<AudioKey>, String> {
+    load_synth_presets(&filepath)?
+      .iter()
+      .map(|(name, params)| self.synthesize(name, params, &filepath))
+      .collect()
+  }
+
+  /// Check if a sfx or music is loaded
+  pub fn is_loaded(&self, name: &str) -> bool { self.store.store.contains_key(name) }
+
+  /// Vary the pitch of an effect each time it plays by up to `variation` of its pitch either way
+  pub fn set_pitch_variation(&mut self, name: &str, variation: f32) -> Result<(), String> {
+    let audio = self.store.get_mut(name.to_string())?;
+    let Sound::Effect { data, variants } = &mut audio.sound else {
+      return Err(format!("Cannot vary the pitch of music {}", name));
+    };
+
+    let variation = variation.clamp(0.0, 0.9);
+    if variation == 0.0 {
+      variants.clear();
+      return Ok(());
     }
+    // chunks are converted to the mixer format when loaded
+    let samples = unsafe { std::slice::from_raw_parts((*data.raw).abuf as *const i16, (*data.raw).alen as usize / 2) };
+    *variants = (0..PITCH_VARIANTS)
+      .map(|variant| {
+        let pitch = 1.0 - variation + 2.0 * variation * variant as f32 / (PITCH_VARIANTS - 1) as f32;
+        let resampled = resample(samples, OUTPUT_CHANNELS as usize, pitch);
+        sdl2::mixer::Chunk::from_raw_buffer(resampled.into_boxed_slice())
+      })
+      .collect::<Result<Vec<_>, _>>()
+      .map_err(|e| format!("Failed to vary the pitch of {}: {}", name, e))?;
+    Ok(())
+  }
+
+  /// Limit the instances of an effect that may play at once
+  pub fn set_max_instances(&mut self, name: &str, max: usize) {
+    self.max_instances.insert(name.to_string(), max.max(1));
   }
 
-  /// Play a sfx or music
-  pub fn play(&self, name: &str, volume: i32, looping: Loop) -> Result<(), String> {
+  // Playback //
+
+  /// Play a sfx or music at a volume in the unit interval, replacing the music if it is music
+  pub fn play(&mut self, name: &str, volume: f32, looping: Loop) -> Result<SoundHandle, String> {
+    self.start(name, volume, looping, None)
+  }
+  /// Play a sfx or music, fading it in from silence over `duration`
+  pub fn fade_in(&mut self, name: &str, volume: f32, looping: Loop, duration: Duration) -> Result<SoundHandle, String> {
+    self.start(name, volume, looping, Some(duration))
+  }
+  /// Start a sound, fading it in if a duration is given
+  fn start(&mut self, name: &str, volume: f32, looping: Loop, fade: Option<Duration>) -> Result<SoundHandle, String> {
     let audio = self.store.get(name.to_string())?;
-    let loops = match looping {
-      Loop::Forever => -1,
-      Loop::Once => 0,
-    };
+    let mixed = self.volumes.mix(audio.sound.get_type(), volume);
 
     match &audio.sound {
       Sound::Music { data } => {
-        sdl2::mixer::Music::set_volume(volume);
-        data.play(loops).map_err(|_| "Failed to play music")?;
-        Ok(())
+        sdl2::mixer::Music::set_volume(mixed);
+        match fade {
+          Some(fade) => data.fade_in(looping.get_loops(), to_ms(fade)),
+          None => data.play(looping.get_loops()),
+        }.map_err(|e| format!("Failed to play music {}: {}", name, e))?;
+        Ok(self.instances.start_music(name, volume))
       }
-      Sound::Effect { data } => {
-        let channel = sdl2::mixer::Channel::all().play(data, loops)?;
-        channel.set_volume(volume);
-        Ok(())
+      Sound::Effect { data, variants } => {
+        let data = if variants.is_empty() { data } else { &variants[random(0, variants.len())] };
+        self.instances.prune(is_channel_active);
+        let max = self.max_instances.get(name).copied().unwrap_or(DEFAULT_MAX_INSTANCES);
+        if self.instances.count(name) >= max {
+          if let Some(oldest) = self.instances.oldest(name) { Channel(oldest).halt(); }
+        }
+
+        let channel = match fade {
+          Some(fade) => Channel::all().fade_in(data, looping.get_loops(), to_ms(fade)),
+          None => Channel::all().play(data, looping.get_loops()),
+        }.map_err(|e| format!("Failed to play sound effect {}: {}", name, e))?;
+        channel.set_volume(mixed);
+        Ok(self.instances.start_effect(channel.0, name, volume))
       }
     }
   }
 
-  /// Stop a playing sfx or music
-  pub fn stop(&self, name: &str) -> Result<(), String> {
-    let audio = self.store.get(name.to_string())?;
-    match &audio.sound {
-      Sound::Music { data: _ } => sdl2::mixer::Music::halt(),
-      Sound::Effect { data: _ } => {
-        unimplemented!("Stopping sound effects is not yet implemented")
+  /// Check if the sound a handle refers to is still playing or paused
+  pub fn is_playing(&mut self, handle: SoundHandle) -> bool {
+    match handle {
+      SoundHandle::Music { .. } => {
+        let active = sdl2::mixer::Music::is_playing() || sdl2::mixer::Music::is_paused();
+        active && self.instances.get(handle).is_some()
+      }
+      SoundHandle::Effect { .. } => {
+        self.instances.prune(is_channel_active);
+        self.instances.get(handle).is_some()
+      }
+    }
+  }
+  /// Stop a playing sound, stale handles are ignored
+  pub fn stop(&mut self, handle: SoundHandle) {
+    if !self.is_playing(handle) { return; }
+    match handle {
+      SoundHandle::Music { .. } => {
+        sdl2::mixer::Music::halt();
+        self.instances.music = None;
+      }
+      SoundHandle::Effect { channel, .. } => {
+        Channel(channel).halt();
+        self.instances.effects.remove(&channel);
+      }
+    }
+  }
+  /// Pause a playing sound
+  pub fn pause(&mut self, handle: SoundHandle) {
+    if !self.is_playing(handle) { return; }
+    match handle {
+      SoundHandle::Music { .. } => sdl2::mixer::Music::pause(),
+      SoundHandle::Effect { channel, .. } => Channel(channel).pause(),
+    }
+  }
+  /// Resume a paused sound
+  pub fn resume(&mut self, handle: SoundHandle) {
+    if !self.is_playing(handle) { return; }
+    match handle {
+      SoundHandle::Music { .. } => sdl2::mixer::Music::resume(),
+      SoundHandle::Effect { channel, .. } => Channel(channel).resume(),
+    }
+  }
+  /// Fade a playing sound to silence over `duration`, then stop it
+  pub fn fade_out(&mut self, handle: SoundHandle, duration: Duration) -> Result<(), String> {
+    if !self.is_playing(handle) { return Ok(()); }
+    match handle {
+      SoundHandle::Music { .. } => sdl2::mixer::Music::fade_out(to_ms(duration))?,
+      SoundHandle::Effect { channel, .. } => { Channel(channel).fade_out(to_ms(duration)); }
+    }
+    Ok(())
+  }
+  // Music //
+
+  /// Get the music that is playing, or queued to play once the current music fades out
+  pub fn get_music(&self) -> Option<&str> {
+    if let Some(queued) = &self.queued { return Some(&queued.name); }
+    self.instances.music
+      .as_ref()
+      .filter(|_| sdl2::mixer::Music::is_playing())
+      .map(|music| music.name.as_str())
+  }
+  /// Get a handle to the music that is playing
+  pub fn get_music_handle(&self) -> Option<SoundHandle> {
+    self.instances.music
+      .as_ref()
+      .filter(|_| sdl2::mixer::Music::is_playing())
+      .map(|music| SoundHandle::Music { id: music.id })
+  }
+  /// Fade out the current music then fade in another, looping it forever, over `duration` in total
+  ///
+  /// SDL_mixer streams one track at a time, so the tracks never overlap: the current music fades out over the first
+  /// half of `duration` before the next fades in over the second half. Music that is already playing is left playing.
+  pub fn fade_to(&mut self, name: &str, volume: f32, duration: Duration) -> Result<(), String> {
+    self.store.get(name.to_string())?;
+    let half = duration / 2;
+    let fading_out = sdl2::mixer::Music::get_fading() == Fading::FadingOut;
+    match self.instances.music.as_ref().filter(|_| sdl2::mixer::Music::is_playing()) {
+      Some(music) if music.name == name && !fading_out => {
+        self.queued = None;
+      }
+      Some(..) => {
+        if !fading_out { sdl2::mixer::Music::fade_out(to_ms(half))?; }
+        self.queued = Some(QueuedMusic { name: name.to_string(), volume, fade: half });
       }
+      None => {
+        self.queued = None;
+        self.fade_in(name, volume, Loop::Forever, half)?;
+      }
+    }
+    Ok(())
+  }
+  /// Fade the music to silence over `duration`, forgetting any queued music
+  pub fn fade_out_music(&mut self, duration: Duration) -> Result<(), String> {
+    self.queued = None;
+    if sdl2::mixer::Music::is_playing() { sdl2::mixer::Music::fade_out(to_ms(duration))?; }
+    Ok(())
+  }
+  /// Start the queued music once the current music has faded out
+  pub fn update(&mut self) -> Result<(), String> {
+    if sdl2::mixer::Music::is_playing() { return Ok(()); }
+    if let Some(QueuedMusic { name, volume, fade }) = self.queued.take() {
+      self.fade_in(&name, volume, Loop::Forever, fade)?;
     }
     Ok(())
   }
+
+  /// Stop every instance of a sfx or music
+  pub fn stop_all(&mut self, name: &str) {
+    let handles = self.instances.effects
+      .iter()
+      .filter(|(_, effect)| effect.name == name)
+      .map(|(channel, effect)| SoundHandle::Effect { channel: *channel, id: effect.id })
+      .chain(self.instances.music.iter().filter(|music| music.name == name).map(|music| SoundHandle::Music { id: music.id }))
+      .collect::<Vec<_>>();
+    for handle in handles { self.stop(handle); }
+  }
+
+  // Volume //
+
+  /// Get the volume of every group
+  pub fn get_volumes(&self) -> Volumes { self.volumes }
+  /// Set the volume of a group in the unit interval
+  pub fn set_volume(&mut self, group: VolumeGroup, volume: f32) {
+    let mut volumes = self.volumes;
+    volumes.set(group, volume);
+    self.set_volumes(volumes);
+  }
+  /// Set the volume of every group, applying it to the sounds that are playing
+  pub fn set_volumes(&mut self, volumes: Volumes) {
+    self.volumes = volumes;
+    if let Some(music) = &self.instances.music {
+      sdl2::mixer::Music::set_volume(self.volumes.mix(SoundType::Music, music.volume));
+    }
+    self.instances.prune(is_channel_active);
+    for (channel, effect) in &self.instances.effects {
+      Channel(*channel).set_volume(self.volumes.mix(SoundType::Effect, effect.volume));
+    }
+  }
 }
 
 // Subsystem //
@@ -141,3 +567,45 @@ fn initialize_audio_subsystem() -> Result<(), String> {
   sdl2::mixer::allocate_channels(MIXER_CHANNELS);
   Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_mix_volume() {
+    let mut volumes = Volumes::default();
+    assert_eq!(volumes.mix(SoundType::Effect, 1.0), MAX_VOLUME);
+    volumes.set(VolumeGroup::Master, 0.5);
+    volumes.set(VolumeGroup::Sfx, 0.5);
+    assert_eq!(volumes.mix(SoundType::Effect, 1.0), MAX_VOLUME / 4, "groups multiply");
+    assert_eq!(volumes.mix(SoundType::Music, 1.0), MAX_VOLUME / 2, "music is not in the sfx group");
+    volumes.set(VolumeGroup::Music, 2.0);
+    assert_eq!(volumes.music, 1.0, "volume is clamped");
+  }
+
+  #[test]
+  fn test_instances() {
+    let mut instances = Instances::default();
+    let first = instances.start_effect(0, "shoot", 1.0);
+    instances.start_effect(1, "shoot", 1.0);
+    instances.start_effect(2, "hit", 1.0);
+    assert_eq!(instances.count("shoot"), 2);
+    assert_eq!(instances.oldest("shoot"), Some(0));
+
+    let reused = instances.start_effect(0, "hit", 1.0);
+    assert!(instances.get(first).is_none(), "handle is stale once its channel is reused");
+    assert!(instances.get(reused).is_some());
+
+    instances.prune(|channel| channel != 1);
+    assert_eq!(instances.count("shoot"), 0, "finished channels are forgotten");
+  }
+
+  #[test]
+  fn test_resample() {
+    let samples = [0, 100, 10, 110, 20, 120, 30, 130];
+    assert_eq!(resample(&samples, 2, 2.0), vec![0, 100, 20, 120], "higher pitch skips frames");
+    assert_eq!(resample(&samples, 2, 0.5).len(), 16, "lower pitch lengthens the sound");
+    assert_eq!(&resample(&samples, 2, 0.5)[..4], &[0, 100, 5, 105], "frames are interpolated");
+  }
+}
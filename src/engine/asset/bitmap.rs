@@ -0,0 +1,247 @@
+/**
+ * Bitmap fonts drawn glyph by glyph from image atlases
+ */
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::engine::asset::texture::{SrcRect, TextureKey, TextureLoader};
+use crate::engine::geometry::shape::Vec2;
+use crate::engine::utility::alias::Size;
+use crate::engine::utility::color::RGBA;
+use crate::engine::asset::vfs::read_to_string;
+
+// Metadata Format //
+
+/// A glyph of a BMFont file
+#[derive(Clone, Copy, Debug)]
+pub struct BitmapGlyph {
+  /// region of the glyph in its page
+  pub src: SrcRect,
+  /// offset from the pen position to draw the glyph at
+  pub offset: Vec2<i32>,
+  /// distance to move the pen after drawing the glyph
+  pub advance: i32,
+  pub page: usize,
+}
+
+/// Metadata of a BMFont text file
+#[derive(Clone, Debug, Default)]
+pub struct BitmapFontData {
+  pub face: String,
+  pub size: u16,
+  pub line_height: Size,
+  pub base: Size,
+  /// image files of the pages relative to the font file
+  pub pages: Vec<String>,
+  pub glyphs: HashMap<char, BitmapGlyph>,
+  pub kerning: HashMap<(char, char), i32>,
+}
+
+/// Split a line of a BMFont text file into its tag and `key=value` attributes, values may be quoted
+fn parse_tag(line: &str) -> Option<(&str, HashMap<&str, &str>)> {
+  let line = line.trim();
+  let (tag, mut rest) = line.split_once(' ').unwrap_or((line, ""));
+  let mut attributes = HashMap::new();
+  loop {
+    rest = rest.trim_start();
+    let Some((key, value)) = rest.split_once('=') else { break; };
+    let (value, remaining) = if let Some(quoted) = value.strip_prefix('"') {
+      quoted.split_once('"').unwrap_or((quoted, ""))
+    } else {
+      value.split_once(' ').unwrap_or((value, ""))
+    };
+    attributes.insert(key, value);
+    rest = remaining;
+  }
+  if tag.is_empty() { None } else { Some((tag, attributes)) }
+}
+
+/// Parse an attribute of a BMFont tag
+fn parse_attribute<T: std::str::FromStr>(tag: &str, attributes: &HashMap<&str, &str>, key: &str) -> Result<T, String> {
+  attributes
+    .get(key)
+    .ok_or(format!("'{}' is missing '{}'", tag, key))?
+    .parse::<T>()
+    .map_err(|_| format!("'{}' has an invalid '{}'", tag, key))
+}
+
+/// Parse a character id of a BMFont tag
+fn parse_char(tag: &str, attributes: &HashMap<&str, &str>, key: &str) -> Result<char, String> {
+  let id = parse_attribute::<u32>(tag, attributes, key)?;
+  char::from_u32(id).ok_or(format!("'{}' has an invalid character {}", tag, id))
+}
+
+/// Parse the BMFont text format, see https://www.angelcode.com/products/bmfont/doc/file_format.html
+pub fn parse_bmfont(data: &str) -> Result<BitmapFontData, String> {
+  let mut font = BitmapFontData::default();
+  for (tag, attributes) in data.lines().filter_map(parse_tag) {
+    match tag {
+      "info" => {
+        font.face = attributes.get("face").map(|face| face.to_string()).unwrap_or_default();
+        font.size = parse_attribute::<i32>(tag, &attributes, "size")?.unsigned_abs() as u16;
+      }
+      "common" => {
+        font.line_height = parse_attribute(tag, &attributes, "lineHeight")?;
+        font.base = parse_attribute(tag, &attributes, "base")?;
+      }
+      "page" => {
+        let id = parse_attribute::<usize>(tag, &attributes, "id")?;
+        let file = attributes.get("file").ok_or(String::from("'page' is missing 'file'"))?;
+        if font.pages.len() <= id { font.pages.resize(id + 1, String::new()); }
+        font.pages[id] = file.to_string();
+      }
+      "char" => {
+        let glyph = BitmapGlyph {
+          src: SrcRect::new(
+            Vec2::new(parse_attribute(tag, &attributes, "x")?, parse_attribute(tag, &attributes, "y")?),
+            Vec2::new(parse_attribute(tag, &attributes, "width")?, parse_attribute(tag, &attributes, "height")?),
+          ),
+          offset: Vec2::new(parse_attribute(tag, &attributes, "xoffset")?, parse_attribute(tag, &attributes, "yoffset")?),
+          advance: parse_attribute(tag, &attributes, "xadvance")?,
+          page: parse_attribute(tag, &attributes, "page").unwrap_or(0),
+        };
+        font.glyphs.insert(parse_char(tag, &attributes, "id")?, glyph);
+      }
+      "kerning" => {
+        let pair = (parse_char(tag, &attributes, "first")?, parse_char(tag, &attributes, "second")?);
+        font.kerning.insert(pair, parse_attribute(tag, &attributes, "amount")?);
+      }
+      _ => {} // chars and kernings counts are not needed
+    }
+  }
+
+  if font.pages.iter().any(String::is_empty) { return Err(String::from("Font has a missing page")); }
+  if let Some((char, ..)) = font.glyphs.iter().find(|(_, glyph)| glyph.page >= font.pages.len()) {
+    return Err(format!("Character '{}' is on a missing page", char));
+  }
+  Ok(font)
+}
+
+// Font //
+
+/// A glyph to draw from a page texture at a position relative to its text
+#[derive(Clone, Copy, Debug)]
+pub struct PlacedGlyph {
+  pub texture: TextureKey,
+  pub src: SrcRect,
+  pub position: Vec2<i32>,
+  pub color: RGBA,
+}
+
+/// A font of glyphs on page textures
+#[derive(Clone, Debug)]
+pub struct BitmapFont {
+  pages: Vec<TextureKey>,
+  data: BitmapFontData,
+}
+
+impl BitmapFont {
+  /// Instantiate a new bitmap font from its metadata and a texture for each page
+  pub fn new(pages: Vec<TextureKey>, data: BitmapFontData) -> Self {
+    Self { pages, data }
+  }
+
+  /// Get the distance between lines
+  pub fn get_line_height(&self) -> Size { self.data.line_height }
+  /// Get the point size of the font
+  pub fn get_size(&self) -> u16 { self.data.size }
+
+  /// Get the glyphs of `text` with the distance to move the pen before each, characters without glyphs are skipped
+  fn glyphs<'a>(&'a self, text: &'a str) -> impl Iterator<Item=(&'a BitmapGlyph, i32)> + 'a {
+    let mut previous = None;
+    text.chars().filter_map(move |char| {
+      let glyph = self.data.glyphs.get(&char)?;
+      let kerning = previous
+        .and_then(|previous| self.data.kerning.get(&(previous, char)))
+        .copied()
+        .unwrap_or(0);
+      previous = Some(char);
+      Some((glyph, kerning))
+    })
+  }
+
+  /// Get the width of a line of text
+  pub fn measure(&self, text: &str) -> Size {
+    self
+      .glyphs(text)
+      .map(|(glyph, kerning)| kerning + glyph.advance)
+      .sum::<i32>()
+      .max(0) as Size
+  }
+
+  /// Place the glyphs of a line of text starting at `pen` and return the pen after the last glyph
+  pub fn place(&self, text: &str, mut pen: Vec2<i32>, color: RGBA, placed: &mut Vec<PlacedGlyph>) -> Vec2<i32> {
+    for (glyph, kerning) in self.glyphs(text) {
+      pen.x += kerning;
+      if glyph.src.size.x > 0 && glyph.src.size.y > 0 {
+        placed.push(PlacedGlyph {
+          texture: self.pages[glyph.page],
+          src: glyph.src,
+          position: pen + glyph.offset,
+          color,
+        });
+      }
+      pen.x += glyph.advance;
+    }
+    pen
+  }
+}
+
+/// Load a bitmap font from a BMFont text file and its page images
+pub fn load_bitmap_font(filepath: impl AsRef<Path>, textures: &mut TextureLoader) -> Result<BitmapFont, String> {
+  let filepath = filepath.as_ref();
+  let data = parse_bmfont(&read_to_string(filepath)?).map_err(|e| format!("Failed to parse font {}: {}", filepath.display(), e))?;
+  let directory = filepath.parent().unwrap_or(Path::new(""));
+  let pages = data.pages
+    .iter()
+    .map(|page| textures.load(directory.join(page)))
+    .collect::<Result<Vec<_>, _>>()?;
+  Ok(BitmapFont::new(pages, data))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const FONT: &str = r#"info face="Numerals Font" size=10 bold=0 italic=0
+common lineHeight=12 base=10 scaleW=96 scaleH=10 pages=1 packed=0
+page id=0 file="numerals.png"
+chars count=3
+char id=32   x=0    y=0    width=0    height=0    xoffset=0    yoffset=0    xadvance=4    page=0  chnl=15
+char id=49   x=0    y=0    width=6    height=10   xoffset=0    yoffset=0    xadvance=8    page=0  chnl=15
+char id=50   x=7    y=0    width=6    height=10   xoffset=1    yoffset=0    xadvance=8    page=0  chnl=15
+kernings count=1
+kerning first=49 second=50 amount=-2
+"#;
+
+  #[test]
+  fn test_parse_bmfont() {
+    let data = parse_bmfont(FONT).unwrap();
+    assert_eq!(data.face, "Numerals Font", "quoted values may contain spaces");
+    assert_eq!((data.size, data.line_height, data.base), (10, 12, 10));
+    assert_eq!(data.pages, vec![String::from("numerals.png")]);
+    assert_eq!(data.glyphs.len(), 3);
+    assert_eq!(data.glyphs[&'2'].offset, Vec2::new(1, 0));
+    assert_eq!(data.kerning[&('1', '2')], -2);
+  }
+
+  #[test]
+  fn test_place_glyphs() {
+    let font = BitmapFont::new(vec![0], parse_bmfont(FONT).unwrap());
+    assert_eq!(font.measure("12"), 14, "kerning pulls the second glyph closer");
+    assert_eq!(font.measure("1 ?"), 12, "missing characters are skipped");
+
+    let mut placed = Vec::new();
+    let pen = font.place("1 2", Vec2::new(0, 4), RGBA::default(), &mut placed);
+    assert_eq!(placed.len(), 2, "spaces have no glyph to draw");
+    assert_eq!(placed[1].position, Vec2::new(13, 4), "glyph is offset from the pen");
+    assert_eq!(pen, Vec2::new(20, 4));
+  }
+
+  #[test]
+  fn test_missing_page() {
+    let font = "common lineHeight=12 base=10\nchar id=49 x=0 y=0 width=6 height=10 xoffset=0 yoffset=0 xadvance=8 page=1";
+    assert!(parse_bmfont(font).is_err(), "glyph is on a page that does not exist");
+  }
+}
@@ -0,0 +1,147 @@
+/**
+ * Lists of assets to load ahead of the scenes and rooms that use them
+ */
+
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::engine::asset::asset::AssetManager;
+use crate::engine::asset::vfs;
+
+/// An asset listed in a manifest
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ManifestEntry {
+  Texture(PathBuf),
+  /// sheet metadata and the texture it describes
+  Sheet(PathBuf),
+}
+
+impl ManifestEntry {
+  /// Get the file the asset is loaded from
+  pub fn get_path(&self) -> &Path {
+    match self {
+      ManifestEntry::Texture(path) | ManifestEntry::Sheet(path) => path,
+    }
+  }
+  /// Load the asset, referencing it in the active texture scope
+  fn load(&self, asset: &mut AssetManager) -> Result<(), String> {
+    match self {
+      ManifestEntry::Texture(path) => asset.texture.load(path).map(|_| ()),
+      ManifestEntry::Sheet(path) => asset.sheet.load(path, &mut asset.texture).map(|_| ()),
+    }
+  }
+}
+
+/// A list of assets without duplicates
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AssetManifest {
+  entries: Vec<ManifestEntry>,
+}
+
+impl AssetManifest {
+  /// Add an entry if it is not already listed
+  fn push(&mut self, entry: ManifestEntry) {
+    if !self.entries.contains(&entry) { self.entries.push(entry); }
+  }
+  /// List a texture
+  pub fn with_texture(mut self, filepath: impl Into<PathBuf>) -> Self {
+    self.push(ManifestEntry::Texture(filepath.into()));
+    self
+  }
+  /// List a sprite sheet
+  pub fn with_sheet(mut self, filepath: impl Into<PathBuf>) -> Self {
+    self.push(ManifestEntry::Sheet(filepath.into()));
+    self
+  }
+  /// List the entries of another manifest
+  pub fn with_manifest(mut self, manifest: AssetManifest) -> Self {
+    self.extend(manifest);
+    self
+  }
+  /// List the entries of another manifest
+  pub fn extend(&mut self, manifest: AssetManifest) {
+    for entry in manifest.entries { self.push(entry); }
+  }
+
+  /// Get the listed entries
+  pub fn get_entries(&self) -> &[ManifestEntry] { &self.entries }
+  /// Get the number of listed entries
+  pub fn len(&self) -> usize { self.entries.len() }
+  /// Check if no entries are listed
+  pub fn is_empty(&self) -> bool { self.entries.is_empty() }
+
+  /// Get the listed files that do not exist
+  pub fn get_missing(&self) -> Vec<&Path> {
+    self.entries
+      .iter()
+      .map(ManifestEntry::get_path)
+      .filter(|path| !vfs::exists(path))
+      .collect()
+  }
+  /// Check every listed file exists, reporting each that is missing
+  pub fn validate(&self) -> Result<(), String> {
+    let missing = self.get_missing();
+    if missing.is_empty() { return Ok(()); }
+    let list = missing.iter().map(|path| format!("\n  {}", path.display())).collect::<String>();
+    Err(format!("Missing {} of {} assets:{}", missing.len(), self.len(), list))
+  }
+}
+
+/// Loads the entries of a manifest a few at a time
+#[derive(Debug)]
+pub struct Preloader {
+  queue: VecDeque<ManifestEntry>,
+  total: usize,
+}
+
+impl Preloader {
+  /// Instantiate a new preloader of a manifest
+  pub fn new(manifest: AssetManifest) -> Self {
+    Self { total: manifest.len(), queue: manifest.entries.into() }
+  }
+  /// Load entries until the budget is spent, loading at least one
+  pub fn step(&mut self, asset: &mut AssetManager, budget: Duration) -> Result<(), String> {
+    let start = Instant::now();
+    while let Some(entry) = self.queue.pop_front() {
+      entry.load(asset).map_err(|e| format!("Failed to preload {}: {}", entry.get_path().display(), e))?;
+      if start.elapsed() >= budget { break; }
+    }
+    Ok(())
+  }
+  /// Load every remaining entry
+  pub fn finish(&mut self, asset: &mut AssetManager) -> Result<(), String> {
+    self.step(asset, Duration::MAX)
+  }
+  /// Check if every entry is loaded
+  pub fn is_done(&self) -> bool { self.queue.is_empty() }
+  /// Get the portion of entries loaded in the unit interval
+  pub fn get_progress(&self) -> f32 {
+    if self.total == 0 { return 1.0; }
+    (self.total - self.queue.len()) as f32 / self.total as f32
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_deduplicate() {
+    let manifest = AssetManifest::default()
+      .with_sheet("asset/sprite/zoomer.json")
+      .with_manifest(AssetManifest::default().with_sheet("asset/sprite/zoomer.json").with_texture("asset/hud/menu_pane.png"));
+    assert_eq!(manifest.len(), 2, "sheet is listed once");
+  }
+
+  #[test]
+  fn test_missing() {
+    let manifest = AssetManifest::default()
+      .with_texture("Cargo.toml")
+      .with_texture("asset/missing.png")
+      .with_sheet("asset/missing.json");
+    assert_eq!(manifest.get_missing(), vec![Path::new("asset/missing.png"), Path::new("asset/missing.json")]);
+    let error = manifest.validate().unwrap_err();
+    assert!(error.starts_with("Missing 2 of 3 assets"), "every missing file is reported");
+  }
+}
@@ -1,5 +1,11 @@
 pub mod audio;
 pub mod texture;
+pub mod sheet;
 pub mod typography;
+pub mod bitmap;
 pub mod asset;
 
+pub mod watch;
+pub mod manifest;
+pub mod synth;
+pub mod vfs;
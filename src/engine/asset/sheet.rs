@@ -0,0 +1,454 @@
+/**
+ * Sprite sheet metadata loading, storage, and retrieval
+ */
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::de::{IgnoredAny, MapAccess, Visitor};
+use serde::{Deserialize, Deserializer};
+
+use crate::engine::asset::texture::{SrcRect, TextureKey, TextureLoader};
+use crate::engine::geometry::shape::Vec2;
+use crate::engine::utility::alias::{Size, Size2};
+use crate::engine::asset::vfs::read_to_string;
+use crate::engine::utility::io::deserialize_json;
+use crate::engine::utility::store::{next_key, Store};
+
+pub type SheetKey = usize;
+
+/// store sprite sheets
+pub type SheetStore = Store<SheetKey, SpriteSheet>;
+
+/// Duration of a frame in a grid sheet that does not specify one
+pub const DEFAULT_FRAME_MS: u64 = 100;
+
+// Metadata Formats //
+
+/// A rectangle in a sheet file
+#[derive(Deserialize, Clone, Copy, Debug)]
+struct SheetRect {
+  #[serde(default)]
+  x: Size,
+  #[serde(default)]
+  y: Size,
+  w: Size,
+  h: Size,
+}
+
+impl From<SheetRect> for SrcRect {
+  fn from(rect: SheetRect) -> Self {
+    SrcRect::new(Vec2::new(rect.x, rect.y), Vec2::new(rect.w, rect.h))
+  }
+}
+
+/// The order frames of a clip are played in
+#[derive(Deserialize, Default, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ClipDirection {
+  #[default]
+  Forward,
+  Reverse,
+  #[serde(rename = "pingpong")]
+  PingPong,
+  #[serde(rename = "pingpong_reverse")]
+  PingPongReverse,
+}
+
+/// A frame of an Aseprite JSON export
+#[derive(Deserialize, Debug)]
+struct AsepriteFrame {
+  #[serde(default)]
+  filename: String,
+  frame: SheetRect,
+  duration: u64,
+}
+
+/// A tagged range of frames in an Aseprite JSON export
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct AsepriteTag {
+  name: String,
+  from: usize,
+  to: usize,
+  #[serde(default)]
+  direction: ClipDirection,
+  /// number of times to play the tag, absent or `"0"` when the tag loops forever
+  repeat: Option<String>,
+}
+
+/// Metadata of an Aseprite JSON export
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct AsepriteMeta {
+  image: String,
+  #[serde(default)]
+  frame_tags: Vec<AsepriteTag>,
+}
+
+/// An Aseprite JSON export in either the *hash* or *array* layout
+#[derive(Deserialize, Debug)]
+struct AsepriteSheet {
+  #[serde(deserialize_with = "deserialize_aseprite_frames")]
+  frames: Vec<AsepriteFrame>,
+  meta: AsepriteMeta,
+}
+
+/// Aseprite frames keyed by filename, or listed in order
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AsepriteFrames {
+  Array(Vec<AsepriteFrame>),
+  #[serde(deserialize_with = "deserialize_ordered_frames")]
+  Hash(Vec<AsepriteFrame>),
+}
+
+fn deserialize_aseprite_frames<'de, D>(deserializer: D) -> Result<Vec<AsepriteFrame>, D::Error> where D: Deserializer<'de> {
+  match AsepriteFrames::deserialize(deserializer)? {
+    AsepriteFrames::Array(frames) | AsepriteFrames::Hash(frames) => Ok(frames),
+  }
+}
+
+/// Deserialize a map of frames keeping the order they appear in the file
+fn deserialize_ordered_frames<'de, D>(deserializer: D) -> Result<Vec<AsepriteFrame>, D::Error> where D: Deserializer<'de> {
+  struct OrderedFrames;
+  impl<'de> Visitor<'de> for OrderedFrames {
+    type Value = Vec<AsepriteFrame>;
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+      formatter.write_str("a map of frames keyed by filename")
+    }
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error> where A: MapAccess<'de> {
+      let mut frames = Vec::new();
+      while let Some((filename, mut frame)) = map.next_entry::<String, AsepriteFrame>()? {
+        frame.filename = filename;
+        frames.push(frame);
+      }
+      Ok(frames)
+    }
+  }
+  deserializer.deserialize_map(OrderedFrames)
+}
+
+/// A clip of a grid sheet
+#[derive(Deserialize, Debug)]
+struct GridClip {
+  from: usize,
+  to: usize,
+  #[serde(default)]
+  direction: ClipDirection,
+  #[serde(default = "default_repeat")]
+  repeat: bool,
+  /// overrides the frame duration of the sheet
+  duration: Option<u64>,
+}
+
+fn default_repeat() -> bool { true }
+fn default_frame_ms() -> u64 { DEFAULT_FRAME_MS }
+
+/// Our own sheet format, an image divided into a grid of equally sized frames
+///
+/// Frames are numbered left to right, top to bottom, and may be given names
+#[derive(Deserialize, Debug)]
+struct GridSheet {
+  image: String,
+  frame: SheetRect,
+  #[serde(default = "default_frame_ms")]
+  duration: u64,
+  #[serde(default)]
+  names: Vec<String>,
+  #[serde(default)]
+  clips: HashMap<String, GridClip>,
+}
+
+// Sprite Sheet //
+
+/// A named source rect of a sheet
+#[derive(Clone, Debug)]
+pub struct SheetFrame {
+  pub name: String,
+  pub src: SrcRect,
+  pub duration: Duration,
+}
+
+/// A named series of frames of a sheet
+#[derive(Clone, Debug)]
+pub struct SheetClip {
+  pub frames: Vec<usize>,
+  pub repeat: bool,
+}
+
+/// A texture divided into named frames and clips
+#[derive(Clone, Debug)]
+pub struct SpriteSheet {
+  pub texture: TextureKey,
+  frames: Vec<SheetFrame>,
+  clips: HashMap<String, SheetClip>,
+}
+
+/// Parsed sheet metadata that is yet to be bound to a texture
+pub struct SheetData {
+  pub image: String,
+  format: SheetFormat,
+}
+
+enum SheetFormat {
+  Aseprite(AsepriteSheet),
+  Grid(GridSheet),
+}
+
+/// The key that tells an Aseprite export apart from a grid sheet
+#[derive(Deserialize)]
+struct SheetProbe {
+  meta: Option<IgnoredAny>,
+}
+
+/// Parse sprite sheet metadata from an Aseprite JSON export, or our own grid format
+///
+/// Sheets are deserialized straight from the text, as a `serde_json::Value` would sort the frames of a *hash* export.
+pub fn parse_sheet(data: &str) -> Result<SheetData, String> {
+  let probe = deserialize_json::<SheetProbe>(data)?;
+  if probe.meta.is_some() {
+    let sheet = deserialize_json::<AsepriteSheet>(data).map_err(|e| format!("Invalid Aseprite sheet: {}", e))?;
+    Ok(SheetData { image: sheet.meta.image.clone(), format: SheetFormat::Aseprite(sheet) })
+  } else {
+    let sheet = deserialize_json::<GridSheet>(data).map_err(|e| format!("Invalid grid sheet: {}", e))?;
+    Ok(SheetData { image: sheet.image.clone(), format: SheetFormat::Grid(sheet) })
+  }
+}
+
+/// Expand a range of frames into the order they are played
+fn expand_clip(from: usize, to: usize, direction: ClipDirection, frame_count: usize) -> Result<Vec<usize>, String> {
+  if from > to || to >= frame_count {
+    return Err(format!("Clip range {}..={} is outside the {} frames of the sheet", from, to, frame_count));
+  }
+  let forward = (from..=to).collect::<Vec<_>>();
+  let reverse = forward.iter().rev().copied().collect::<Vec<_>>();
+  // the ends of a ping-pong are not repeated
+  let bounce = |there: &Vec<usize>, back: &Vec<usize>| {
+    let mut frames = there.clone();
+    if back.len() > 2 { frames.extend(&back[1..back.len() - 1]); }
+    frames
+  };
+  Ok(match direction {
+    ClipDirection::Forward => forward,
+    ClipDirection::Reverse => reverse,
+    ClipDirection::PingPong => bounce(&forward, &reverse),
+    ClipDirection::PingPongReverse => bounce(&reverse, &forward),
+  })
+}
+
+impl SpriteSheet {
+  /// Bind parsed sheet metadata to the texture it describes
+  pub fn build(texture: TextureKey, texture_dimensions: Size2, data: SheetData) -> Result<Self, String> {
+    let (frames, clips) = match data.format {
+      SheetFormat::Aseprite(sheet) => {
+        let frames = sheet.frames
+          .into_iter()
+          .map(|frame| SheetFrame { name: frame.filename, src: frame.frame.into(), duration: Duration::from_millis(frame.duration) })
+          .collect::<Vec<_>>();
+        let clips = sheet.meta.frame_tags
+          .into_iter()
+          .map(|tag| {
+            let clip = SheetClip {
+              frames: expand_clip(tag.from, tag.to, tag.direction, frames.len())?,
+              repeat: tag.repeat.as_deref().map_or(true, |repeat| repeat == "0"),
+            };
+            Ok((tag.name, clip))
+          })
+          .collect::<Result<HashMap<_, _>, String>>()?;
+        (frames, clips)
+      }
+      SheetFormat::Grid(sheet) => {
+        let SheetRect { w, h, .. } = sheet.frame;
+        if w == 0 || h == 0 { return Err(String::from("Grid frame must have a size")); }
+        if texture_dimensions.x % w != 0 || texture_dimensions.y % h != 0 {
+          return Err(format!("Grid frame {}x{} does not divide evenly into {}x{} texture", w, h, texture_dimensions.x, texture_dimensions.y));
+        }
+        let columns = texture_dimensions.x / w;
+        let rows = texture_dimensions.y / h;
+        let mut frames = (0..rows * columns)
+          .map(|index| SheetFrame {
+            name: sheet.names.get(index as usize).cloned().unwrap_or_else(|| index.to_string()),
+            src: SrcRect::new(Vec2::new(index % columns * w, index / columns * h), Vec2::new(w, h)),
+            duration: Duration::from_millis(sheet.duration),
+          })
+          .collect::<Vec<_>>();
+        let mut clips = HashMap::new();
+        for (name, clip) in sheet.clips {
+          let clip_frames = expand_clip(clip.from, clip.to, clip.direction, frames.len())?;
+          if let Some(duration) = clip.duration {
+            // frames shared between clips take the duration of the last clip that claims them
+            for index in &clip_frames { frames[*index].duration = Duration::from_millis(duration); }
+          }
+          clips.insert(name, SheetClip { frames: clip_frames, repeat: clip.repeat });
+        }
+        (frames, clips)
+      }
+    };
+
+    if frames.is_empty() { return Err(String::from("Sprite sheet has no frames")); }
+    Ok(Self { texture, frames, clips })
+  }
+
+  /// Get a frame by name
+  pub fn get_frame(&self, name: &str) -> Result<&SheetFrame, String> {
+    self.frames
+      .iter()
+      .find(|frame| frame.name == name)
+      .ok_or(format!("Sprite sheet has no frame '{}'", name))
+  }
+  /// Get the first frame of the sheet
+  pub fn get_first_frame(&self) -> &SheetFrame { &self.frames[0] }
+  /// Get a clip by name
+  pub fn get_clip(&self, name: &str) -> Result<&SheetClip, String> {
+    self.clips
+      .get(name)
+      .ok_or(format!("Sprite sheet has no clip '{}'", name))
+  }
+  /// Get the names of the clips of the sheet
+  pub fn get_clip_names(&self) -> impl Iterator<Item=&String> { self.clips.keys() }
+  /// Get the frames of a clip in the order they are played
+  pub fn get_clip_frames(&self, name: &str) -> Result<impl Iterator<Item=&SheetFrame>, String> {
+    let clip = self.get_clip(name)?;
+    Ok(clip.frames.iter().map(|index| &self.frames[*index]))
+  }
+}
+
+// Loader //
+
+/// Load and store sprite sheets
+pub struct SheetLoader {
+  store: SheetStore,
+  paths: HashMap<PathBuf, SheetKey>,
+}
+
+impl SheetLoader {
+  /// Instantiate a new sheet loader
+  pub fn new() -> Self {
+    Self { store: SheetStore::new(), paths: HashMap::new() }
+  }
+
+  /// Load sheet metadata from a file, and the texture it describes relative to the file
+  ///
+  /// A sheet already loaded from the file is reused while its texture is loaded, adding a reference to the texture.
+  pub fn load(&mut self, filepath: impl AsRef<Path>, textures: &mut TextureLoader) -> Result<SheetKey, String> {
+    let filepath = filepath.as_ref();
+    if let Some(key) = self.paths.get(filepath) {
+      let texture = self.store.get(*key)?.texture;
+      if textures.is_loaded(texture) {
+        textures.retain(texture)?;
+        return Ok(*key);
+      }
+      self.store.store.remove(key); // the texture was released, so the sheet is loaded again
+    }
+
+    let sheet = Self::build(filepath, textures)?;
+    let key = next_key();
+    self.store.add(key, sheet);
+    self.paths.insert(filepath.to_path_buf(), key);
+
+    Ok(key)
+  }
+  /// Parse sheet metadata again, replacing the sheet in place so its key remains valid
+  ///
+  /// The sheet is bound to the texture it already references, so no reference is added. Returns false if no sheet is
+  /// loaded from the file. Components built from the sheet keep their frames.
+  pub fn reload(&mut self, filepath: impl AsRef<Path>, textures: &mut TextureLoader) -> Result<bool, String> {
+    let filepath = filepath.as_ref();
+    let Some(key) = self.paths.get(filepath).copied() else { return Ok(false); };
+    let texture = self.store.get(key)?.texture;
+    let (data, image) = Self::parse(filepath)?;
+    if textures.find(&image) != Some(texture) {
+      return Err(format!("Sheet {} changed its image, which is only loaded with its scene", filepath.display()));
+    }
+    let sheet = Self::bind(filepath, data, texture, textures)?;
+    self.store.set(key, sheet);
+    Ok(true)
+  }
+  /// Parse sheet metadata from a file and bind it to the texture it describes
+  fn build(filepath: &Path, textures: &mut TextureLoader) -> Result<SpriteSheet, String> {
+    let (data, image) = Self::parse(filepath)?;
+    let texture = textures.load(&image)?;
+    Self::bind(filepath, data, texture, textures)
+  }
+  /// Parse sheet metadata from a file, and get the path of its image
+  fn parse(filepath: &Path) -> Result<(SheetData, PathBuf), String> {
+    let data = parse_sheet(&read_to_string(filepath)?).map_err(|e| format!("Failed to parse sheet {}: {}", filepath.display(), e))?;
+    let image = filepath.parent().unwrap_or(Path::new("")).join(&data.image);
+    Ok((data, image))
+  }
+  /// Bind sheet metadata to a loaded texture
+  fn bind(filepath: &Path, data: SheetData, texture: TextureKey, textures: &mut TextureLoader) -> Result<SpriteSheet, String> {
+    let dimensions = textures.use_store().get(texture)?.dimensions;
+    SpriteSheet::build(texture, dimensions, data).map_err(|e| format!("Failed to build sheet {}: {}", filepath.display(), e))
+  }
+  /// Get the files sheets are loaded from
+  pub fn get_paths(&self) -> impl Iterator<Item=&Path> { self.paths.keys().map(PathBuf::as_path) }
+
+  /// Get a loaded sheet
+  pub fn get(&self, key: SheetKey) -> Result<&SpriteSheet, String> { self.store.get(key) }
+
+  /// Returns an immutable reference to the store
+  pub fn use_store(&self) -> &SheetStore { &self.store }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_grid_sheet() {
+    let data = parse_sheet(r#"{
+      "image": "cursor.png",
+      "frame": { "w": 5, "h": 5 },
+      "names": ["idle"],
+      "clips": { "blink": { "from": 0, "to": 3, "duration": 150 } }
+    }"#).unwrap();
+    let sheet = SpriteSheet::build(0, Size2::new(20, 10), data).unwrap();
+
+    assert_eq!(sheet.get_frame("idle").unwrap().src.origin, Vec2::new(0, 0), "first frame is named");
+    let wrapped = sheet.get_frame("5").unwrap().src;
+    assert_eq!((wrapped.origin, wrapped.size), (Vec2::new(5, 5), Vec2::new(5, 5)), "frames wrap onto the next row");
+    assert_eq!(sheet.get_frame("6").unwrap().duration, Duration::from_millis(DEFAULT_FRAME_MS), "frames outside clips use the sheet duration");
+
+    let blink = sheet.get_clip_frames("blink").unwrap().collect::<Vec<_>>();
+    assert_eq!(blink.len(), 4, "clip has 4 frames");
+    assert!(blink.iter().all(|frame| frame.duration == Duration::from_millis(150)), "clip overrides the frame duration");
+    assert!(sheet.get_clip("blink").unwrap().repeat, "clips repeat by default");
+  }
+
+  #[test]
+  fn test_aseprite_sheet() {
+    let data = parse_sheet(r#"{
+      "frames": {
+        "zoomer 10.aseprite": { "frame": { "x": 32, "y": 0, "w": 16, "h": 16 }, "duration": 200 },
+        "zoomer 2.aseprite": { "frame": { "x": 0, "y": 0, "w": 16, "h": 16 }, "duration": 100 },
+        "zoomer 1.aseprite": { "frame": { "x": 16, "y": 0, "w": 16, "h": 16 }, "duration": 100 }
+      },
+      "meta": {
+        "image": "zoomer.png",
+        "frameTags": [
+          { "name": "crawl", "from": 0, "to": 2, "direction": "pingpong" },
+          { "name": "die", "from": 1, "to": 2, "direction": "reverse", "repeat": "1" }
+        ]
+      }
+    }"#).unwrap();
+    assert_eq!(data.image, "zoomer.png");
+    let sheet = SpriteSheet::build(0, Size2::new(48, 16), data).unwrap();
+
+    assert_eq!(sheet.get_first_frame().name, "zoomer 10.aseprite", "hash frames keep the order of the file");
+    let crawl = sheet.get_clip_frames("crawl").unwrap().map(|frame| frame.src.origin.x).collect::<Vec<_>>();
+    assert_eq!(crawl, vec![32, 0, 16, 0], "ping-pong plays back without repeating its ends");
+    let die = sheet.get_clip("die").unwrap();
+    assert_eq!(die.frames, vec![2, 1], "reverse plays backwards");
+    assert!(!die.repeat, "tags with a repeat count do not loop");
+  }
+
+  #[test]
+  fn test_invalid_clip() {
+    let data = parse_sheet(r#"{ "image": "a.png", "frame": { "w": 8, "h": 8 }, "clips": { "run": { "from": 0, "to": 4 } } }"#).unwrap();
+    assert!(SpriteSheet::build(0, Size2::new(16, 8), data).is_err(), "clip is outside the sheet");
+  }
+}
@@ -0,0 +1,343 @@
+/**
+ * Synthesize sound effects from parameter sets, in the style of sfxr
+ */
+
+use std::collections::HashMap;
+use std::f32::consts::TAU;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::engine::asset::vfs::read_to_string;
+use crate::engine::utility::io::deserialize_json;
+
+/// Shape of the oscillator
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Waveform {
+  #[default]
+  Square,
+  Sawtooth,
+  Sine,
+  Triangle,
+  /// a random level held for each period of the oscillator
+  Noise,
+}
+
+/// Parameters of a synthesized sound, every field is optional in a data file
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SynthParams {
+  pub waveform: Waveform,
+  /// starting frequency in hertz
+  pub frequency: f32,
+  /// change in frequency in octaves per second
+  pub slide: f32,
+  /// the sound ends once the frequency slides below this in hertz
+  pub min_frequency: f32,
+  /// fraction of each period a square wave is high
+  pub duty: f32,
+  /// depth of the vibrato as a fraction of the frequency
+  pub vibrato_depth: f32,
+  /// vibrato frequency in hertz
+  pub vibrato_speed: f32,
+  /// seconds to rise to full volume
+  pub attack: f32,
+  /// seconds to hold at full volume
+  pub sustain: f32,
+  /// extra volume at the start of the sustain, fading over the sustain
+  pub punch: f32,
+  /// seconds to fall to silence
+  pub decay: f32,
+  /// fraction of white noise mixed into the waveform
+  pub noise: f32,
+  /// bits of each sample kept by the bit-crusher, 16 keeps every bit
+  pub bits: u32,
+  /// samples each crushed sample is held for, reducing the sample rate
+  pub downsample: u32,
+  /// volume in the unit interval
+  pub volume: f32,
+  /// seed of the noise, so a sound is the same each time it is synthesized
+  pub seed: u32,
+}
+
+impl Default for SynthParams {
+  /// A short square wave blip
+  fn default() -> Self {
+    Self {
+      waveform: Waveform::default(),
+      frequency: 440.0,
+      slide: 0.0,
+      min_frequency: 0.0,
+      duty: 0.5,
+      vibrato_depth: 0.0,
+      vibrato_speed: 0.0,
+      attack: 0.0,
+      sustain: 0.1,
+      punch: 0.0,
+      decay: 0.1,
+      noise: 0.0,
+      bits: 16,
+      downsample: 1,
+      volume: 0.5,
+      seed: 1,
+    }
+  }
+}
+
+impl SynthParams {
+  /// Get the length of the envelope in seconds
+  pub fn get_duration(&self) -> f32 { self.attack.max(0.0) + self.sustain.max(0.0) + self.decay.max(0.0) }
+
+  /// Get the volume of the envelope `time` seconds into the sound
+  fn envelope(&self, time: f32) -> f32 {
+    let (attack, sustain, decay) = (self.attack.max(0.0), self.sustain.max(0.0), self.decay.max(0.0));
+    if time < attack {
+      time / attack
+    } else if time < attack + sustain {
+      1.0 + self.punch * (1.0 - (time - attack) / sustain)
+    } else if time < attack + sustain + decay {
+      1.0 - (time - attack - sustain) / decay
+    } else {
+      0.0
+    }
+  }
+}
+
+/// Xorshift generator, so noise is reproducible from a seed
+struct Noise(u32);
+
+impl Noise {
+  /// Get the next noise sample in [-1, 1]
+  fn next(&mut self) -> f32 {
+    self.0 ^= self.0 << 13;
+    self.0 ^= self.0 >> 17;
+    self.0 ^= self.0 << 5;
+    self.0 as f32 / u32::MAX as f32 * 2.0 - 1.0
+  }
+}
+
+/// Synthesize signed 16-bit samples, interleaved across `channels` at `sample_rate`
+pub fn synthesize(params: &SynthParams, sample_rate: u32, channels: usize) -> Vec<i16> {
+  let rate = sample_rate as f32;
+  let frames = (params.get_duration() * rate) as usize;
+  let levels = 2.0_f32.powi(params.bits.clamp(1, 16) as i32 - 1);
+  let downsample = params.downsample.max(1) as usize;
+
+  let mut noise = Noise(params.seed.max(1));
+  let mut held_noise = noise.next();
+  let mut phase = 0.0_f32;
+  let mut crushed = 0.0_f32;
+  let mut samples = Vec::with_capacity(frames * channels);
+  for frame in 0..frames {
+    let time = frame as f32 / rate;
+    let vibrato = 1.0 + params.vibrato_depth * (TAU * params.vibrato_speed * time).sin();
+    let frequency = params.frequency * 2.0_f32.powf(params.slide * time) * vibrato;
+    if frequency < params.min_frequency { break; }
+
+    phase += frequency.min(rate / 2.0) / rate;
+    if phase >= 1.0 {
+      phase = phase.fract();
+      held_noise = noise.next();
+    }
+    let wave = match params.waveform {
+      Waveform::Square => if phase < params.duty { 1.0 } else { -1.0 },
+      Waveform::Sawtooth => 2.0 * phase - 1.0,
+      Waveform::Sine => (TAU * phase).sin(),
+      Waveform::Triangle => 4.0 * (phase - 0.5).abs() - 1.0,
+      Waveform::Noise => held_noise,
+    };
+    let noise_mix = params.noise.clamp(0.0, 1.0);
+    let sample = (wave * (1.0 - noise_mix) + noise.next() * noise_mix) * params.envelope(time) * params.volume;
+
+    if frame % downsample == 0 {
+      crushed = ((sample * levels).round() / levels).clamp(-1.0, 1.0);
+    }
+    let value = (crushed * i16::MAX as f32) as i16;
+    samples.extend(std::iter::repeat(value).take(channels));
+  }
+  samples
+}
+
+/// A phrase of notes played by one synth preset, one note or rest per step
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct SynthVoice {
+  /// name of the synth preset that plays the notes
+  pub preset: String,
+  /// steps separated by whitespace, each a note such as `C4`, `F#3` or `Bb2`, or `.` to rest
+  pub notes: String,
+}
+
+/// A loop of voices played over each other, the loop is as long as the longest voice and shorter voices repeat
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct SynthSequence {
+  /// steps per minute
+  pub tempo: f32,
+  pub voices: Vec<SynthVoice>,
+}
+
+impl SynthVoice {
+  /// Get the frequency of each step of the voice, rests have none
+  pub fn get_steps(&self) -> Result<Vec<Option<f32>>, String> {
+    self.notes
+      .split_whitespace()
+      .map(|step| if step == "." { Ok(None) } else { get_note_frequency(step).map(Some) })
+      .collect()
+  }
+}
+
+/// Get the frequency in hertz of a note name such as `A4`, `C#3` or `Bb2`, in equal temperament from A4 at 440 Hz
+pub fn get_note_frequency(note: &str) -> Result<f32, String> {
+  let invalid = || format!("Invalid note '{}'", note);
+  let mut chars = note.chars();
+  let semitone = match chars.next().ok_or_else(invalid)? {
+    'C' => 0,
+    'D' => 2,
+    'E' => 4,
+    'F' => 5,
+    'G' => 7,
+    'A' => 9,
+    'B' => 11,
+    _ => return Err(invalid()),
+  };
+  let rest = chars.as_str();
+  let (accidental, octave) = match rest.chars().next() {
+    Some('#') => (1, &rest[1..]),
+    Some('b') => (-1, &rest[1..]),
+    _ => (0, rest),
+  };
+  let octave = octave.parse::<i32>().map_err(|_| invalid())?;
+  let midi = (octave + 1) * 12 + semitone + accidental;
+  Ok(440.0 * 2.0_f32.powf((midi - 69) as f32 / 12.0))
+}
+
+/// Synthesize a sequence into a seamless loop of signed 16-bit samples, interleaved across `channels` at `sample_rate`
+///
+/// Notes play their preset at the pitch of the note, and notes that ring past the end of the loop wrap to its start.
+pub fn synthesize_sequence(sequence: &SynthSequence, presets: &HashMap<String, SynthParams>, sample_rate: u32, channels: usize) -> Result<Vec<i16>, String> {
+  if sequence.tempo <= 0.0 { return Err(format!("Tempo {} is not positive", sequence.tempo)); }
+  let voices = sequence.voices
+    .iter()
+    .map(|voice| {
+      let preset = presets.get(&voice.preset).ok_or_else(|| format!("Synth preset {} is missing", voice.preset))?;
+      voice.get_steps().map(|steps| (preset, steps))
+    })
+    .collect::<Result<Vec<_>, String>>()?;
+  let steps = voices.iter().map(|(.., steps)| steps.len()).max().unwrap_or(0);
+  let step_frames = (60.0 / sequence.tempo * sample_rate as f32) as usize;
+  let frames = steps * step_frames;
+  if frames == 0 { return Err(String::from("Sequence has no steps")); }
+
+  let length = frames * channels;
+  let mut mix = vec![0.0_f32; length];
+  for (preset, notes) in voices.iter().filter(|(.., notes)| !notes.is_empty()) {
+    for step in 0..steps {
+      let Some(frequency) = notes[step % notes.len()] else { continue; };
+      let note = synthesize(&SynthParams { frequency, ..(*preset).clone() }, sample_rate, channels);
+      let start = step * step_frames * channels;
+      for (offset, sample) in note.into_iter().enumerate() {
+        mix[(start + offset) % length] += sample as f32;
+      }
+    }
+  }
+  Ok(mix.into_iter().map(|sample| sample.clamp(i16::MIN as f32, i16::MAX as f32) as i16).collect())
+}
+
+/// Encode signed 16-bit samples, interleaved across `channels` at `sample_rate`, as a WAV file
+pub fn encode_wav(samples: &[i16], sample_rate: u32, channels: u16) -> Vec<u8> {
+  let data_size = (samples.len() * 2) as u32;
+  let block_align = channels * 2;
+  let mut wav = Vec::with_capacity(44 + data_size as usize);
+  wav.extend_from_slice(b"RIFF");
+  wav.extend_from_slice(&(36 + data_size).to_le_bytes());
+  wav.extend_from_slice(b"WAVEfmt ");
+  wav.extend_from_slice(&16_u32.to_le_bytes());
+  // uncompressed PCM
+  wav.extend_from_slice(&1_u16.to_le_bytes());
+  wav.extend_from_slice(&channels.to_le_bytes());
+  wav.extend_from_slice(&sample_rate.to_le_bytes());
+  wav.extend_from_slice(&(sample_rate * block_align as u32).to_le_bytes());
+  wav.extend_from_slice(&block_align.to_le_bytes());
+  wav.extend_from_slice(&16_u16.to_le_bytes());
+  wav.extend_from_slice(b"data");
+  wav.extend_from_slice(&data_size.to_le_bytes());
+  for sample in samples { wav.extend_from_slice(&sample.to_le_bytes()); }
+  wav
+}
+
+/// Load named synth parameters from a data file
+pub fn load_synth_presets(filepath: impl AsRef<Path>) -> Result<HashMap<String, SynthParams>, String> {
+  let filepath = filepath.as_ref();
+  deserialize_json(&read_to_string(filepath)?).map_err(|e| format!("Failed to parse synth presets {}: {}", filepath.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const RATE: u32 = 1000;
+
+  #[test]
+  fn test_envelope_length() {
+    let params = SynthParams { attack: 0.25, sustain: 0.25, decay: 0.5, ..Default::default() };
+    assert_eq!(synthesize(&params, RATE, 2).len(), 2000, "every frame of the envelope in stereo");
+
+    let params = SynthParams { slide: -8.0, min_frequency: 230.0, sustain: 1.0, ..Default::default() };
+    assert_eq!(synthesize(&params, RATE, 1).len(), 117, "sound ends once the slide passes the minimum frequency");
+  }
+
+  #[test]
+  fn test_bit_crush() {
+    let params = SynthParams { waveform: Waveform::Sine, volume: 1.0, bits: 2, ..Default::default() };
+    let levels = synthesize(&params, RATE, 1).into_iter().collect::<std::collections::HashSet<_>>();
+    assert_eq!(levels.len(), 5, "samples are quantized to 2 bits either side of silence");
+
+    let params = SynthParams { waveform: Waveform::Sawtooth, downsample: 4, ..Default::default() };
+    let samples = synthesize(&params, RATE, 1);
+    assert!(samples.chunks(4).all(|held| held.iter().all(|sample| *sample == held[0])), "samples are held");
+  }
+
+  #[test]
+  fn test_note_frequency() {
+    let close = |note: &str, frequency: f32| (get_note_frequency(note).unwrap() - frequency).abs() < 0.01;
+    assert!(close("A4", 440.0) && close("A3", 220.0) && close("C4", 261.63));
+    assert!(close("C#4", 277.18) && close("Db4", 277.18), "sharps and flats name the same note");
+    assert!(get_note_frequency("H4").is_err() && get_note_frequency("C").is_err());
+  }
+
+  #[test]
+  fn test_sequence() {
+    let presets = HashMap::from([(String::from("pluck"), SynthParams { sustain: 0.5, decay: 0.0, volume: 1.0, ..Default::default() })]);
+    let voice = |notes: &str| SynthVoice { preset: String::from("pluck"), notes: String::from(notes) };
+    let sequence = SynthSequence { tempo: 60.0, voices: vec![voice("A4 . . ."), voice("A3 .")] };
+    let samples = synthesize_sequence(&sequence, &presets, RATE, 1).unwrap();
+    assert_eq!(samples.len(), 4000, "as long as the longest voice");
+    let melody = synthesize_sequence(&SynthSequence { tempo: 60.0, voices: vec![voice("A4 . . .")] }, &presets, RATE, 1).unwrap();
+    assert_ne!(samples[..500], melody[..500], "voices play over each other");
+    assert!(samples[500..1000].iter().all(|sample| *sample == 0), "rests are silent");
+    assert!(samples[2000..2500].iter().all(|sample| *sample != 0), "shorter voices repeat");
+
+    let ringing = SynthSequence { tempo: 240.0, voices: vec![voice(". A4")] };
+    let samples = synthesize_sequence(&ringing, &presets, RATE, 1).unwrap();
+    assert!(samples[..250].iter().all(|sample| *sample != 0), "notes ringing past the end wrap to the start");
+
+    let missing = SynthSequence { tempo: 60.0, voices: vec![SynthVoice { preset: String::from("organ"), notes: String::from("C4") }] };
+    assert!(synthesize_sequence(&missing, &presets, RATE, 1).is_err());
+    assert!(synthesize_sequence(&SynthSequence { tempo: 60.0, voices: vec![voice("C4 X9")] }, &presets, RATE, 1).is_err());
+  }
+
+  #[test]
+  fn test_encode_wav() {
+    let wav = encode_wav(&[0, 1, -1, i16::MAX], 22050, 2);
+    assert_eq!(wav.len(), 44 + 8);
+    assert_eq!((&wav[0..4], &wav[8..16], &wav[36..40]), (&b"RIFF"[..], &b"WAVEfmt "[..], &b"data"[..]));
+    assert_eq!(u32::from_le_bytes(wav[24..28].try_into().unwrap()), 22050);
+    assert_eq!(i16::from_le_bytes(wav[50..52].try_into().unwrap()), i16::MAX);
+  }
+
+  #[test]
+  fn test_reproducible() {
+    let params = SynthParams { waveform: Waveform::Noise, noise: 0.5, seed: 7, ..Default::default() };
+    assert_eq!(synthesize(&params, RATE, 1), synthesize(&params, RATE, 1), "noise is seeded");
+  }
+}
@@ -2,25 +2,133 @@
  * Texture loading, storage, and retrieval
  */
 
-use std::path::Path;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
 
 use sdl2::image::LoadTexture;
-use sdl2::render::{TextureCreator, TextureQuery};
+use sdl2::pixels::PixelFormatEnum;
+use sdl2::render::{BlendMode, TextureCreator, TextureQuery};
 use sdl2::surface::Surface;
 use sdl2::video::WindowContext;
 
+use crate::engine::asset::vfs;
 use crate::engine::geometry::shape::Rec2;
 use crate::engine::utility::alias::{Size, Size2};
-use crate::engine::utility::store::{next_key, Store};
+use crate::engine::utility::store::{next_key, Key, Store};
 
 pub type TextureKey = usize;
 
 /// store textures
 pub type TextureStore = Store<TextureKey, Texture>;
 
+/// Identifies a group of texture references released together
+pub type TextureScope = Key;
+
+/// Bytes per pixel of a decoded texture, used to estimate memory
+const BYTES_PER_PIXEL: u64 = 4;
+
+/// References to textures held by a scope
+#[derive(Debug, Default)]
+struct ScopeReferences {
+  parent: Option<TextureScope>,
+  textures: Vec<TextureKey>,
+}
+
+/// Bookkeeping of the paths textures were loaded from, how many references each has, and the scopes holding them
+///
+/// References acquired while a scope is active are released when the scope is closed. References acquired while no
+/// scope is active are held until released explicitly. Scopes opened while another is active are closed with it.
+#[derive(Debug, Default)]
+struct TextureReferences {
+  paths: HashMap<PathBuf, TextureKey>,
+  counts: HashMap<TextureKey, usize>,
+  scopes: HashMap<TextureScope, ScopeReferences>,
+  active: Option<TextureScope>,
+}
+
+impl TextureReferences {
+  /// Get the texture loaded from a path if it is still loaded
+  fn get_path(&self, filepath: &Path) -> Option<TextureKey> { self.paths.get(filepath).copied() }
+
+  /// Add a reference to a texture, held by the active scope
+  fn acquire(&mut self, key: TextureKey) {
+    *self.counts.entry(key).or_insert(0) += 1;
+    if let Some(scope) = self.active.and_then(|scope| self.scopes.get_mut(&scope)) {
+      scope.textures.push(key);
+    }
+  }
+  /// Add a reference to a new texture loaded from a path
+  fn acquire_path(&mut self, key: TextureKey, filepath: PathBuf) {
+    self.paths.insert(filepath, key);
+    self.acquire(key);
+  }
+  /// Remove a reference to a texture, returning true if it was the last
+  fn release(&mut self, key: TextureKey) -> Result<bool, String> {
+    let count = self.counts.get_mut(&key).ok_or(format!("Texture {} is not referenced", key))?;
+    *count -= 1;
+    if *count > 0 { return Ok(false); }
+    self.counts.remove(&key);
+    self.paths.retain(|_, texture| *texture != key);
+    Ok(true)
+  }
+
+  /// Open a scope within the active scope
+  fn open_scope(&mut self) -> TextureScope {
+    let scope = next_key();
+    self.scopes.insert(scope, ScopeReferences { parent: self.active, textures: Vec::new() });
+    scope
+  }
+  /// Release the references of a scope and the scopes within it, returning the textures no longer referenced
+  fn close_scope(&mut self, scope: TextureScope) -> Result<Vec<TextureKey>, String> {
+    let references = self.scopes.remove(&scope).ok_or(format!("Texture scope {} is not open", scope))?;
+    let children = self.scopes
+      .iter()
+      .filter(|(_, child)| child.parent == Some(scope))
+      .map(|(child, ..)| *child)
+      .collect::<Vec<_>>();
+
+    let mut unused = Vec::new();
+    for child in children { unused.extend(self.close_scope(child)?); }
+    for key in references.textures {
+      if self.release(key)? { unused.push(key); }
+    }
+    if self.active == Some(scope) { self.active = references.parent; }
+    Ok(unused)
+  }
+}
+
+/// Counts of the textures held by a loader
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TextureStats {
+  /// textures in the store
+  pub textures: usize,
+  /// textures loaded from files
+  pub files: usize,
+  /// references held to textures
+  pub references: usize,
+  /// open scopes
+  pub scopes: usize,
+  /// estimated memory of the textures in bytes
+  pub bytes: u64,
+}
+
+impl Display for TextureStats {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    write!(
+      f,
+      "textures {} ({} files) refs {} scopes {} {:.1}MiB",
+      self.textures, self.files, self.references, self.scopes, self.bytes as f64 / (1024.0 * 1024.0),
+    )
+  }
+}
+
 /// Load and store textures
+///
+/// Textures are loaded once per path and reference-counted, then destroyed once their last reference is released.
 pub struct TextureLoader {
   store: TextureStore,
+  references: TextureReferences,
   subsystem: TextureCreator<WindowContext>,
 }
 
@@ -30,25 +138,53 @@ impl TextureLoader {
     Self {
       subsystem: creator,
       store: TextureStore::new(),
+      references: TextureReferences::default(),
     }
   }
 
-  /// Loads a texture from a file and adds it to the store
+  /// Loads a texture from a file and adds it to the store, or references it if the file is already loaded
   pub fn load(&mut self, filepath: impl AsRef<Path>) -> Result<TextureKey, String> {
+    let filepath = filepath.as_ref().components().collect::<PathBuf>();
+    if let Some(key) = self.references.get_path(&filepath) {
+      self.references.acquire(key);
+      return Ok(key);
+    }
+
     // load texture
     let internal_texture = self
       .subsystem
-      .load_texture(filepath)
-      .map_err(|_| "Failed to load texture")?;
+      .load_texture_bytes(&vfs::read(&filepath)?)
+      .map_err(|_| format!("Failed to load texture {}", filepath.display()))?;
 
     // store texture
     let texture = Texture::new(internal_texture);
     let key = next_key();
     self.store.add(key, texture);
+    self.references.acquire_path(key, filepath);
 
     Ok(key)
   }
 
+  /// Decode a texture loaded from a file again, replacing it in place so its key remains valid
+  ///
+  /// Returns false if no texture is loaded from the file.
+  pub fn reload(&mut self, filepath: impl AsRef<Path>) -> Result<bool, String> {
+    let filepath = filepath.as_ref().components().collect::<PathBuf>();
+    let Some(key) = self.references.get_path(&filepath) else { return Ok(false); };
+    let internal_texture = self
+      .subsystem
+      .load_texture_bytes(&vfs::read(&filepath)?)
+      .map_err(|_| format!("Failed to reload texture {}", filepath.display()))?;
+    self.replace(key, Texture::new(internal_texture))?;
+    Ok(true)
+  }
+  /// Get the texture loaded from a file without adding a reference to it
+  pub fn find(&self, filepath: impl AsRef<Path>) -> Option<TextureKey> {
+    self.references.get_path(&filepath.as_ref().components().collect::<PathBuf>())
+  }
+  /// Get the files textures are loaded from
+  pub fn get_paths(&self) -> impl Iterator<Item=&Path> { self.references.paths.keys().map(PathBuf::as_path) }
+
   /// Builds a texture from a surface
   pub fn build_from_surface(&self, surface: Surface) -> Result<Texture, &str> {
     let internal_texture = self
@@ -59,6 +195,82 @@ impl TextureLoader {
     Ok(texture)
   }
 
+  /// Builds a blank texture of `dimensions` that can be rendered to
+  pub fn build_target(&self, dimensions: Size2) -> Result<Texture, String> {
+    let mut internal_texture = self
+      .subsystem
+      .create_texture_target(PixelFormatEnum::RGBA8888, dimensions.x, dimensions.y)
+      .map_err(|e| e.to_string())?;
+    internal_texture.set_blend_mode(BlendMode::Blend);
+    Ok(Texture::new(internal_texture))
+  }
+
+  /// Adds a built texture to the store
+  pub fn add(&mut self, texture: Texture) -> TextureKey {
+    let key = next_key();
+    self.store.add(key, texture);
+    self.references.acquire(key);
+    key
+  }
+
+  /// Replace a built texture in the store, destroying the texture it replaces
+  pub fn replace(&mut self, key: TextureKey, texture: Texture) -> Result<(), String> {
+    let previous = std::mem::replace(self.store.get_mut(key)?, texture);
+    unsafe { previous.internal.destroy(); }
+    Ok(())
+  }
+
+  // References //
+
+  /// Add a reference to a loaded texture, held by the active scope
+  pub fn retain(&mut self, key: TextureKey) -> Result<(), String> {
+    if !self.is_loaded(key) { return Err(format!("Texture {} is not loaded", key)); }
+    self.references.acquire(key);
+    Ok(())
+  }
+  /// Remove a reference to a texture, destroying it if no references remain
+  pub fn release(&mut self, key: TextureKey) -> Result<(), String> {
+    if self.references.release(key)? { self.unload(key)?; }
+    Ok(())
+  }
+  /// Check if a texture is in the store
+  pub fn is_loaded(&self, key: TextureKey) -> bool { self.store.store.contains_key(&key) }
+
+  /// Remove a texture from the store and destroy it
+  fn unload(&mut self, key: TextureKey) -> Result<(), String> {
+    let texture = self.store.store.remove(&key).ok_or(format!("Texture {} is not loaded", key))?;
+    unsafe { texture.internal.destroy(); }
+    Ok(())
+  }
+
+  // Scopes //
+
+  /// Open a scope within the active scope, it is closed along with the active scope
+  pub fn open_scope(&mut self) -> TextureScope { self.references.open_scope() }
+  /// Make a scope active so it holds the references acquired, returning the scope that was active
+  pub fn set_scope(&mut self, scope: Option<TextureScope>) -> Option<TextureScope> {
+    std::mem::replace(&mut self.references.active, scope)
+  }
+  /// Release the references held by a scope and its nested scopes, destroying textures no longer referenced
+  pub fn close_scope(&mut self, scope: TextureScope) -> Result<(), String> {
+    for key in self.references.close_scope(scope)? { self.unload(key)?; }
+    Ok(())
+  }
+
+  /// Count the textures held by the loader
+  pub fn get_stats(&self) -> TextureStats {
+    TextureStats {
+      textures: self.store.store.len(),
+      files: self.references.paths.len(),
+      references: self.references.counts.values().sum(),
+      scopes: self.references.scopes.len(),
+      bytes: self.store.store
+        .values()
+        .map(|texture| texture.dimensions.x as u64 * texture.dimensions.y as u64 * BYTES_PER_PIXEL)
+        .sum(),
+    }
+  }
+
   /// Returns an immutable reference to the store
   pub fn use_store(&mut self) -> &mut TextureStore {
     &mut self.store
@@ -85,3 +297,44 @@ impl Texture {
     }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_reference_count() {
+    let mut references = TextureReferences::default();
+    references.acquire_path(0, PathBuf::from("asset/sprite/zoomer.png"));
+    references.acquire(0);
+    assert_eq!(references.get_path(Path::new("asset/sprite/zoomer.png")), Some(0), "path is cached");
+
+    assert_eq!(references.release(0), Ok(false), "texture is still referenced");
+    assert_eq!(references.release(0), Ok(true), "last reference is released");
+    assert_eq!(references.get_path(Path::new("asset/sprite/zoomer.png")), None, "released path is no longer cached");
+    assert!(references.release(0).is_err(), "texture has no references");
+  }
+
+  #[test]
+  fn test_scopes() {
+    let mut references = TextureReferences::default();
+    references.acquire(0); // held outside of any scope
+
+    let scene = references.open_scope();
+    references.active = Some(scene);
+    references.acquire(0);
+    references.acquire(1);
+    let room = references.open_scope();
+    references.active = Some(room);
+    references.acquire(1);
+    references.acquire(2);
+    references.active = Some(scene);
+
+    let mut unused = references.close_scope(scene).unwrap();
+    unused.sort();
+    assert_eq!(unused, vec![1, 2], "nested scopes are closed with their parent");
+    assert_eq!(references.counts.get(&0), Some(&1), "reference outside of the scope is held");
+    assert_eq!(references.active, None, "parent of the closed scope is active");
+    assert!(references.close_scope(room).is_err(), "nested scope is already closed");
+  }
+}
@@ -2,20 +2,54 @@
  * Typeface loading, storage, and retrieval
  */
 
-use std::path::Path;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
 
+use sdl2::rwops::RWops;
 use sdl2::ttf::{Font, Sdl2TtfContext};
+use crate::engine::asset::bitmap::{BitmapFont, load_bitmap_font};
+use crate::engine::asset::texture::TextureLoader;
+use crate::engine::asset::vfs;
 use crate::engine::utility::store::Store;
 
-use crate::game::utility::path::{get_filename, get_path};
+use crate::game::utility::path::get_filename;
+
+/// Identifies a loaded typeface by the filename it was loaded from and its point size
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FontHandle {
+  pub family: String,
+  pub size: u16,
+}
+
+impl FontHandle {
+  /// Instantiate a new font handle
+  pub fn new(family: impl Into<String>, size: u16) -> Self {
+    Self { family: family.into(), size }
+  }
+}
+
+impl Display for FontHandle {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{} ({}pt)", self.family, self.size)
+  }
+}
 
 /// store typefaces
-pub type TypefaceStore<'ttf, 'f> = Store<String, Font<'ttf, 'f>>;
+pub type TypefaceStore<'ttf, 'f> = Store<FontHandle, Font<'ttf, 'f>>;
 
 /// Load and store typefaces
+///
+/// A typeface may be loaded at several sizes, each stored under its own handle. The first typeface loaded is used by
+/// text without a handle until another default is set. Bitmap fonts are stored alongside typefaces under the size
+/// they were drawn at.
 pub struct TypefaceLoader<'ttf, 'b> {
   subsystem: &'ttf Sdl2TtfContext,
   store: TypefaceStore<'ttf, 'b>,
+  /// data of each typeface file, shared by every size it is loaded at
+  files: HashMap<PathBuf, &'static [u8]>,
+  bitmaps: HashMap<FontHandle, BitmapFont>,
+  default: Option<FontHandle>,
 }
 
 impl<'ttf, 'l> TypefaceLoader<'ttf, 'l> {
@@ -24,20 +58,68 @@ impl<'ttf, 'l> TypefaceLoader<'ttf, 'l> {
     Self {
       subsystem,
       store: TypefaceStore::new(),
+      files: HashMap::new(),
+      bitmaps: HashMap::new(),
+      default: None,
     }
   }
 
-  /// Loads a typeface from a file and adds it to the store
-  pub fn load(&mut self, filepath: &Path, size: u16) -> Result<(), String> {
-    let path_str = get_path(filepath)?;
-    let filename = get_filename(filepath)?;
+  /// Loads a typeface from a file at `size` and adds it to the store
+  pub fn load(&mut self, filepath: &Path, size: u16) -> Result<FontHandle, String> {
+    let handle = FontHandle::new(get_filename(filepath)?, size);
+
+    // fonts read glyphs from their data while they are used, so the data lives as long as the game
+    let data = match self.files.get(filepath) {
+      Some(data) => *data,
+      None => {
+        let data = &*Box::leak(vfs::read(filepath)?.into_boxed_slice());
+        self.files.insert(filepath.to_path_buf(), data);
+        data
+      }
+    };
+    let font = self.subsystem.load_font_from_rwops(RWops::from_bytes(data)?, size)?;
+    self.store.add(handle.clone(), font);
+    if self.default.is_none() { self.default = Some(handle.clone()); }
+
+    Ok(handle)
+  }
+
+  /// Loads a typeface from a file at each of `sizes`
+  pub fn load_sizes(&mut self, filepath: &Path, sizes: &[u16]) -> Result<Vec<FontHandle>, String> {
+    sizes
+      .iter()
+      .map(|size| self.load(filepath, *size))
+      .collect()
+  }
 
-    let font = self.subsystem.load_font(path_str, size)?;
-    self.store.add(String::from(filename), font);
+  /// Loads a bitmap font from a BMFont text file and its page images
+  pub fn load_bitmap(&mut self, filepath: &Path, textures: &mut TextureLoader) -> Result<FontHandle, String> {
+    let font = load_bitmap_font(filepath, textures)?;
+    let handle = FontHandle::new(get_filename(filepath)?, font.get_size());
+    self.bitmaps.insert(handle.clone(), font);
+    Ok(handle)
+  }
 
+  /// Use a loaded typeface for text without a handle
+  pub fn set_default(&mut self, font: &FontHandle) -> Result<(), String> {
+    self.store.get(font.clone())?;
+    self.default = Some(font.clone());
     Ok(())
   }
 
+  /// Get a loaded typeface, or the default typeface with `None`
+  pub fn get(&self, font: Option<&FontHandle>) -> Result<&Font<'ttf, 'l>, String> {
+    let handle = font
+      .or(self.default.as_ref())
+      .ok_or(String::from("No typeface has been loaded"))?;
+    self.store.get(handle.clone())
+  }
+
+  /// Get a loaded bitmap font, if `font` refers to one
+  pub fn get_bitmap(&self, font: Option<&FontHandle>) -> Option<&BitmapFont> {
+    self.bitmaps.get(font?)
+  }
+
   /// Returns an immutable reference to the store
   pub fn use_store(&self) -> &TypefaceStore {
     &self.store
@@ -0,0 +1,218 @@
+/**
+ * Virtual filesystem that reads assets from the directory tree, or from a packed archive
+ */
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::path::{Component, Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Filename of the packed archive beside the executable
+pub const ARCHIVE_NAME: &str = "asset.pak";
+
+/// Size of a header and the data alignment of a tar archive
+const BLOCK_SIZE: u64 = 512;
+
+/// Get the archive key of a path relative to the asset root, collapsing `.` and `..` components
+pub fn normalize(path: &Path) -> String {
+  let mut parts = Vec::new();
+  for component in path.components() {
+    match component {
+      Component::Normal(part) => parts.push(part.to_string_lossy()),
+      Component::ParentDir => { parts.pop(); }
+      Component::CurDir | Component::RootDir | Component::Prefix(..) => {}
+    }
+  }
+  parts.join("/")
+}
+
+/// Location of a file in an archive
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct ArchiveEntry {
+  offset: u64,
+  size: u64,
+}
+
+/// Parse a null or space terminated field of a tar header
+fn parse_field(field: &[u8]) -> &str {
+  let end = field.iter().position(|byte| *byte == 0).unwrap_or(field.len());
+  std::str::from_utf8(&field[..end]).unwrap_or_default().trim()
+}
+
+/// Index the regular files of an uncompressed ustar archive, reading only their headers
+fn read_index(reader: &mut (impl Read + Seek)) -> Result<HashMap<String, ArchiveEntry>, String> {
+  let mut entries = HashMap::new();
+  let mut header = [0; BLOCK_SIZE as usize];
+  let mut offset = 0;
+  loop {
+    reader.read_exact(&mut header).map_err(|_| String::from("Archive is truncated"))?;
+    if header.iter().all(|byte| *byte == 0) { break; } // end of archive
+
+    let name = parse_field(&header[0..100]);
+    let prefix = if &header[257..262] == b"ustar" { parse_field(&header[345..500]) } else { "" };
+    let size = u64::from_str_radix(parse_field(&header[124..136]), 8)
+      .map_err(|_| format!("Archive entry {} has an invalid size", name))?;
+
+    let data = offset + BLOCK_SIZE;
+    if matches!(header[156], b'0' | 0) {
+      let path = Path::new(prefix).join(name);
+      entries.insert(normalize(&path), ArchiveEntry { offset: data, size });
+    }
+    offset = data + size.div_ceil(BLOCK_SIZE) * BLOCK_SIZE;
+    reader.seek(SeekFrom::Start(offset)).map_err(|e| e.to_string())?;
+  }
+  Ok(entries)
+}
+
+/// Files packed into a single uncompressed tar archive, read on demand
+#[derive(Debug)]
+pub struct Archive {
+  path: PathBuf,
+  entries: HashMap<String, ArchiveEntry>,
+}
+
+impl Archive {
+  /// Index the files of an archive
+  pub fn open(path: impl Into<PathBuf>) -> Result<Self, String> {
+    let path = path.into();
+    let file = File::open(&path).map_err(|e| format!("Failed to open archive {}: {}", path.display(), e))?;
+    let entries = read_index(&mut BufReader::new(file)).map_err(|e| format!("Failed to index archive {}: {}", path.display(), e))?;
+    Ok(Self { path, entries })
+  }
+  /// Check if a file is packed in the archive
+  pub fn contains(&self, path: &Path) -> bool { self.entries.contains_key(&normalize(path)) }
+  /// Read a file packed in the archive
+  pub fn read(&self, path: &Path) -> Result<Vec<u8>, String> {
+    let entry = self.entries
+      .get(&normalize(path))
+      .ok_or(format!("{} is not in archive {}", path.display(), self.path.display()))?;
+    let mut file = File::open(&self.path).map_err(|e| e.to_string())?;
+    file.seek(SeekFrom::Start(entry.offset)).map_err(|e| e.to_string())?;
+    let mut data = vec![0; entry.size as usize];
+    file.read_exact(&mut data).map_err(|e| e.to_string())?;
+    Ok(data)
+  }
+}
+
+/// Where assets are read from
+#[derive(Debug)]
+pub enum Vfs {
+  /// files of a directory tree, relative to its root
+  Directory(PathBuf),
+  Archive(Archive),
+}
+
+impl Vfs {
+  /// Find the assets of the running build
+  ///
+  /// Development builds read the directory tree of the crate. Release builds read the archive beside the executable, or
+  /// in the resources of a macOS bundle, falling back to the directory of the executable if no archive is found.
+  pub fn locate() -> Self {
+    if cfg!(debug_assertions) { return Vfs::Directory(PathBuf::from(env!("CARGO_MANIFEST_DIR"))); }
+
+    let directory = std::env::current_exe()
+      .ok()
+      .and_then(|exe| exe.parent().map(Path::to_path_buf))
+      .unwrap_or_default();
+    let archive = [directory.join(ARCHIVE_NAME), directory.join("../Resources").join(ARCHIVE_NAME)]
+      .into_iter()
+      .find(|path| path.is_file());
+    match archive.map(Archive::open) {
+      Some(Ok(archive)) => Vfs::Archive(archive),
+      Some(Err(error)) => {
+        eprintln!("{}, reading assets from {}", error, directory.display());
+        Vfs::Directory(directory)
+      }
+      None => Vfs::Directory(directory),
+    }
+  }
+
+  /// Read a file
+  pub fn read(&self, path: &Path) -> Result<Vec<u8>, String> {
+    match self {
+      Vfs::Directory(root) => std::fs::read(root.join(path)).map_err(|e| e.to_string()),
+      Vfs::Archive(archive) => archive.read(path),
+    }
+  }
+  /// Check if a file exists
+  pub fn exists(&self, path: &Path) -> bool {
+    match self {
+      Vfs::Directory(root) => root.join(path).is_file(),
+      Vfs::Archive(archive) => archive.contains(path),
+    }
+  }
+  /// Get the file on disk a path refers to, or `None` if it is packed in an archive
+  pub fn resolve(&self, path: &Path) -> Option<PathBuf> {
+    match self {
+      Vfs::Directory(root) => Some(root.join(path)),
+      Vfs::Archive(..) => None,
+    }
+  }
+}
+
+static VFS: OnceLock<Vfs> = OnceLock::new();
+
+/// Get the filesystem assets are read from, locating it on first use
+fn get_vfs() -> &'static Vfs { VFS.get_or_init(Vfs::locate) }
+
+/// Read an asset
+pub fn read(filepath: impl AsRef<Path>) -> Result<Vec<u8>, String> { get_vfs().read(filepath.as_ref()) }
+
+/// Read an asset as a string
+pub fn read_to_string(filepath: impl AsRef<Path>) -> Result<String, String> {
+  String::from_utf8(read(filepath)?).map_err(|e| e.to_string())
+}
+
+/// Check if an asset exists
+pub fn exists(filepath: impl AsRef<Path>) -> bool { get_vfs().exists(filepath.as_ref()) }
+
+/// Get the file on disk an asset is read from, or `None` if it is packed in an archive
+pub fn resolve(filepath: impl AsRef<Path>) -> Option<PathBuf> { get_vfs().resolve(filepath.as_ref()) }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// Build a ustar header of a regular file
+  fn header(name: &str, size: usize) -> Vec<u8> {
+    let mut header = vec![0; BLOCK_SIZE as usize];
+    header[..name.len()].copy_from_slice(name.as_bytes());
+    header[124..135].copy_from_slice(format!("{:011o}", size).as_bytes());
+    header[156] = b'0';
+    header[257..263].copy_from_slice(b"ustar\0");
+    header
+  }
+
+  /// Build an archive of files
+  fn archive(files: &[(&str, &str)]) -> Vec<u8> {
+    let mut archive = Vec::new();
+    for (name, data) in files {
+      archive.extend(header(name, data.len()));
+      archive.extend(data.as_bytes());
+      archive.resize(archive.len().div_ceil(BLOCK_SIZE as usize) * BLOCK_SIZE as usize, 0);
+    }
+    archive.extend([0; 2 * BLOCK_SIZE as usize]);
+    archive
+  }
+
+  #[test]
+  fn test_normalize() {
+    assert_eq!(normalize(Path::new("asset/world/../tileset/./cavern.tsx")), "asset/tileset/cavern.tsx");
+    assert_eq!(normalize(Path::new("./data/story.json")), "data/story.json");
+  }
+
+  #[test]
+  fn test_read_archive() {
+    let path = std::env::temp_dir().join(format!("vfs_{}.pak", std::process::id()));
+    std::fs::write(&path, archive(&[("data/story.json", "{}"), ("asset/hud/pane.png", &"x".repeat(600))])).unwrap();
+
+    let archive = Archive::open(&path).unwrap();
+    assert_eq!(archive.read(Path::new("data/story.json")).unwrap(), b"{}");
+    assert_eq!(archive.read(Path::new("asset/world/../hud/pane.png")).unwrap().len(), 600, "data spans blocks");
+    assert!(!archive.contains(Path::new("data/missing.json")));
+    assert!(archive.read(Path::new("data/missing.json")).is_err());
+
+    std::fs::remove_file(&path).unwrap();
+  }
+}
@@ -0,0 +1,90 @@
+/**
+ * Detect changes to files by polling their modification times
+ */
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use crate::engine::asset::vfs;
+use crate::engine::utility::time::{ConsumeAction, Timer};
+
+/// Get the modification time of an asset, or `None` if it cannot be read or is packed in an archive
+fn modified(filepath: &Path) -> Option<SystemTime> {
+  std::fs::metadata(vfs::resolve(filepath)?).and_then(|metadata| metadata.modified()).ok()
+}
+
+/// Polls the modification times of watched files at an interval
+///
+/// A file that is deleted and created again is reported as changed once it reappears.
+pub struct FileWatcher {
+  files: HashMap<PathBuf, Option<SystemTime>>,
+  timer: Timer,
+}
+
+impl FileWatcher {
+  /// Instantiate a new file watcher that checks files every `interval`
+  pub fn new(interval: Duration) -> Self {
+    Self { files: HashMap::new(), timer: Timer::new(interval, true) }
+  }
+
+  /// Watch a file for changes if it is not already watched
+  pub fn watch(&mut self, filepath: impl AsRef<Path>) {
+    let filepath = filepath.as_ref().components().collect::<PathBuf>();
+    if self.files.contains_key(&filepath) { return; }
+    let modified = modified(&filepath);
+    self.files.insert(filepath, modified);
+  }
+  /// Watch many files for changes
+  pub fn watch_many<P: AsRef<Path>>(&mut self, filepaths: impl IntoIterator<Item=P>) {
+    for filepath in filepaths { self.watch(filepath); }
+  }
+  /// Stop watching a file
+  pub fn unwatch(&mut self, filepath: impl AsRef<Path>) {
+    self.files.remove(&filepath.as_ref().components().collect::<PathBuf>());
+  }
+  /// Get the number of watched files
+  pub fn len(&self) -> usize { self.files.len() }
+
+  /// Get the files that changed since they were last checked, once the interval has elapsed
+  pub fn poll(&mut self) -> Vec<PathBuf> {
+    if !self.timer.consume(ConsumeAction::Restart) { return Vec::new(); }
+    self.check()
+  }
+  /// Get the files that changed since they were last checked
+  pub fn check(&mut self) -> Vec<PathBuf> {
+    let mut changed = Vec::new();
+    for (filepath, last) in &mut self.files {
+      let current = modified(filepath);
+      if current != *last && current.is_some() { changed.push(filepath.clone()); }
+      *last = current;
+    }
+    changed.sort();
+    changed
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_check() {
+    let directory = std::env::temp_dir().join(format!("watch_{}", std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+    let filepath = directory.join("story.json");
+    std::fs::write(&filepath, "{}").unwrap();
+
+    let mut watcher = FileWatcher::new(Duration::ZERO);
+    watcher.watch(&filepath);
+    assert!(watcher.check().is_empty(), "file is unchanged since it was watched");
+
+    let later = SystemTime::now() + Duration::from_secs(10);
+    std::fs::File::options().write(true).open(&filepath).unwrap().set_modified(later).unwrap();
+    assert_eq!(watcher.check(), vec![filepath.clone()], "modification is reported");
+    assert!(watcher.check().is_empty(), "modification is only reported once");
+
+    std::fs::remove_dir_all(&directory).unwrap();
+    assert!(watcher.check().is_empty(), "deleted file is not reported");
+  }
+}
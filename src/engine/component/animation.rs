@@ -1,10 +1,9 @@
 use std::time::Duration;
 
-use crate::engine::asset::asset::AssetManager;
-use crate::engine::asset::texture::{SrcRect, TextureKey};
+use crate::engine::asset::sheet::SpriteSheet;
+use crate::engine::asset::texture::SrcRect;
 use crate::engine::component::sprite::Sprite;
 use crate::engine::ecs::system::{SysArgs, Systemize};
-use crate::engine::utility::alias::{Size, Size2};
 use crate::engine::utility::invariant::invariant;
 use crate::engine::utility::time::Timer;
 use crate::game::scene::level::physics::frozen::Frozen;
@@ -44,6 +43,17 @@ impl Animation {
       infinite,
     })
   }
+  /// Instantiate a new animation from a clip of a sprite sheet
+  ///
+  /// Clips that don't repeat free their entity once complete
+  pub fn from_clip(sheet: &SpriteSheet, clip: &str) -> Result<Self, String> {
+    let frames = sheet
+      .get_clip_frames(clip)?
+      .map(|frame| AnimationFrame::new(frame.src, frame.duration))
+      .collect();
+    let repeat = sheet.get_clip(clip)?.repeat;
+    Self::build(frames, repeat).map_err(|e| format!("Failed to build animation of clip '{}': {}", clip, e))
+  }
   /// Get the current animation frame
   fn get_current_frame(&self) -> &AnimationFrame { &self.frames[self.current_frame] }
   /// Restart the animation
@@ -99,21 +109,4 @@ impl Systemize for Animation {
 
     Ok(())
   }
-}
-
-/// Create an animation from a texture
-pub fn make_texture_animation(texture_key: TextureKey, asset: &mut AssetManager, dimensions: Size2, frame_duration: Duration) -> Result<Animation, String> {
-  let texture = asset.texture.use_store().get(texture_key)?;
-
-  invariant(texture.dimensions.x % dimensions.x == 0, "Animation frame width must divide evenly into texture width")?;
-  invariant(texture.dimensions.y == dimensions.y, "Animation frame height must match texture height")?;
-
-  let frames = (0..texture.dimensions.x / dimensions.x)
-    .map(|i| {
-      let origin = Size2::new(i as Size * dimensions.x, 0);
-      AnimationFrame::new(SrcRect::new(origin, dimensions), frame_duration)
-    })
-    .collect();
-  let animation = Animation::build(frames, true).map_err(|e| format!("Failed to build animation: {}", e))?;
-  Ok(animation)
 }
\ No newline at end of file
@@ -0,0 +1,346 @@
+/**
+ * Animation state machine of named clips driven by parameters
+ */
+
+use std::collections::{HashMap, HashSet};
+
+use crate::engine::asset::sheet::{SheetFrame, SpriteSheet};
+use crate::engine::asset::texture::SrcRect;
+use crate::engine::component::sprite::Sprite;
+use crate::engine::ecs::system::{SysArgs, Systemize};
+use crate::engine::utility::time::Timer;
+use crate::game::scene::level::physics::frozen::Frozen;
+
+/// A requirement on a parameter for a transition to be taken
+#[derive(Clone, Debug)]
+pub enum Condition {
+  /// A bool parameter is `true`
+  Is(String),
+  /// A bool parameter is `false` or unset
+  IsNot(String),
+  /// A float parameter is greater than a value
+  Above(String, f32),
+  /// A float parameter is less than a value
+  Below(String, f32),
+  /// A trigger was set since the last update
+  Triggered(String),
+}
+
+/// A change of state when every condition is met
+#[derive(Clone, Debug)]
+pub struct Transition {
+  from: Option<String>,
+  to: String,
+  conditions: Vec<Condition>,
+}
+
+impl Transition {
+  /// Instantiate a transition from any state to `to`
+  pub fn to(to: impl Into<String>) -> Self {
+    Self { from: None, to: to.into(), conditions: Vec::new() }
+  }
+  /// Only take the transition from `from`
+  pub fn from(mut self, from: impl Into<String>) -> Self {
+    self.from = Some(from.into());
+    self
+  }
+  /// Require a condition to be met for the transition to be taken
+  pub fn when(mut self, condition: Condition) -> Self {
+    self.conditions.push(condition);
+    self
+  }
+  /// Check if a trigger is required by the transition
+  fn is_triggered(&self) -> bool {
+    self.conditions.iter().any(|condition| matches!(condition, Condition::Triggered(..)))
+  }
+}
+
+/// A clip of frames played by a state
+#[derive(Clone, Debug)]
+struct AnimatorClip {
+  frames: Vec<SheetFrame>,
+  repeat: bool,
+  then: Option<String>,
+  events: HashMap<usize, String>,
+  /// emitted when a one-shot clip plays to completion
+  completion: Option<String>,
+}
+
+/// Plays the clip of the current state of a state machine
+///
+/// Transitions are taken in the order they were added, the first with every condition met wins. One-shot clips play
+/// to completion before returning to their `then` state, or the default state, and may only be interrupted by
+/// triggered transitions.
+#[derive(Clone, Debug)]
+pub struct Animator {
+  clips: HashMap<String, AnimatorClip>,
+  transitions: Vec<Transition>,
+  default: String,
+  state: String,
+  frame: usize,
+  timer: Timer,
+  bools: HashMap<String, bool>,
+  floats: HashMap<String, f32>,
+  triggers: HashSet<String>,
+  events: Vec<String>,
+}
+
+impl Animator {
+  /// Instantiate a new animator with a state for each clip of `sheet`, starting in `default`
+  pub fn build(sheet: &SpriteSheet, default: impl Into<String>) -> Result<Self, String> {
+    let default = default.into();
+    let clips = sheet
+      .get_clip_names()
+      .map(|name| {
+        let clip = sheet.get_clip(name)?;
+        let frames = sheet.get_clip_frames(name)?.cloned().collect();
+        Ok((name.clone(), AnimatorClip { frames, repeat: clip.repeat, then: None, events: HashMap::new(), completion: None }))
+      })
+      .collect::<Result<HashMap<_, _>, String>>()?;
+    if !clips.contains_key(&default) { return Err(format!("Animator default state '{}' has no clip", default)); }
+
+    let mut animator = Self {
+      clips,
+      transitions: Vec::new(),
+      state: default.clone(),
+      default,
+      frame: 0,
+      timer: Timer::default(),
+      bools: HashMap::new(),
+      floats: HashMap::new(),
+      triggers: HashSet::new(),
+      events: Vec::new(),
+    };
+    animator.enter(animator.default.clone());
+    Ok(animator)
+  }
+
+  /// Get a clip of the animator
+  fn get_clip(&self, state: &str) -> Result<&AnimatorClip, String> {
+    self.clips.get(state).ok_or(format!("Animator has no state '{}'", state))
+  }
+  /// Get a mutable clip of the animator
+  fn get_clip_mut(&mut self, state: &str) -> Result<&mut AnimatorClip, String> {
+    self.clips.get_mut(state).ok_or(format!("Animator has no state '{}'", state))
+  }
+
+  /// Add a transition between states
+  pub fn with_transition(mut self, transition: Transition) -> Result<Self, String> {
+    self.get_clip(&transition.to)?;
+    if let Some(from) = &transition.from { self.get_clip(from)?; }
+    self.transitions.push(transition);
+    Ok(self)
+  }
+  /// Emit `event` when `frame` of a state's clip is shown
+  pub fn with_event(mut self, state: &str, frame: usize, event: impl Into<String>) -> Result<Self, String> {
+    let clip = self.get_clip_mut(state)?;
+    if frame >= clip.frames.len() { return Err(format!("State '{}' has no frame {}", state, frame)); }
+    clip.events.insert(frame, event.into());
+    if self.state == state && self.frame == frame { self.emit(); }
+    Ok(self)
+  }
+  /// Emit `event` when the one-shot clip of a state plays to completion without being interrupted
+  pub fn with_completion(mut self, state: &str, event: impl Into<String>) -> Result<Self, String> {
+    let clip = self.get_clip_mut(state)?;
+    if clip.repeat { return Err(format!("State '{}' repeats and never completes", state)); }
+    clip.completion = Some(event.into());
+    Ok(self)
+  }
+  /// Enter `then` once the one-shot clip of `state` completes, rather than the default state
+  pub fn with_return(mut self, state: &str, then: impl Into<String>) -> Result<Self, String> {
+    let then = then.into();
+    self.get_clip(&then)?;
+    self.get_clip_mut(state)?.then = Some(then);
+    Ok(self)
+  }
+
+  // Parameters //
+
+  /// Set a bool parameter
+  pub fn set_bool(&mut self, name: impl Into<String>, value: bool) { self.bools.insert(name.into(), value); }
+  /// Set a float parameter
+  pub fn set_float(&mut self, name: impl Into<String>, value: f32) { self.floats.insert(name.into(), value); }
+  /// Set a trigger, consumed by the next update
+  pub fn trigger(&mut self, name: impl Into<String>) { self.triggers.insert(name.into()); }
+
+  /// Check if a condition is met by the current parameters
+  fn is_met(&self, condition: &Condition) -> bool {
+    match condition {
+      Condition::Is(name) => self.bools.get(name).copied().unwrap_or(false),
+      Condition::IsNot(name) => !self.bools.get(name).copied().unwrap_or(false),
+      Condition::Above(name, value) => self.floats.get(name).is_some_and(|param| param > value),
+      Condition::Below(name, value) => self.floats.get(name).is_some_and(|param| param < value),
+      Condition::Triggered(name) => self.triggers.contains(name),
+    }
+  }
+
+  // State //
+
+  /// Get the current state
+  pub fn get_state(&self) -> &str { &self.state }
+  /// Get the source rect of the current frame
+  pub fn get_src(&self) -> SrcRect { self.clips[&self.state].frames[self.frame].src }
+  /// Enter a state immediately regardless of transitions
+  pub fn play(&mut self, state: impl Into<String>) -> Result<(), String> {
+    let state = state.into();
+    self.get_clip(&state)?;
+    self.enter(state);
+    Ok(())
+  }
+  /// Enter a state from its first frame
+  fn enter(&mut self, state: String) {
+    self.state = state;
+    self.show(0);
+  }
+  /// Show a frame of the current state
+  fn show(&mut self, frame: usize) {
+    self.frame = frame;
+    self.timer = Timer::new(self.clips[&self.state].frames[frame].duration, true);
+    self.emit();
+  }
+  /// Emit the event of the current frame if it has one
+  fn emit(&mut self) {
+    if let Some(event) = self.clips[&self.state].events.get(&self.frame) {
+      self.events.push(event.clone());
+    }
+  }
+
+  /// Take the events emitted since they were last taken
+  pub fn take_events(&mut self) -> Vec<String> { std::mem::take(&mut self.events) }
+  /// Take an event if it was emitted since events were last taken
+  pub fn take_event(&mut self, event: &str) -> bool {
+    let count = self.events.len();
+    self.events.retain(|emitted| emitted != event);
+    self.events.len() != count
+  }
+
+  /// Take a transition, advance the current clip, and return the frame to show
+  pub fn update(&mut self) -> SrcRect {
+    let clip = &self.clips[&self.state];
+    let one_shot = !clip.repeat;
+    let next = self.transitions
+      .iter()
+      .filter(|transition| !one_shot || transition.is_triggered())
+      .filter(|transition| transition.from.as_ref().map_or(true, |from| *from == self.state))
+      .find(|transition| transition.conditions.iter().all(|condition| self.is_met(condition)))
+      .filter(|transition| transition.to != self.state) // a met transition to the current state holds it
+      .map(|transition| transition.to.clone());
+    self.triggers.clear();
+
+    if let Some(next) = next {
+      self.enter(next);
+    } else if self.timer.done() {
+      let clip = &self.clips[&self.state];
+      let frame = self.frame + 1;
+      if frame < clip.frames.len() {
+        self.show(frame);
+      } else if clip.repeat {
+        self.show(0);
+      } else {
+        let then = clip.then.clone().unwrap_or(self.default.clone());
+        if let Some(event) = clip.completion.clone() { self.events.push(event); }
+        self.enter(then);
+      }
+    }
+
+    self.get_src()
+  }
+}
+
+impl Systemize for Animator {
+  /// Update sprites based on their animators, frozen entities hold their frame
+  fn system(SysArgs { world, .. }: &mut SysArgs) -> Result<(), String> {
+    for (_, (animator, sprite)) in world.query::<(&mut Animator, &mut Sprite)>().without::<&Frozen>() {
+      sprite.src = animator.update();
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::engine::asset::sheet::parse_sheet;
+  use crate::engine::utility::alias::Size2;
+
+  use super::*;
+
+  fn make_sheet() -> SpriteSheet {
+    let data = parse_sheet(r#"{
+      "image": "creature.png",
+      "frame": { "w": 8, "h": 8 },
+      "duration": 0,
+      "clips": {
+        "idle": { "from": 0, "to": 1 },
+        "run": { "from": 2, "to": 3 },
+        "attack": { "from": 4, "to": 5, "repeat": false }
+      }
+    }"#).unwrap();
+    SpriteSheet::build(0, Size2::new(48, 8), data).unwrap()
+  }
+
+  #[test]
+  fn test_transition() {
+    let mut animator = Animator::build(&make_sheet(), "idle").unwrap()
+      .with_transition(Transition::to("run").when(Condition::Is(String::from("grounded"))).when(Condition::Above(String::from("speed"), 0.0))).unwrap()
+      .with_transition(Transition::to("idle").when(Condition::Below(String::from("speed"), 1.0))).unwrap();
+
+    animator.set_bool("grounded", true);
+    animator.set_float("speed", 0.0);
+    animator.update();
+    assert_eq!(animator.get_state(), "idle", "not moving");
+
+    animator.set_float("speed", 64.0);
+    animator.update();
+    assert_eq!(animator.get_state(), "run", "moving on the ground");
+
+    animator.set_float("speed", 0.0);
+    animator.update();
+    assert_eq!(animator.get_state(), "idle", "stopped moving");
+  }
+
+  #[test]
+  fn test_one_shot() {
+    let mut animator = Animator::build(&make_sheet(), "idle").unwrap()
+      .with_transition(Transition::to("attack").when(Condition::Triggered(String::from("attack")))).unwrap()
+      .with_transition(Transition::to("run").when(Condition::Is(String::from("moving")))).unwrap()
+      .with_event("attack", 1, "spit").unwrap();
+
+    animator.trigger("attack");
+    animator.set_bool("moving", true);
+    animator.update();
+    assert_eq!(animator.get_state(), "attack", "trigger takes the attack transition");
+    assert!(animator.take_events().is_empty(), "event is on the second frame");
+
+    animator.update();
+    assert_eq!(animator.get_state(), "attack", "one-shot is not interrupted");
+    assert!(animator.take_event("spit"), "event is emitted on the second frame");
+
+    animator.update();
+    assert_eq!(animator.get_state(), "idle", "one-shot returns to the default state");
+    assert_eq!(animator.get_src().origin.x, 0, "default state starts from its first frame");
+    animator.update();
+    assert_eq!(animator.get_state(), "run", "transitions resume once the one-shot completes");
+  }
+
+  #[test]
+  fn test_completion() {
+    let mut animator = Animator::build(&make_sheet(), "idle").unwrap()
+      .with_transition(Transition::to("attack").when(Condition::Triggered(String::from("attack")))).unwrap()
+      .with_completion("attack", "spit").unwrap();
+
+    animator.trigger("attack");
+    animator.update();
+    animator.update();
+    assert!(animator.take_events().is_empty(), "one-shot has not completed on its last frame");
+    animator.update();
+    assert!(animator.take_event("spit"), "event is emitted once the one-shot completes");
+    assert!(Animator::build(&make_sheet(), "idle").unwrap().with_completion("idle", "spit").is_err(), "repeating clips never complete");
+  }
+
+  #[test]
+  fn test_invalid_state() {
+    assert!(Animator::build(&make_sheet(), "jump").is_err(), "default state has no clip");
+    let animator = Animator::build(&make_sheet(), "idle").unwrap();
+    assert!(animator.with_transition(Transition::to("jump")).is_err(), "transition to a state with no clip");
+  }
+}
@@ -2,4 +2,5 @@ pub mod position;
 pub mod text;
 pub mod sprite;
 pub mod animation;
+pub mod animator;
 mod timer;
\ No newline at end of file
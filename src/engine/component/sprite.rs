@@ -2,9 +2,17 @@
  * A handle for a texture used for rendering
  */
 
+use crate::engine::asset::sheet::SpriteSheet;
 use crate::engine::asset::texture::{SrcRect, TextureKey};
 use crate::engine::geometry::shape::Vec2;
 
+/// Mirroring of a sprite, applied before it is rotated
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Flip {
+  pub horizontal: bool,
+  pub vertical: bool,
+}
+
 /// Render a texture to the screen each frame
 #[derive(Copy, Clone)]
 pub struct Sprite {
@@ -12,16 +20,35 @@ pub struct Sprite {
   pub src: SrcRect,
   pub rotation: f64,
   pub centroid: Option<Vec2<i32>>,
+  pub flip: Flip,
+  /// opacity the texture is drawn with, opaque at `u8::MAX`
+  pub alpha: u8,
 }
 
 impl Sprite {
   /// Instantiate a new Sprite component
   pub fn new(texture: TextureKey, src: SrcRect) -> Self {
-    Self { texture, src, rotation: 0.0, centroid: None }
+    Self { texture, src, rotation: 0.0, centroid: None, flip: Flip::default(), alpha: u8::MAX }
+  }
+  /// Instantiate a new Sprite component of a named frame of a sheet
+  pub fn from_frame(sheet: &SpriteSheet, frame: &str) -> Result<Self, String> {
+    Ok(Self::new(sheet.texture, sheet.get_frame(frame)?.src))
+  }
+  /// Instantiate a new Sprite component of the first frame of a clip of a sheet
+  pub fn from_clip(sheet: &SpriteSheet, clip: &str) -> Result<Self, String> {
+    let frame = sheet.get_clip_frames(clip)?.next().ok_or(format!("Clip '{}' has no frames", clip))?;
+    Ok(Self::new(sheet.texture, frame.src))
   }
   /// Rotate the sprite around an optional centroid
   pub fn rotate(&mut self, rotation: f64, centroid: Option<Vec2<i32>>) {
     self.rotation = rotation % 360.0;
     self.centroid = centroid;
   }
+  /// Mirror the sprite before it is rotated
+  pub fn flip(&mut self, flip: Flip) { self.flip = flip; }
+  /// Draw the sprite with an opacity in the unit interval
+  pub fn with_opacity(mut self, opacity: f32) -> Self {
+    self.alpha = (opacity.clamp(0.0, 1.0) * u8::MAX as f32).round() as u8;
+    self
+  }
 }
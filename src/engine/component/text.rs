@@ -3,13 +3,50 @@
   * Text rendering components and utilities
   */
 
+use sdl2::pixels::PixelFormatEnum;
+use sdl2::rect::Rect;
+use sdl2::render::BlendMode;
+use sdl2::surface::Surface;
 use sdl2::ttf::Font;
 
+use crate::engine::asset::bitmap::{BitmapFont, PlacedGlyph};
 use crate::engine::asset::texture::{TextureKey, TextureLoader};
+use crate::engine::asset::typography::{FontHandle, TypefaceLoader};
 use crate::engine::geometry::shape::Vec2;
-use crate::engine::utility::alias::Size2;
+use crate::engine::utility::alias::{Size, Size2};
 use crate::engine::utility::color::RGBA;
-use crate::engine::utility::store::next_key;
+use crate::engine::utility::layout::{count_chars, Justify, Layout, LayoutRun, layout_spans, parse_markup, SpanStyle, Whitespace};
+use crate::engine::utility::text::NEW_LINE;
+
+/// Lay out the content of a text into a box of `width` pixels
+///
+/// Content is parsed for inline markup and wrapped by the metrics of the font it is rendered with
+#[derive(Copy, Clone, Debug, Default)]
+pub struct TextLayout {
+  pub width: Size,
+  pub justify: Justify,
+  /// overrides the height of the font
+  pub line_height: Option<Size>,
+  pub whitespace: Whitespace,
+}
+
+impl TextLayout {
+  /// Instantiate a new layout of `width` and `justify`
+  pub fn new(width: Size, justify: Justify) -> Self { Self { width, justify, line_height: None, whitespace: Whitespace::default() } }
+  /// Set the distance between lines
+  pub fn with_line_height(mut self, line_height: Size) -> Self {
+    self.line_height = Some(line_height);
+    self
+  }
+  /// Set the treatment of whitespace between words
+  pub fn with_whitespace(mut self, whitespace: Whitespace) -> Self {
+    self.whitespace = whitespace;
+    self
+  }
+}
+
+/// Horizontal offset emphasised text is drawn again at
+const EMPHASIS_OFFSET: i32 = 1;
 
 pub struct Text {
   content: String,
@@ -17,6 +54,11 @@ pub struct Text {
   color: RGBA,
   texture: Option<TextureKey>,
   dimensions: Size2,
+  layout: Option<TextLayout>,
+  reveal: Option<usize>,
+  font: Option<FontHandle>,
+  /// glyphs to draw when rendered in a bitmap font, rather than a texture
+  glyphs: Option<Vec<PlacedGlyph>>,
 }
 
 impl Text {
@@ -28,13 +70,34 @@ impl Text {
       texture: None,
       color,
       dimensions: Vec2::default(),
+      layout: None,
+      reveal: None,
+      font: None,
+      glyphs: None,
     }
   }
-  /// call with `Text::new` to set `content` with `font` in `color`
+  /// call with `Text::new` to render in a loaded typeface rather than the default, before setting content
+  pub fn with_font(mut self, font: FontHandle) -> Self {
+    self.font = Some(font);
+    self.dirty = true;
+    self
+  }
+  /// call with `Text::new` to lay out content into a paragraph, before setting content
+  pub fn with_layout(mut self, layout: TextLayout) -> Self {
+    self.layout = Some(layout);
+    self.dirty = true;
+    self
+  }
+  /// call with `Text::new` to set `content` in its typeface and color
   /// ## Panics
-  /// Will panic if the texture cannot be built
-  pub fn with_content<'fonts, 'app>(mut self, content: impl Into<String>, font: &Font<'fonts, 'app>, texture_loader: &mut TextureLoader) -> Self {
+  /// Will panic if the typeface is not loaded or the texture cannot be built
+  pub fn with_content(mut self, content: impl Into<String>, typefaces: &TypefaceLoader, texture_loader: &mut TextureLoader) -> Self {
     self.set_content(content.into());
+    if let Some(bitmap) = typefaces.get_bitmap(self.font.as_ref()) {
+      self.build_glyphs(bitmap).expect("Failed to lay out glyphs");
+      return self;
+    }
+    let font = typefaces.get(self.font.as_ref()).expect("Failed to get typeface");
     self.build_texture(font, texture_loader).expect("Failed to rebuild texture");
     self
   }
@@ -44,10 +107,16 @@ impl Text {
     if self.content.is_empty() { return Ok(()); } // no need to build a texture for empty content
 
     // create texture of the content in the font and color
-    let surface = font
-      .render(&self.content)
-      .blended(self.color)
-      .map_err(|e| e.to_string())?;
+    let surface = if let Some(layout) = self.layout {
+      self.render_layout(font, layout)?
+    } else {
+      let content = self.content.chars().take(self.reveal.unwrap_or(usize::MAX)).collect::<String>();
+      if content.is_empty() { return Ok(()); } // nothing has been revealed yet
+      font
+        .render(&content)
+        .blended(self.color)
+        .map_err(|e| e.to_string())?
+    };
     let texture = texture_loader
       .build_from_surface(surface)
       .map_err(|e| e.to_string())?;
@@ -56,16 +125,10 @@ impl Text {
     // update the texture in the store this text component references
     if let Some(texture_key) = self.texture {
       // replace the texture in the store
-      texture_loader
-        .use_store()
-        .set(texture_key, texture);
+      texture_loader.replace(texture_key, texture)?;
     } else {
       // add a new texture to the store
-      let texture_key = next_key();
-      self.texture = Some(texture_key);
-      texture_loader
-        .use_store()
-        .add(texture_key, texture);
+      self.texture = Some(texture_loader.add(texture));
     }
 
     // texture and dimensions are valid for the content
@@ -74,6 +137,92 @@ impl Text {
     Ok(())
   }
 
+  /// Render the content laid out into lines
+  ///
+  /// The surface covers the whole layout regardless of how much is revealed, so revealing text never moves it
+  fn render_layout<'fonts, 'app>(&self, font: &Font<'fonts, 'app>, layout: TextLayout) -> Result<Surface<'static>, String> {
+    let spans = parse_markup(&self.content)?;
+    let line_height = layout.line_height.unwrap_or(font.height() as Size);
+    let measure = |text: &str, style: SpanStyle| {
+      let (width, ..) = font.size_of(text).unwrap_or_default();
+      if style.emphasis { width + EMPHASIS_OFFSET as Size } else { width }
+    };
+    let paragraph = layout_spans(&spans, layout.width, line_height, layout.justify, layout.whitespace, measure);
+
+    let mut surface = Surface::new(paragraph.dimensions.x.max(1), paragraph.dimensions.y.max(1), PixelFormatEnum::RGBA32)?;
+    for (pen, run, text) in self.reveal_runs(&paragraph) {
+      if text.trim().is_empty() { continue; }
+      let mut rendered = font
+        .render(&text)
+        .blended(run.style.color.unwrap_or(self.color))
+        .map_err(|e| e.to_string())?;
+      let destination = Rect::new(pen.x, pen.y, rendered.width(), rendered.height());
+      rendered.set_blend_mode(BlendMode::None)?;
+      rendered.blit(None, &mut surface, destination)?;
+
+      // embolden emphasised runs by drawing them again offset
+      if run.style.emphasis {
+        rendered.set_blend_mode(BlendMode::Blend)?;
+        rendered.blit(None, &mut surface, Rect::new(pen.x + EMPHASIS_OFFSET, pen.y, rendered.width(), rendered.height()))?;
+      }
+    }
+
+    Ok(surface)
+  }
+
+  /// Get the position and revealed text of each run of a layout
+  fn reveal_runs<'a>(&self, paragraph: &'a Layout) -> Vec<(Vec2<i32>, &'a LayoutRun, String)> {
+    let mut remaining = self.reveal.unwrap_or(usize::MAX);
+    let mut runs = Vec::new();
+    for (index, line) in paragraph.lines.iter().enumerate() {
+      for run in &line.runs {
+        if remaining == 0 { return runs; }
+        let text = run.text.chars().take(remaining).collect::<String>();
+        remaining -= text.chars().count();
+        let pen = Vec2::new(run.x as i32, index as i32 * paragraph.line_height as i32);
+        runs.push((pen, run, text));
+      }
+    }
+    runs
+  }
+
+  /// Place the glyphs of the content in a bitmap font, recalculating dimensions
+  fn build_glyphs(&mut self, font: &BitmapFont) -> Result<(), String> {
+    let mut glyphs = Vec::new();
+    if let Some(layout) = self.layout {
+      let spans = parse_markup(&self.content)?;
+      let line_height = layout.line_height.unwrap_or(font.get_line_height());
+      let measure = |text: &str, style: SpanStyle| {
+        if style.emphasis { font.measure(text) + EMPHASIS_OFFSET as Size } else { font.measure(text) }
+      };
+      let paragraph = layout_spans(&spans, layout.width, line_height, layout.justify, layout.whitespace, measure);
+      for (pen, run, text) in self.reveal_runs(&paragraph) {
+        let color = run.style.color.unwrap_or(self.color);
+        font.place(&text, pen, color, &mut glyphs);
+        if run.style.emphasis { font.place(&text, pen + Vec2::new(EMPHASIS_OFFSET, 0), color, &mut glyphs); }
+      }
+      self.dimensions = paragraph.dimensions;
+    } else {
+      let line_height = font.get_line_height();
+      let mut remaining = self.reveal.unwrap_or(usize::MAX);
+      let mut width = 0;
+      let lines = self.content.split(NEW_LINE).collect::<Vec<_>>();
+      for (index, line) in lines.iter().enumerate() {
+        width = width.max(font.measure(line));
+        let text = line.chars().take(remaining).collect::<String>();
+        remaining = remaining.saturating_sub(text.chars().count() + 1); // newlines are revealed as characters
+        font.place(&text, Vec2::new(0, index as i32 * line_height as i32), self.color, &mut glyphs);
+      }
+      self.dimensions = Size2::new(width, lines.len() as Size * line_height);
+    }
+
+    // glyphs and dimensions are valid for the content
+    self.glyphs = Some(glyphs);
+    self.dirty = false;
+
+    Ok(())
+  }
+
   /// Updates the content of a text
   pub fn set_content(&mut self, content: impl Into<String>) {
     let content = content.into();
@@ -81,12 +230,33 @@ impl Text {
     self.dirty = true;
     self.content = content;
   }
+  /// Get the typeface of the text, `None` being the default typeface
+  pub fn get_font(&self) -> Option<&FontHandle> { self.font.as_ref() }
+  /// Changes the typeface of the text
+  pub fn set_font(&mut self, font: Option<FontHandle>) {
+    if self.font == font { return; }
+    self.dirty = true;
+    self.font = font;
+  }
   /// Get the raw, potentially dirty, text content
   pub fn get_text(&self) -> &String { &self.content }
+  /// Get the number of characters shown, excluding markup
+  pub fn get_visible_len(&self) -> usize {
+    if self.layout.is_none() { return self.content.chars().count(); }
+    parse_markup(&self.content)
+      .map(|spans| count_chars(&spans))
+      .unwrap_or(self.content.chars().count())
+  }
+  /// Only show the first `count` characters of the content, or all of it with `None`
+  pub fn set_reveal(&mut self, count: Option<usize>) {
+    if self.reveal == count { return; }
+    self.dirty = true;
+    self.reveal = count;
+  }
   /// Updates the content of a text and rebuilds the texture recalculating dimensions
   /// ## Panics
-  /// Will panic if the texture cannot be built
-  pub fn get_content<'ttf, 'a>(&mut self, typeface: &Font<'ttf, 'a>, texture_loader: &mut TextureLoader) -> Option<TextureKey> {
+  /// Will panic if the typeface is not loaded or the texture cannot be built
+  pub fn get_content(&mut self, typefaces: &TypefaceLoader, texture_loader: &mut TextureLoader) -> Option<TextureKey> {
     // if the content has changed, the texture is stale
     if self.dirty {
       self.texture = None;
@@ -95,6 +265,7 @@ impl Text {
 
     // check if texture is missing
     if self.texture.is_none() && !self.content.is_empty() {
+      let typeface = typefaces.get(self.font.as_ref()).expect("Failed to get typeface");
       self
         .build_texture(typeface, texture_loader)
         .expect("Failed to rebuild texture"); // panic is fine, as failing to rebuild a texture is unexpected
@@ -102,6 +273,15 @@ impl Text {
 
     self.texture
   }
+  /// Get the glyphs to draw the content in a bitmap font, placing them again if the content has changed
+  /// ## Panics
+  /// Will panic if the content cannot be laid out
+  pub fn get_glyphs(&mut self, font: &BitmapFont) -> &[PlacedGlyph] {
+    if self.dirty || self.glyphs.is_none() {
+      self.build_glyphs(font).expect("Failed to lay out glyphs"); // panic is fine, as failing to lay out glyphs is unexpected
+    }
+    self.glyphs.as_deref().unwrap_or_default()
+  }
   /// Get the dimensions of the text
   pub fn get_dimensions(&self) -> Size2 {
     self.dimensions
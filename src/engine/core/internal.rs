@@ -3,6 +3,7 @@ use sdl2::keyboard::Keycode;
 use crate::engine::component::position::Position;
 
 use crate::engine::component::text::Text;
+use crate::engine::ecs::spatial::SpatialIndex;
 use crate::engine::ecs::system::{Schedule, SysArgs, Systemize, SystemManager, SystemTag};
 use crate::engine::ecs::world::World;
 use crate::engine::render::camera::{CameraTether, Sticky1};
@@ -15,6 +16,9 @@ use crate::engine::utility::time::SECOND_MS;
  * Internal engine systems
  */
 
+/// Frames between captures when capturing a sequence from the hotkey
+pub const CAPTURE_INTERVAL: u32 = 4;
+
 struct FpsText;
 
 static mut FPS_TEXT: Option<Entity> = None;
@@ -24,9 +28,12 @@ static mut MAX_FPS: DeltaMS = DeltaMS::MIN;
 /// Add internal systems to the system manager
 pub fn add_internal_systems(systems: &mut SystemManager) {
   systems.add(Schedule::PostUpdate, SystemTag::Internal, sys_fullscreen_toggle).expect("Failed to add fullscreen toggle system");
+  systems.add(Schedule::PostUpdate, SystemTag::Internal, sys_capture).expect("Failed to add capture system");
+  systems.add(Schedule::PostUpdate, SystemTag::Internal, sys_audio).expect("Failed to add audio system");
 
   //systems.add(Schedule::PostUpdate, sys_update_fps_text);
   systems.add(Schedule::PostUpdate, SystemTag::Internal, CameraTether::system).expect("Failed to add camera tether system");
+  systems.add(Schedule::PostUpdate, SystemTag::Internal, SpatialIndex::system).expect("Failed to add spatial index system");
   systems.add(Schedule::PostUpdate, SystemTag::Internal, Renderer::system).expect("Failed to add renderer system");
 }
 
@@ -45,6 +52,23 @@ fn sys_fullscreen_toggle(SysArgs { render, event, .. }: &mut SysArgs) -> Result<
   Ok(())
 }
 
+/// Take a screenshot, or toggle capturing every `CAPTURE_INTERVAL` frames while shift is held
+fn sys_capture(SysArgs { render, event, .. }: &mut SysArgs) -> Result<(), String> {
+  if !event.is_key_pressed(Keycode::F12) { return Ok(()); }
+  if event.is_key_held(Keycode::LShift) || event.is_key_held(Keycode::RShift) {
+    let sequencing = render.capture.is_sequencing();
+    render.capture.set_interval(if sequencing { None } else { Some(CAPTURE_INTERVAL) });
+  } else {
+    render.capture.queue_screenshot();
+  }
+  Ok(())
+}
+
+/// Start queued music once the current music fades out
+fn sys_audio(SysArgs { asset, .. }: &mut SysArgs) -> Result<(), String> {
+  asset.audio.update()
+}
+
 #[allow(dead_code)]
 fn sys_update_fps_text(SysArgs { delta, world, .. }: &mut SysArgs) -> Result<(), String> {
   unsafe {
@@ -7,6 +7,7 @@ use crate::engine::asset::asset::AssetManager;
 use crate::engine::ecs::system::SystemManager;
 use crate::engine::ecs::world::World;
 use crate::engine::render::camera::Camera;
+use crate::engine::render::renderer::Renderer;
 use crate::engine::utility::state::State;
 
 /// Structures needed for the application lifecycle
@@ -14,6 +15,7 @@ pub struct LifecycleArgs<'app, 'fonts> {
   pub world: &'app mut World,
   pub system: &'app mut SystemManager,
   pub camera: &'app mut Camera,
+  pub render: &'app mut Renderer,
   pub state: &'app mut State,
   pub asset: &'app mut AssetManager<'fonts>,
 }
@@ -24,10 +26,11 @@ impl<'app, 'fonts> LifecycleArgs<'app, 'fonts> {
     world: &'app mut World,
     system: &'app mut SystemManager,
     camera: &'app mut Camera,
+    render: &'app mut Renderer,
     state: &'app mut State,
     asset: &'app mut AssetManager<'fonts>,
   ) -> Self {
-    Self { world, camera, system, asset, state }
+    Self { world, camera, render, system, asset, state }
   }
 }
 
@@ -3,19 +3,33 @@
  */
 
 use std::time::Duration;
+use crate::engine::asset::manifest::{AssetManifest, Preloader};
+use crate::engine::asset::texture::TextureScope;
 use crate::engine::core::lifecycle::LifecycleArgs;
+use crate::engine::ecs::system::SystemTag;
 use crate::engine::utility::time::Timer;
 
+/// Time spent preloading assets each frame while the loading scene is shown
+pub const PRELOAD_BUDGET: Duration = Duration::from_millis(8);
+
 /// A scene is a defined state of the game
 pub trait Scene {
+  /// List the assets to load before the scene is set up
+  fn manifest(&mut self) -> Result<AssetManifest, String> { Ok(AssetManifest::default()) }
   fn setup(&mut self, args: &mut LifecycleArgs);
   fn destroy(&mut self, args: &mut LifecycleArgs);
 }
 
 /// Scene manager is responsible for managing the current scene
+///
+/// Textures referenced while a scene is current are released when it is destroyed. The assets listed in the manifest
+/// of the next scene are checked and preloaded before it is set up, showing the loading scene in the meantime.
 pub struct SceneManager {
   scene: Option<Box<dyn Scene>>,
   next: Option<Box<dyn Scene>>,
+  textures: Option<TextureScope>,
+  loading: Option<Box<dyn Scene>>,
+  preloading: Option<(Box<dyn Scene>, Preloader)>,
 }
 
 impl SceneManager {
@@ -24,18 +38,61 @@ impl SceneManager {
     Self {
       scene: None,
       next: Some(Box::new(initial_scene)),
+      textures: None,
+      loading: None,
+      preloading: None,
     }
   }
+  /// Show a scene while the assets of the next scene are preloaded
+  pub fn with_loading(mut self, scene: impl Scene + 'static) -> Self {
+    self.loading = Some(Box::new(scene));
+    self
+  }
   /// Queue the next scene
   pub fn queue_next(&mut self, scene: impl Scene + 'static) { self.next = Some(Box::new(scene)); }
   /// Check if there is a scene in the queue
   pub fn is_queue(&self) -> bool { self.next.is_some() }
-  /// Load the next scene
-  fn load(&mut self, args: &mut LifecycleArgs) {
-    if let Some(next) = self.next.as_mut() {
-      next.setup(args);
-      self.scene = self.next.take();
+  /// Get the portion of the next scene's assets that are preloaded, if they are being preloaded
+  pub fn get_progress(&self) -> Option<f32> {
+    self.preloading.as_ref().map(|(_, preloader)| preloader.get_progress())
+  }
+  /// Check the assets of the next scene, then preload them showing the loading scene, or set up the next scene
+  fn load(&mut self, args: &mut LifecycleArgs) -> Result<(), String> {
+    let Some(mut next) = self.next.take() else { return Ok(()); };
+    let scope = args.asset.texture.open_scope();
+    args.asset.texture.set_scope(Some(scope));
+    self.textures = Some(scope);
+
+    let manifest = next.manifest()?;
+    manifest.validate()?;
+    let mut preloader = Preloader::new(manifest);
+    match self.loading.as_mut() {
+      Some(loading) if !preloader.is_done() => {
+        loading.setup(args);
+        self.preloading = Some((next, preloader));
+      }
+      _ => {
+        preloader.finish(args.asset)?;
+        next.setup(args);
+        self.scene = Some(next);
+      }
     }
+    Ok(())
+  }
+  /// Preload the assets of the next scene within the frame budget, then replace the loading scene with it
+  pub fn preload(&mut self, args: &mut LifecycleArgs) -> Result<(), String> {
+    let Some((_, preloader)) = self.preloading.as_mut() else { return Ok(()); };
+    preloader.step(args.asset, PRELOAD_BUDGET)?;
+    if !preloader.is_done() { return Ok(()); }
+
+    let (mut next, ..) = self.preloading.take().expect("Scene is preloading");
+    if let Some(loading) = self.loading.as_mut() { loading.destroy(args); }
+    args.system.remove(SystemTag::Scene);
+    args.system.remove(SystemTag::Suspendable);
+    args.world.free_all_now();
+    next.setup(args);
+    self.scene = Some(next);
+    Ok(())
   }
   /// Destroy the current scene
   fn destroy(&mut self, args: &mut LifecycleArgs) {
@@ -43,11 +100,14 @@ impl SceneManager {
       current.destroy(args);
     }
     args.world.free_all_now();
+    if let Some(scope) = self.textures.take() {
+      args.asset.texture.close_scope(scope).expect("Failed to release scene textures");
+    }
   }
   /// Destroy the current scene and set up the next
-  pub fn next(&mut self, args: &mut LifecycleArgs) {
+  pub fn next(&mut self, args: &mut LifecycleArgs) -> Result<(), String> {
     self.destroy(args);
-    self.load(args);
+    self.load(args)
   }
 }
 
@@ -1,2 +1,3 @@
+pub mod spatial;
 pub mod system;
 pub mod world;
\ No newline at end of file
@@ -0,0 +1,305 @@
+/**
+ * Spatial indexing of entities for culling and proximity queries
+ */
+
+use std::collections::{HashMap, HashSet};
+
+use hecs::{Entity, Or};
+
+use crate::engine::component::position::Position;
+use crate::engine::component::sprite::Sprite;
+use crate::engine::component::text::Text;
+use crate::engine::ecs::system::{SysArgs, Systemize};
+use crate::engine::geometry::shape::{Rec2, Vec2};
+use crate::engine::render::camera::{Sticky1, Sticky2};
+use crate::engine::utility::alias::{Coordinate, Size};
+
+/// Width and height of a cell in the spatial grid in worldspace
+pub const SPATIAL_CELL_SIZE: Size = 64;
+
+/// The worldspace bounds of an indexed entity
+pub type SpatialBounds = Rec2<f32, Size>;
+
+/// An entity stored in the spatial index
+struct SpatialEntry {
+  bounds: SpatialBounds,
+  generation: u64,
+}
+
+/// A uniform grid of cells that stores the entities overlapping them
+///
+/// Entities with a `Position` and a `Sprite` or `Text` are indexed each frame by the engine.
+/// Entities on sticky layers are positioned relative to the camera, so are not indexed.
+pub struct SpatialIndex {
+  cell_size: Size,
+  cells: HashMap<Coordinate, HashSet<Entity>>,
+  entries: HashMap<Entity, SpatialEntry>,
+  generation: u64,
+}
+
+impl Default for SpatialIndex {
+  /// Instantiate a spatial index of `SPATIAL_CELL_SIZE` cells
+  fn default() -> Self { Self::new(SPATIAL_CELL_SIZE) }
+}
+
+impl SpatialIndex {
+  /// Instantiate a new spatial index with cells of `cell_size`
+  pub fn new(cell_size: Size) -> Self {
+    Self {
+      cell_size: cell_size.max(1),
+      cells: HashMap::new(),
+      entries: HashMap::new(),
+      generation: 0,
+    }
+  }
+
+  /// Get the range of cells that `bounds` overlaps
+  fn cells_of(&self, bounds: &SpatialBounds) -> (Coordinate, Coordinate) {
+    let cell_size = self.cell_size as f32;
+    let extent = bounds.origin + Vec2::from(bounds.size);
+    let from = Coordinate::new((bounds.origin.x / cell_size).floor() as i32, (bounds.origin.y / cell_size).floor() as i32);
+    // an entity with no size still occupies the cell of its origin
+    let to = Coordinate::new(
+      ((extent.x / cell_size).ceil() as i32 - 1).max(from.x),
+      ((extent.y / cell_size).ceil() as i32 - 1).max(from.y),
+    );
+    (from, to)
+  }
+
+  /// Add an entity to the cells overlapping `bounds`
+  fn link(&mut self, entity: Entity, bounds: &SpatialBounds) {
+    let (from, to) = self.cells_of(bounds);
+    for y in from.y..=to.y {
+      for x in from.x..=to.x {
+        self.cells.entry(Coordinate::new(x, y)).or_default().insert(entity);
+      }
+    }
+  }
+  /// Remove an entity from the cells overlapping `bounds`
+  fn unlink(&mut self, entity: Entity, bounds: &SpatialBounds) {
+    let (from, to) = self.cells_of(bounds);
+    for y in from.y..=to.y {
+      for x in from.x..=to.x {
+        let coordinate = Coordinate::new(x, y);
+        if let Some(cell) = self.cells.get_mut(&coordinate) {
+          cell.remove(&entity);
+          if cell.is_empty() { self.cells.remove(&coordinate); }
+        }
+      }
+    }
+  }
+
+  /// Add an entity to the index, or update its bounds if already indexed
+  pub fn insert(&mut self, entity: Entity, bounds: SpatialBounds) {
+    let generation = self.generation;
+    if let Some(entry) = self.entries.get_mut(&entity) {
+      entry.generation = generation;
+      if entry.bounds.origin == bounds.origin && entry.bounds.size == bounds.size { return; } // entity has not moved
+      let old_bounds = entry.bounds;
+      entry.bounds = bounds;
+      self.unlink(entity, &old_bounds);
+    } else {
+      self.entries.insert(entity, SpatialEntry { bounds, generation });
+    }
+    self.link(entity, &bounds);
+  }
+  /// Remove an entity from the index
+  pub fn remove(&mut self, entity: Entity) {
+    if let Some(entry) = self.entries.remove(&entity) {
+      self.unlink(entity, &entry.bounds);
+    }
+  }
+  /// Remove every entity from the index
+  pub fn clear(&mut self) {
+    self.cells.clear();
+    self.entries.clear();
+  }
+
+  /// Start a new pass of insertions
+  ///
+  /// Entities not inserted before the pass is ended with `end_pass` are removed
+  pub fn begin_pass(&mut self) { self.generation += 1; }
+  /// Remove any entities not inserted since `begin_pass`
+  pub fn end_pass(&mut self) {
+    let generation = self.generation;
+    let stale = self.entries
+      .iter()
+      .filter(|(_, entry)| entry.generation != generation)
+      .map(|(entity, _)| *entity)
+      .collect::<Vec<_>>();
+    for entity in stale { self.remove(entity); }
+  }
+
+  /// Get the entities overlapping `area` ordered by when they were spawned
+  pub fn query(&self, area: &SpatialBounds) -> Vec<Entity> {
+    let (from, to) = self.cells_of(area);
+    let mut found = HashSet::new();
+    for y in from.y..=to.y {
+      for x in from.x..=to.x {
+        if let Some(cell) = self.cells.get(&Coordinate::new(x, y)) {
+          found.extend(cell.iter().copied());
+        }
+      }
+    }
+
+    let mut entities = found
+      .into_iter()
+      .filter(|entity| {
+        self.entries
+          .get(entity)
+          .is_some_and(|entry| overlaps_or_touches(&entry.bounds, area))
+      })
+      .collect::<Vec<_>>();
+    entities.sort_by_key(|entity| entity.id());
+    entities
+  }
+  /// Get the entities whose bounds are within `radius` of `position`
+  pub fn query_near(&self, position: Vec2<f32>, radius: f32) -> Vec<Entity> {
+    let radius = radius.max(0.0);
+    let diameter = (radius * 2.0).ceil() as Size;
+    let area = SpatialBounds::new(position - radius, Vec2::new(diameter, diameter));
+    self
+      .query(&area)
+      .into_iter()
+      .filter(|entity| {
+        self.entries
+          .get(entity)
+          .is_some_and(|entry| distance_to_bounds(&entry.bounds, position) <= radius)
+      })
+      .collect()
+  }
+  /// Get the indexed bounds of an entity
+  pub fn get_bounds(&self, entity: Entity) -> Option<SpatialBounds> {
+    self.entries.get(&entity).map(|entry| entry.bounds)
+  }
+  /// Get the number of entities in the index
+  pub fn len(&self) -> usize { self.entries.len() }
+  /// Check if the index has no entities
+  pub fn is_empty(&self) -> bool { self.entries.is_empty() }
+}
+
+/// Check if two bounds overlap, treating zero sized bounds as a point
+fn overlaps_or_touches(bounds: &SpatialBounds, area: &SpatialBounds) -> bool {
+  if bounds.size.x == 0 || bounds.size.y == 0 {
+    let extent = area.origin + Vec2::from(area.size);
+    return bounds.origin.x >= area.origin.x
+      && bounds.origin.x <= extent.x
+      && bounds.origin.y >= area.origin.y
+      && bounds.origin.y <= extent.y;
+  }
+  bounds.overlaps(area)
+}
+
+/// Get the shortest distance from `position` to the edges of `bounds`
+fn distance_to_bounds(bounds: &SpatialBounds, position: Vec2<f32>) -> f32 {
+  let extent = bounds.origin + Vec2::from(bounds.size);
+  let mut closest = position;
+  closest.clamp(&bounds.origin, &extent);
+  (position - closest).get_magnitude()
+}
+
+/// Query for entities to index
+type QuerySpatial<'a> = (&'a Position, Or<&'a Sprite, &'a Text>);
+
+/// Index the bounds of positioned sprites and text in worldspace
+impl Systemize for SpatialIndex {
+  fn system(SysArgs { world, spatial, .. }: &mut SysArgs) -> Result<(), String> {
+    spatial.begin_pass();
+    for (entity, (position, renderable)) in world
+      .query::<QuerySpatial>()
+      .without::<Or<&Sticky1, &Sticky2>>()
+    {
+      let size = match renderable {
+        Or::Left(sprite) | Or::Both(sprite, ..) => sprite.src.size,
+        Or::Right(text) => text.get_dimensions(),
+      };
+      spatial.insert(entity, SpatialBounds::new(position.0, size));
+    }
+    spatial.end_pass();
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use hecs::World;
+
+  use super::*;
+
+  fn make_bounds(x: f32, y: f32, w: Size, h: Size) -> SpatialBounds {
+    SpatialBounds::new(Vec2::new(x, y), Vec2::new(w, h))
+  }
+
+  #[test]
+  fn test_query_area() {
+    let mut world = World::new();
+    let (near, far) = (world.spawn(()), world.spawn(()));
+
+    let mut index = SpatialIndex::new(32);
+    index.insert(near, make_bounds(8.0, 8.0, 16, 16));
+    index.insert(far, make_bounds(640.0, 320.0, 16, 16));
+
+    assert_eq!(index.query(&make_bounds(0.0, 0.0, 100, 100)), vec![near], "only the near entity is in the area");
+    assert_eq!(index.query(&make_bounds(600.0, 300.0, 100, 100)), vec![far], "only the far entity is in the area");
+    assert!(index.query(&make_bounds(200.0, 200.0, 16, 16)).is_empty(), "no entities are in the area");
+  }
+
+  #[test]
+  fn test_query_spans_cells() {
+    let mut world = World::new();
+    let wide = world.spawn(());
+
+    let mut index = SpatialIndex::new(16);
+    index.insert(wide, make_bounds(-20.0, 0.0, 200, 8));
+
+    assert_eq!(index.query(&make_bounds(-16.0, 0.0, 4, 4)), vec![wide], "entity is found from its first cell");
+    assert_eq!(index.query(&make_bounds(170.0, 0.0, 4, 4)), vec![wide], "entity is found from its last cell");
+  }
+
+  #[test]
+  fn test_update_bounds() {
+    let mut world = World::new();
+    let entity = world.spawn(());
+
+    let mut index = SpatialIndex::new(32);
+    index.insert(entity, make_bounds(0.0, 0.0, 16, 16));
+    index.insert(entity, make_bounds(256.0, 0.0, 16, 16));
+
+    assert!(index.query(&make_bounds(0.0, 0.0, 32, 32)).is_empty(), "entity has left its old cell");
+    assert_eq!(index.query(&make_bounds(256.0, 0.0, 32, 32)), vec![entity], "entity is in its new cell");
+    assert_eq!(index.len(), 1, "entity is indexed once");
+  }
+
+  #[test]
+  fn test_pass_removes_stale() {
+    let mut world = World::new();
+    let (kept, freed) = (world.spawn(()), world.spawn(()));
+
+    let mut index = SpatialIndex::new(32);
+    index.begin_pass();
+    index.insert(kept, make_bounds(0.0, 0.0, 16, 16));
+    index.insert(freed, make_bounds(0.0, 0.0, 16, 16));
+    index.end_pass();
+
+    index.begin_pass();
+    index.insert(kept, make_bounds(0.0, 0.0, 16, 16));
+    index.end_pass();
+
+    assert_eq!(index.query(&make_bounds(0.0, 0.0, 32, 32)), vec![kept], "entities not inserted during the pass are removed");
+    assert!(index.get_bounds(freed).is_none(), "freed entity has no bounds");
+  }
+
+  #[test]
+  fn test_query_near() {
+    let mut world = World::new();
+    let (close, corner, distant) = (world.spawn(()), world.spawn(()), world.spawn(()));
+
+    let mut index = SpatialIndex::new(32);
+    index.insert(close, make_bounds(10.0, 0.0, 8, 8));
+    index.insert(corner, make_bounds(20.0, 20.0, 8, 8));
+    index.insert(distant, make_bounds(100.0, 0.0, 8, 8));
+
+    assert_eq!(index.query_near(Vec2::new(0.0, 0.0), 24.0), vec![close], "the corner is further than the radius");
+    assert_eq!(index.query_near(Vec2::new(0.0, 0.0), 30.0), vec![close, corner], "the corner is within the radius");
+  }
+}
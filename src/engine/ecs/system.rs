@@ -9,8 +9,10 @@ use std::hash::Hash;
 use crate::engine::asset::asset::AssetManager;
 use crate::engine::core::event::EventStore;
 use crate::engine::core::scene::SceneManager;
+use crate::engine::ecs::spatial::SpatialIndex;
 use crate::engine::ecs::world::World;
 use crate::engine::render::camera::Camera;
+use crate::engine::render::debug::DebugDraw;
 use crate::engine::render::renderer::Renderer;
 use crate::engine::utility::alias::DeltaMS;
 use crate::engine::utility::state::State;
@@ -46,6 +48,8 @@ pub struct SysArgs<'app, 'fonts> {
   pub scene: &'app mut SceneManager,
   pub asset: &'app mut AssetManager<'fonts>,
   pub state: &'app mut State,
+  pub spatial: &'app mut SpatialIndex,
+  pub debug: &'app mut DebugDraw,
 }
 
 /// A mutable context provided to systems
@@ -60,6 +64,8 @@ impl<'app, 'fonts> SysArgs<'app, 'fonts> {
     scene: &'app mut SceneManager,
     state: &'app mut State,
     asset: &'app mut AssetManager<'fonts>,
+    spatial: &'app mut SpatialIndex,
+    debug: &'app mut DebugDraw,
   ) -> Self {
     Self {
       delta,
@@ -70,6 +76,8 @@ impl<'app, 'fonts> SysArgs<'app, 'fonts> {
       scene,
       state,
       asset,
+      spatial,
+      debug,
     }
   }
 }
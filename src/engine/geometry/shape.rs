@@ -308,7 +308,7 @@ impl<T> Div<T> for Vec2<T> where T: UnitPrimitive {
 // Rectangle 2D //
 
 /// A Rectangle representation in 2D space of some numeric type `T`
-#[derive(Clone, Copy, Debug, Default)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct Rec2<T: UnitPrimitive, U: SizePrimitive> {
   pub origin: Vec2<T>,
   pub size: Vec2<U>,
@@ -342,6 +342,24 @@ impl Rec2<f32, Size> {
   pub fn centroid(&self) -> Vec2<f32> {
     Vec2::new(self.origin.x + (self.size.x / 2) as f32, self.origin.y + (self.size.y / 2) as f32)
   }
+
+  /// Does the rectangle contain a point? Points on the edge are contained
+  pub fn contains_point(&self, point: Vec2<f32>) -> bool {
+    let extent = self.origin + Vec2::from(self.size);
+    point.x >= self.origin.x && point.x <= extent.x && point.y >= self.origin.y && point.y <= extent.y
+  }
+
+  /// Does the rectangle overlap another rectangle?
+  ///
+  /// Rectangles that only share an edge are not considered overlapping
+  pub fn overlaps(&self, other: &Rec2<f32, Size>) -> bool {
+    let extent = self.origin + Vec2::from(self.size);
+    let other_extent = other.origin + Vec2::from(other.size);
+    self.origin.x < other_extent.x
+      && extent.x > other.origin.x
+      && self.origin.y < other_extent.y
+      && extent.y > other.origin.y
+  }
 }
 
 impl Rec2<i32, Size> {
@@ -373,6 +391,126 @@ impl From<Rec2<i32, Size>> for Rec2<f32, Size> {
   }
 }
 
+// Ellipse 2D //
+
+/// An axis aligned ellipse in 2D space
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Ellipse {
+  pub center: Vec2<f32>,
+  pub radii: Vec2<f32>,
+}
+
+impl Ellipse {
+  /// Instantiate a new ellipse
+  pub const fn new(center: Vec2<f32>, radii: Vec2<f32>) -> Self { Self { center, radii } }
+  /// Instantiate the ellipse inscribed in a rectangle
+  pub fn from_bounds(bounds: &Rec2<f32, Size>) -> Self {
+    let radii = Vec2::<f32>::from(bounds.size) / 2.0;
+    Self::new(bounds.origin + radii, radii)
+  }
+  /// Does the ellipse contain a point? Points on the edge are contained
+  pub fn contains(&self, point: Vec2<f32>) -> bool {
+    if self.radii.x <= 0.0 || self.radii.y <= 0.0 { return false; }
+    let offset = (point - self.center) / self.radii;
+    offset.x * offset.x + offset.y * offset.y <= 1.0
+  }
+  /// Get the rectangle the ellipse is inscribed in
+  pub fn get_bounds(&self) -> Rec2<f32, Size> { get_bounds(&[self.center - self.radii, self.center + self.radii]) }
+  /// Move the ellipse by an offset
+  pub fn translate(&self, offset: Vec2<f32>) -> Self { Self::new(self.center + offset, self.radii) }
+}
+
+// Polygon 2D //
+
+/// A closed polygon in 2D space
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Polygon {
+  pub points: Vec<Vec2<f32>>,
+}
+
+impl Polygon {
+  /// Instantiate a new polygon from its points in order
+  pub fn new(points: Vec<Vec2<f32>>) -> Self { Self { points } }
+  /// Get the edges of the polygon, the last of which closes back to the first point
+  pub fn edges(&self) -> impl Iterator<Item=(Vec2<f32>, Vec2<f32>)> + '_ {
+    self.points.iter().copied().zip(self.points.iter().copied().cycle().skip(1))
+  }
+  /// Does the polygon contain a point?
+  ///
+  /// Uses the even-odd rule, so the polygon may be concave, and points inside a self intersection are outside
+  pub fn contains(&self, point: Vec2<f32>) -> bool {
+    let crossings = self
+      .edges()
+      .filter(|(start, end)| {
+        (start.y > point.y) != (end.y > point.y)
+          && point.x < start.x + (point.y - start.y) * (end.x - start.x) / (end.y - start.y)
+      })
+      .count();
+    crossings % 2 == 1
+  }
+  /// Get the smallest rectangle containing the polygon
+  pub fn get_bounds(&self) -> Rec2<f32, Size> { get_bounds(&self.points) }
+  /// Move the polygon by an offset
+  pub fn translate(&self, offset: Vec2<f32>) -> Self { Self::new(self.points.iter().map(|point| *point + offset).collect()) }
+}
+
+// Polyline 2D //
+
+/// An open path of line segments in 2D space
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Polyline {
+  pub points: Vec<Vec2<f32>>,
+}
+
+impl Polyline {
+  /// Instantiate a new polyline from its points in order
+  pub fn new(points: Vec<Vec2<f32>>) -> Self { Self { points } }
+  /// Get the segments of the path from start to end
+  pub fn segments(&self) -> impl Iterator<Item=(Vec2<f32>, Vec2<f32>)> + '_ {
+    self.points.windows(2).map(|segment| (segment[0], segment[1]))
+  }
+  /// Get the length of the path
+  pub fn get_length(&self) -> f32 { self.segments().map(|(start, end)| (end - start).get_magnitude()).sum() }
+  /// Get the point `distance` along the path, clamped to its ends
+  pub fn point_at(&self, distance: f32) -> Option<Vec2<f32>> {
+    let mut remaining = distance.max(0.0);
+    for (start, end) in self.segments() {
+      let length = (end - start).get_magnitude();
+      if remaining <= length && length > 0.0 { return Some(start + (end - start) * (remaining / length)); }
+      remaining -= length;
+    }
+    self.points.last().copied()
+  }
+  /// Get the point on the path closest to `point`
+  pub fn closest_point(&self, point: Vec2<f32>) -> Option<Vec2<f32>> {
+    let closest = self
+      .segments()
+      .map(|(start, end)| {
+        let direction = end - start;
+        let length = direction.x * direction.x + direction.y * direction.y;
+        let offset = point - start;
+        let along = if length > 0.0 { ((offset.x * direction.x + offset.y * direction.y) / length).clamp(0.0, 1.0) } else { 0.0 };
+        start + direction * along
+      })
+      .min_by(|a, b| (*a - point).get_magnitude().total_cmp(&(*b - point).get_magnitude()));
+    closest.or(self.points.first().copied())
+  }
+  /// Get the smallest rectangle containing the path
+  pub fn get_bounds(&self) -> Rec2<f32, Size> { get_bounds(&self.points) }
+  /// Move the path by an offset
+  pub fn translate(&self, offset: Vec2<f32>) -> Self { Self::new(self.points.iter().map(|point| *point + offset).collect()) }
+}
+
+/// Get the smallest rectangle containing every point, rounding its size up
+fn get_bounds(points: &[Vec2<f32>]) -> Rec2<f32, Size> {
+  let Some(first) = points.first() else { return Rec2::default(); };
+  let (min, max) = points.iter().fold((*first, *first), |(min, max), point| {
+    (Vec2::new(min.x.min(point.x), min.y.min(point.y)), Vec2::new(max.x.max(point.x), max.y.max(point.y)))
+  });
+  let size = max - min;
+  Rec2::new(min, Vec2::new(size.x.ceil() as Size, size.y.ceil() as Size))
+}
+
 // tests
 #[cfg(test)]
 mod tests {
@@ -561,4 +699,60 @@ mod tests {
     assert_eq!(rect.width(), 3);
     assert_eq!(rect.height(), 4);
   }
-}
\ No newline at end of file
+
+  #[test]
+  fn rec2_overlaps() {
+    let rec = Rec2::new(Vec2::new(0.0, 0.0), Vec2::new(16u32, 16u32));
+    assert!(rec.overlaps(&Rec2::new(Vec2::new(8.0, 8.0), Vec2::new(16, 16))), "rectangles intersect");
+    assert!(rec.overlaps(&Rec2::new(Vec2::new(4.0, 4.0), Vec2::new(2, 2))), "rectangle is contained");
+    assert!(!rec.overlaps(&Rec2::new(Vec2::new(16.0, 0.0), Vec2::new(16, 16))), "rectangles share an edge");
+    assert!(!rec.overlaps(&Rec2::new(Vec2::new(-32.0, 40.0), Vec2::new(8, 8))), "rectangles are apart");
+  }
+
+  // Shapes //
+
+  #[test]
+  fn rec2_contains_point() {
+    let rec = Rec2::new(Vec2::new(0.0, 0.0), Vec2::new(16u32, 8u32));
+    assert!(rec.contains_point(Vec2::new(16.0, 8.0)), "point is on the edge");
+    assert!(!rec.contains_point(Vec2::new(8.0, 9.0)));
+  }
+
+  #[test]
+  fn ellipse_contains() {
+    let ellipse = Ellipse::from_bounds(&Rec2::new(Vec2::new(0.0, 0.0), Vec2::new(32u32, 16u32)));
+    assert_eq!(ellipse, Ellipse::new(Vec2::new(16.0, 8.0), Vec2::new(16.0, 8.0)));
+    assert!(ellipse.contains(Vec2::new(30.0, 8.0)));
+    assert!(!ellipse.contains(Vec2::new(2.0, 2.0)), "point is in the corner of the bounds");
+    assert!(!Ellipse::new(Vec2::new(0.0, 0.0), Vec2::new(0.0, 4.0)).contains(Vec2::new(0.0, 0.0)), "ellipse is flat");
+    assert_eq!(ellipse.translate(Vec2::new(-16.0, 0.0)).get_bounds().origin, Vec2::new(-16.0, 0.0));
+  }
+
+  #[test]
+  fn polygon_contains() {
+    // an L shape with its notch in the top right
+    let polygon = Polygon::new(vec![
+      Vec2::new(0.0, 0.0), Vec2::new(8.0, 0.0), Vec2::new(8.0, 8.0),
+      Vec2::new(16.0, 8.0), Vec2::new(16.0, 16.0), Vec2::new(0.0, 16.0),
+    ]);
+    assert!(polygon.contains(Vec2::new(4.0, 4.0)));
+    assert!(polygon.contains(Vec2::new(12.0, 12.0)));
+    assert!(!polygon.contains(Vec2::new(12.0, 4.0)), "point is in the notch");
+    assert!(!polygon.contains(Vec2::new(-1.0, 4.0)));
+    assert_eq!(polygon.edges().count(), 6, "last edge closes the polygon");
+    let bounds = polygon.get_bounds();
+    assert_eq!((bounds.origin, bounds.size), (Vec2::new(0.0, 0.0), Vec2::new(16, 16)));
+  }
+
+  #[test]
+  fn polyline_walk() {
+    let path = Polyline::new(vec![Vec2::new(0.0, 0.0), Vec2::new(10.0, 0.0), Vec2::new(10.0, 20.0)]);
+    assert_eq!(path.get_length(), 30.0);
+    assert_eq!(path.point_at(5.0), Some(Vec2::new(5.0, 0.0)));
+    assert_eq!(path.point_at(15.0), Some(Vec2::new(10.0, 5.0)));
+    assert_eq!(path.point_at(100.0), Some(Vec2::new(10.0, 20.0)), "distance is clamped to the end");
+    assert_eq!(path.closest_point(Vec2::new(14.0, 8.0)), Some(Vec2::new(10.0, 8.0)));
+    assert_eq!(path.closest_point(Vec2::new(-4.0, -4.0)), Some(Vec2::new(0.0, 0.0)));
+    assert_eq!(Polyline::default().point_at(1.0), None);
+  }
+}
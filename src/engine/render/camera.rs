@@ -109,3 +109,22 @@ impl Systemize for CameraTether {
 /// Entities with this component will be positioned relative to the camera
 pub type Sticky1 = layer::Layer9;
 pub type Sticky2 = layer::Layer8;
+
+/// Scroll an entity by a fraction of the camera movement
+///
+/// The entity is drawn at its position while the camera viewport is at `origin`, a factor of 1 scrolls with the
+/// world and 0 stays fixed on screen. Entities with parallax are drawn away from their position, so are never culled.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Parallax {
+  pub factor: Vec2<f32>,
+  pub origin: Vec2<f32>,
+}
+
+impl Parallax {
+  /// Instantiate a new parallax of `factor` aligned with the world while the camera is at `origin`
+  pub fn new(factor: Vec2<f32>, origin: Vec2<f32>) -> Self { Self { factor, origin } }
+  /// Get the worldspace position an entity at `position` is drawn at while the camera is at `camera`
+  pub fn apply(&self, position: Vec2<f32>, camera: Vec2<f32>) -> Vec2<f32> {
+    position + (camera - self.origin) * (Vec2::new(1.0, 1.0) - self.factor)
+  }
+}
@@ -0,0 +1,93 @@
+/**
+ * Capture rendered frames to image files
+ */
+
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Directory captures are written to
+pub const CAPTURE_DIRECTORY: &str = "screenshots";
+
+/// Tracks requested screenshots and frame sequences
+pub struct FrameCapture {
+  directory: PathBuf,
+  screenshot: bool,
+  interval: Option<u32>,
+  sequence: Option<PathBuf>,
+  frame: u64,
+}
+
+impl Default for FrameCapture {
+  /// Instantiate a frame capture writing to `CAPTURE_DIRECTORY`
+  fn default() -> Self { Self::new(CAPTURE_DIRECTORY) }
+}
+
+impl FrameCapture {
+  /// Instantiate a new frame capture writing to `directory`
+  pub fn new(directory: impl AsRef<Path>) -> Self {
+    Self {
+      directory: directory.as_ref().to_path_buf(),
+      screenshot: false,
+      interval: None,
+      sequence: None,
+      frame: 0,
+    }
+  }
+
+  /// Capture the next presented frame
+  pub fn queue_screenshot(&mut self) { self.screenshot = true; }
+  /// Capture every `interval`th presented frame to numbered files, or stop capturing with `None`
+  pub fn set_interval(&mut self, interval: Option<u32>) {
+    self.interval = interval.filter(|interval| *interval > 0);
+    self.sequence = self.interval.map(|_| self.directory.join(format!("capture_{}", timestamp())));
+    self.frame = 0;
+  }
+  /// Check if frames are being captured every `interval` frames
+  pub fn is_sequencing(&self) -> bool { self.interval.is_some() }
+
+  /// Advance a frame and get the paths it should be written to
+  pub fn next(&mut self) -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    if self.screenshot {
+      self.screenshot = false;
+      paths.push(self.directory.join(format!("screenshot_{}.png", timestamp())));
+    }
+    if let (Some(interval), Some(sequence)) = (self.interval, &self.sequence) {
+      if self.frame % interval as u64 == 0 {
+        paths.push(sequence.join(format!("frame_{:06}.png", self.frame / interval as u64)));
+      }
+    }
+    self.frame += 1;
+    paths
+  }
+}
+
+/// Milliseconds since the unix epoch, used to name captures
+fn timestamp() -> u128 {
+  SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map(|duration| duration.as_millis())
+    .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_capture_interval() {
+    let mut capture = FrameCapture::new("captures");
+    assert!(capture.next().is_empty(), "nothing is captured by default");
+
+    capture.set_interval(Some(3));
+    let captured = (0..7).map(|_| capture.next().len()).collect::<Vec<_>>();
+    assert_eq!(captured, vec![1, 0, 0, 1, 0, 0, 1], "every third frame is captured");
+
+    capture.set_interval(None);
+    capture.queue_screenshot();
+    let paths = capture.next();
+    assert_eq!(paths.len(), 1, "a queued screenshot is captured once");
+    assert!(paths[0].starts_with("captures"), "screenshot is written to the capture directory");
+    assert!(capture.next().is_empty(), "screenshot is not repeated");
+  }
+}
@@ -0,0 +1,179 @@
+/**
+ * Immediate-mode debug drawing queued by systems and flushed on top of the frame
+ */
+
+use std::collections::HashSet;
+
+use crate::engine::asset::asset::AssetManager;
+use crate::engine::geometry::shape::{Rec2, Vec2};
+use crate::engine::render::camera::Camera;
+use crate::engine::render::renderer::Renderer;
+use crate::engine::utility::alias::Size;
+use crate::engine::utility::color::RGBA;
+
+/// Length of the lines drawn at the head of an arrow
+pub const ARROW_HEAD_LENGTH: f32 = 4.0;
+/// Angle between the shaft of an arrow and the lines of its head in radians
+pub const ARROW_HEAD_ANGLE: f32 = std::f32::consts::FRAC_PI_6;
+
+/// A group of debug drawing that can be shown or hidden as one
+pub type DebugCategory = &'static str;
+
+/// A shape in worldspace
+#[derive(Clone, Debug)]
+enum DebugShape {
+  Line { from: Vec2<f32>, to: Vec2<f32> },
+  Rect { rect: Rec2<f32, Size>, filled: bool },
+  Circle { center: Vec2<f32>, radius: f32, filled: bool },
+  Text { position: Vec2<f32>, text: String },
+}
+
+/// A queued shape of a color
+#[derive(Clone, Debug)]
+struct DebugCommand {
+  shape: DebugShape,
+  color: RGBA,
+}
+
+/// Queue of shapes drawn above every layer at the end of the frame
+///
+/// Drawing is skipped while the queue is disabled or the category of a shape is hidden, so systems may queue shapes
+/// unconditionally.
+#[derive(Default)]
+pub struct DebugDraw {
+  enabled: bool,
+  hidden: HashSet<DebugCategory>,
+  commands: Vec<DebugCommand>,
+}
+
+impl DebugDraw {
+  /// Show or hide every category
+  pub fn set_enabled(&mut self, enabled: bool) { self.enabled = enabled; }
+  /// Check if debug drawing is shown
+  pub fn is_enabled(&self) -> bool { self.enabled }
+  /// Show or hide a category while debug drawing is enabled
+  pub fn set_visible(&mut self, category: DebugCategory, visible: bool) {
+    if visible { self.hidden.remove(category); } else { self.hidden.insert(category); }
+  }
+  /// Toggle the visibility of a category
+  pub fn toggle_visible(&mut self, category: DebugCategory) {
+    let visible = self.hidden.contains(category);
+    self.set_visible(category, visible);
+  }
+  /// Check if shapes of a category are drawn
+  pub fn is_visible(&self, category: DebugCategory) -> bool {
+    self.enabled && !self.hidden.contains(category)
+  }
+
+  /// Queue a shape if its category is drawn
+  fn queue(&mut self, category: DebugCategory, shape: DebugShape, color: RGBA) {
+    if !self.is_visible(category) { return; }
+    self.commands.push(DebugCommand { shape, color });
+  }
+
+  // Shapes //
+
+  /// Queue a line between two points
+  pub fn line(&mut self, category: DebugCategory, from: Vec2<f32>, to: Vec2<f32>, color: RGBA) {
+    self.queue(category, DebugShape::Line { from, to }, color);
+  }
+  /// Queue a line from `from` to `to` with a head at `to`
+  pub fn arrow(&mut self, category: DebugCategory, from: Vec2<f32>, to: Vec2<f32>, color: RGBA) {
+    if !self.is_visible(category) { return; }
+    self.line(category, from, to, color);
+    let angle = (from.y - to.y).atan2(from.x - to.x);
+    for side in [-ARROW_HEAD_ANGLE, ARROW_HEAD_ANGLE] {
+      let head = Vec2::new((angle + side).cos(), (angle + side).sin()) * ARROW_HEAD_LENGTH;
+      self.line(category, to, to + head, color);
+    }
+  }
+  /// Queue the outline of a rect
+  pub fn rect(&mut self, category: DebugCategory, rect: Rec2<f32, Size>, color: RGBA) {
+    self.queue(category, DebugShape::Rect { rect, filled: false }, color);
+  }
+  /// Queue a filled rect
+  pub fn fill_rect(&mut self, category: DebugCategory, rect: Rec2<f32, Size>, color: RGBA) {
+    self.queue(category, DebugShape::Rect { rect, filled: true }, color);
+  }
+  /// Queue the outline of a circle
+  pub fn circle(&mut self, category: DebugCategory, center: Vec2<f32>, radius: f32, color: RGBA) {
+    self.queue(category, DebugShape::Circle { center, radius, filled: false }, color);
+  }
+  /// Queue a filled circle
+  pub fn fill_circle(&mut self, category: DebugCategory, center: Vec2<f32>, radius: f32, color: RGBA) {
+    self.queue(category, DebugShape::Circle { center, radius, filled: true }, color);
+  }
+  /// Queue text in the default typeface with its top left at `position`
+  pub fn text(&mut self, category: DebugCategory, position: Vec2<f32>, text: impl Into<String>, color: RGBA) {
+    if !self.is_visible(category) { return; }
+    self.queue(category, DebugShape::Text { position, text: text.into() }, color);
+  }
+
+  /// Get the number of queued shapes
+  pub fn len(&self) -> usize { self.commands.len() }
+  /// Check if no shapes are queued
+  pub fn is_empty(&self) -> bool { self.commands.is_empty() }
+
+  /// Draw and clear the queued shapes translated by the camera
+  pub fn flush(&mut self, render: &mut Renderer, camera: &Camera, asset: &mut AssetManager) -> Result<(), String> {
+    for DebugCommand { shape, color } in self.commands.drain(..) {
+      match shape {
+        DebugShape::Line { from, to } => render.draw_line(camera.translate(from), camera.translate(to), color),
+        DebugShape::Rect { rect, filled } => {
+          let rect = Rec2::new(camera.translate(rect.origin), rect.size);
+          if filled { render.fill_rect(rect, color); } else { render.draw_rect(rect, color); }
+        }
+        DebugShape::Circle { center, radius, filled } => {
+          render.draw_circle(camera.translate(center), radius.round() as Size, color, filled)?;
+        }
+        DebugShape::Text { position, text } => {
+          let font = asset.typeface.get(None)?;
+          let surface = font.render(&text).blended(color).map_err(|e| e.to_string())?;
+          let texture = asset.texture.build_from_surface(surface)?;
+          render.draw_texture(&texture, camera.translate(position));
+          unsafe { texture.internal.destroy(); } // texture is drawn once, so is not kept in the store
+        }
+      }
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const CATEGORY: DebugCategory = "collider";
+
+  #[test]
+  fn test_disabled() {
+    let mut debug = DebugDraw::default();
+    debug.line(CATEGORY, Vec2::default(), Vec2::new(8.0, 8.0), RGBA::default());
+    assert!(debug.is_empty(), "shapes are not queued while disabled");
+
+    debug.set_enabled(true);
+    debug.line(CATEGORY, Vec2::default(), Vec2::new(8.0, 8.0), RGBA::default());
+    assert_eq!(debug.len(), 1, "shapes are queued while enabled");
+  }
+
+  #[test]
+  fn test_hidden_category() {
+    let mut debug = DebugDraw::default();
+    debug.set_enabled(true);
+    debug.toggle_visible(CATEGORY);
+    debug.circle(CATEGORY, Vec2::default(), 4.0, RGBA::default());
+    debug.circle("creature", Vec2::default(), 4.0, RGBA::default());
+    assert_eq!(debug.len(), 1, "only the visible category is queued");
+
+    debug.toggle_visible(CATEGORY);
+    assert!(debug.is_visible(CATEGORY), "category is shown again");
+  }
+
+  #[test]
+  fn test_arrow() {
+    let mut debug = DebugDraw::default();
+    debug.set_enabled(true);
+    debug.arrow(CATEGORY, Vec2::default(), Vec2::new(16.0, 0.0), RGBA::default());
+    assert_eq!(debug.len(), 3, "an arrow is a shaft and two lines of its head");
+  }
+}
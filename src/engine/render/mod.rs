@@ -2,3 +2,6 @@ pub mod system;
 pub mod renderer;
 pub mod camera;
 
+pub mod capture;
+pub mod debug;
+pub mod presentation;
@@ -0,0 +1,72 @@
+/**
+ * How the logical canvas is scaled onto the window
+ */
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// Scaling of the logical canvas onto the window
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Presentation {
+  /// Scale by the largest whole multiple that fits the window, letterboxing the remainder
+  #[default]
+  Integer,
+  /// Scale to fit the window preserving the aspect ratio, letterboxing the remainder
+  Stretch,
+  /// Size the window to a whole multiple of the logical size
+  Multiplier(u32),
+}
+
+impl Display for Presentation {
+  /// Display a presentation as it is parsed, eg: `integer`, `stretch`, `2x`
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Presentation::Integer => write!(f, "integer"),
+      Presentation::Stretch => write!(f, "stretch"),
+      Presentation::Multiplier(multiplier) => write!(f, "{}x", multiplier),
+    }
+  }
+}
+
+impl FromStr for Presentation {
+  type Err = String;
+  /// Parse a presentation from `integer`, `stretch`, or a multiplier such as `2` or `2x`
+  fn from_str(value: &str) -> Result<Self, Self::Err> {
+    match value.trim().to_lowercase().as_str() {
+      "integer" => Ok(Presentation::Integer),
+      "stretch" => Ok(Presentation::Stretch),
+      other => other
+        .trim_end_matches('x')
+        .parse::<u32>()
+        .ok()
+        .filter(|multiplier| *multiplier > 0)
+        .map(Presentation::Multiplier)
+        .ok_or(format!("'{}' is not a presentation, expected 'integer', 'stretch', or a multiplier such as '2x'", value)),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_parse() {
+    assert_eq!("integer".parse::<Presentation>(), Ok(Presentation::Integer));
+    assert_eq!("Stretch".parse::<Presentation>(), Ok(Presentation::Stretch));
+    assert_eq!("3".parse::<Presentation>(), Ok(Presentation::Multiplier(3)));
+    assert_eq!("3x".parse::<Presentation>(), Ok(Presentation::Multiplier(3)));
+    assert!("0x".parse::<Presentation>().is_err(), "window cannot be zero sized");
+    assert!("fill".parse::<Presentation>().is_err(), "unknown presentation");
+  }
+
+  #[test]
+  fn test_display_round_trip() {
+    for presentation in [Presentation::Integer, Presentation::Stretch, Presentation::Multiplier(2)] {
+      assert_eq!(presentation.to_string().parse::<Presentation>(), Ok(presentation));
+    }
+  }
+}
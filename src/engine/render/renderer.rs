@@ -2,12 +2,23 @@
  * Rendering subsystem
  */
 
+use std::path::Path;
+
+use sdl2::gfx::primitives::DrawRenderer;
+use sdl2::image::SaveSurface;
+use sdl2::pixels::{Color, PixelFormatEnum};
 use sdl2::rect::{Point, Rect};
 use sdl2::render::TextureCreator;
-use sdl2::video::{FullscreenType, WindowContext};
+use sdl2::surface::Surface;
+use sdl2::video::{FullscreenType, WindowContext, WindowPos};
 
-use crate::engine::asset::texture::{SrcRect, Texture};
+use crate::engine::asset::bitmap::PlacedGlyph;
+use crate::engine::asset::texture::{SrcRect, Texture, TextureStore};
+use crate::engine::component::sprite::Flip;
 use crate::engine::geometry::shape::{IntConvertable, Rec2, SizePrimitive, Vec2};
+use crate::engine::render::capture::FrameCapture;
+use crate::engine::render::presentation::Presentation;
+use crate::engine::utility::alias::Size;
 use crate::engine::utility::color::RGBA;
 
 /// Z layer for rendering
@@ -50,6 +61,7 @@ pub struct Properties {
   pub dimensions: Vec2<u32>,
   pub logical: Option<Vec2<u32>>,
   pub fullscreen: bool,
+  pub presentation: Presentation,
   pub show_cursor: bool,
   pub vsync: bool,
   pub opengl: bool,
@@ -62,6 +74,7 @@ pub struct Properties {
 pub struct Renderer {
   subsystem: sdl2::render::WindowCanvas,
   properties: Properties,
+  pub capture: FrameCapture,
 }
 
 impl Renderer {
@@ -80,15 +93,40 @@ impl Renderer {
     let mut subsystem = builder.build().map_err(|e| e.to_string())?;
 
     // apply post-construction properties
-    subsystem.set_integer_scale(true).map_err(|e| e.to_string())?;
     subsystem.set_draw_color(properties.screen_color);
     subsystem.set_blend_mode(sdl2::render::BlendMode::Blend);
     if let Some(size) = properties.logical {
       subsystem.set_logical_size(size.x, size.y).map_err(|e| e.to_string())?;
     }
 
-    Ok(Self { subsystem, properties })
+    let presentation = properties.presentation;
+    let mut renderer = Self { subsystem, properties, capture: FrameCapture::default() };
+    renderer.set_presentation(presentation)?;
+    Ok(renderer)
+  }
+
+  /// Scale the logical canvas onto the window by `presentation`
+  ///
+  /// Multipliers resize the window, which only takes effect once the window leaves fullscreen
+  pub fn set_presentation(&mut self, presentation: Presentation) -> Result<(), String> {
+    let integer = presentation != Presentation::Stretch;
+    self.subsystem.set_integer_scale(integer).map_err(|e| e.to_string())?;
+
+    let window = self.subsystem.window_mut();
+    if let Presentation::Multiplier(multiplier) = presentation {
+      let logical = self.properties.logical.unwrap_or(self.properties.dimensions);
+      window.set_resizable(false);
+      window.set_size(logical.x * multiplier.max(1), logical.y * multiplier.max(1)).map_err(|e| e.to_string())?;
+      window.set_position(WindowPos::Centered, WindowPos::Centered);
+    } else {
+      window.set_resizable(true);
+    }
+
+    self.properties.presentation = presentation;
+    Ok(())
   }
+  /// Get how the logical canvas is scaled onto the window
+  pub fn get_presentation(&self) -> Presentation { self.properties.presentation }
 
   /// Instantiate a new `TextureCreator` from the `Renderer`
   pub fn new_texture_creator(&self) -> TextureCreator<WindowContext> { self.subsystem.texture_creator() }
@@ -106,7 +144,9 @@ impl Renderer {
       self.subsystem
         .window_mut()
         .set_fullscreen(FullscreenType::Off)
-        .expect("Failed to set windowed")
+        .expect("Failed to set windowed");
+      // restore the window size of a multiplier
+      self.set_presentation(self.properties.presentation).expect("Failed to restore presentation");
     }
   }
   /// Check if the window is in fullscreen mode
@@ -120,10 +160,31 @@ impl Renderer {
     self.subsystem.clear();
   }
   /// Present what has been rendered to the screen
+  ///
+  /// Captures requested from `capture` are written before presenting
   pub fn present(&mut self) {
+    for path in self.capture.next() {
+      if let Err(error) = self.save_canvas(&path) {
+        eprintln!("Failed to capture frame to {}: {}", path.display(), error);
+      }
+    }
     self.subsystem.present();
     self.clear();
   }
+  /// Read back what has been rendered this frame and write it to a PNG file at `path`
+  pub fn save_canvas(&self, path: impl AsRef<Path>) -> Result<(), String> {
+    let path = path.as_ref();
+    if let Some(directory) = path.parent() {
+      std::fs::create_dir_all(directory).map_err(|e| e.to_string())?;
+    }
+
+    let format = PixelFormatEnum::ABGR8888;
+    let (width, height) = self.subsystem.output_size()?;
+    let mut pixels = self.subsystem.read_pixels(None, format)?;
+    let pitch = width * format.byte_size_per_pixel() as u32;
+    let surface = Surface::from_data(&mut pixels, width, height, pitch, format)?;
+    surface.save(path)
+  }
 
   /// Draw `texture` to the screen at `position`
   pub fn draw_texture<T: IntConvertable>(&mut self, texture: &Texture, position: Vec2<T>) {
@@ -136,7 +197,7 @@ impl Renderer {
       .map_err(|error| eprintln!("{error}"))
       .ok();
   }
-  /// Draw `from` `texture` to the screen at `position`
+  /// Draw `from` `texture` to the screen at `position`, mirrored by `flip` then rotated about `centroid`
   pub fn draw_from_texture<T: IntConvertable>(
     &mut self,
     texture: &Texture,
@@ -144,6 +205,7 @@ impl Renderer {
     from: SrcRect,
     rotation: f64,
     centroid: Option<Vec2<T>>,
+    flip: Flip,
   ) {
     let (x, y) = position.destructure();
     let ((sx, sy), (w, h)) = from.destructure();
@@ -151,10 +213,50 @@ impl Renderer {
     let src = Rect::new(sx as i32, sy as i32, w, h);
     let centroid = centroid.map(|c| Point::new(c.x.into(), c.y.into()));
     self.subsystem
-      .copy_ex(&texture.internal, src, dest, rotation, centroid, false, false)
+      .copy_ex(&texture.internal, src, dest, rotation, centroid, flip.horizontal, flip.vertical)
       .map_err(|error| eprintln!("{error}"))
       .ok();
   }
+  /// Draw `glyphs` of a bitmap font tinted their color, relative to `position`
+  pub fn draw_glyphs(&mut self, textures: &mut TextureStore, glyphs: &[PlacedGlyph], position: Vec2<i32>) -> Result<(), String> {
+    for glyph in glyphs {
+      let texture = textures.get_mut(glyph.texture)?;
+      let (red, green, blue, alpha) = glyph.color.destructure();
+      texture.internal.set_color_mod(red, green, blue);
+      texture.internal.set_alpha_mod(alpha);
+      self.draw_from_texture(texture, position + glyph.position, glyph.src, 0.0, None, Flip::default());
+    }
+    // pages are shared by every text in the font, so restore them untinted
+    for glyph in glyphs {
+      let texture = textures.get_mut(glyph.texture)?;
+      texture.internal.set_color_mod(u8::MAX, u8::MAX, u8::MAX);
+      texture.internal.set_alpha_mod(u8::MAX);
+    }
+    Ok(())
+  }
+  /// Draw regions of textures onto a `target` texture, clearing it first
+  ///
+  /// Each region is mirrored by its flip then rotated about its center. `target` must have been built as a render
+  /// target, see `TextureLoader::build_target`
+  pub fn draw_to_texture<'t>(&mut self, target: &mut Texture, copies: impl Iterator<Item=(&'t Texture, SrcRect, Vec2<i32>, f64, Flip)>) -> Result<(), String> {
+    let mut result = Ok(());
+    self.subsystem
+      .with_texture_canvas(&mut target.internal, |canvas| {
+        canvas.set_draw_color(RGBA::default());
+        canvas.clear();
+        for (texture, from, position, rotation, flip) in copies {
+          let ((sx, sy), (w, h)) = from.destructure();
+          let src = Rect::new(sx as i32, sy as i32, w, h);
+          let dest = Rect::new(position.x, position.y, w, h);
+          if let Err(error) = canvas.copy_ex(&texture.internal, src, dest, rotation, None, flip.horizontal, flip.vertical) {
+            result = Err(error);
+            return;
+          }
+        }
+      })
+      .map_err(|e| e.to_string())?;
+    result
+  }
   /// Draw `rect` of `color` to the screen
   pub fn draw_rect<T: IntConvertable, U: SizePrimitive>(
     &mut self,
@@ -187,6 +289,16 @@ impl Renderer {
       .map_err(|error| eprintln!("{error}"))
       .ok();
   }
+  /// Draw a circle of `radius` around `center` of `color`, outlined or `filled`
+  pub fn draw_circle(&mut self, center: Vec2<i32>, radius: Size, color: RGBA, filled: bool) -> Result<(), String> {
+    let (x, y, radius) = (center.x as i16, center.y as i16, radius.min(i16::MAX as Size) as i16);
+    let color = Color::from(color);
+    if filled {
+      self.subsystem.filled_circle(x, y, radius, color)
+    } else {
+      self.subsystem.circle(x, y, radius, color)
+    }
+  }
 }
 
 /// Create a new `sdl2::video::Window` with the given `RendererProperties`
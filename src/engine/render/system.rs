@@ -2,16 +2,22 @@
  * Rendering system
  */
 
-use hecs::{Component, Or};
+use hecs::{Component, Or, Without};
 use crate::engine::asset::asset::AssetManager;
 use crate::engine::component::position::Position;
 use crate::engine::component::sprite::Sprite;
 use crate::engine::component::text::Text;
+use crate::engine::ecs::spatial::SpatialBounds;
 use crate::engine::ecs::system::{SysArgs, Systemize};
 use crate::engine::geometry::shape::Vec2;
-use crate::engine::render::camera::{Sticky1, Sticky2};
+use crate::engine::render::camera::{Parallax, Sticky1, Sticky2};
 use crate::engine::render::renderer::{layer, Renderer};
 
+/// Distance beyond the viewport that entities are still rendered
+///
+/// Accounts for rotated sprites whose bounds exceed their source rect
+pub const CULL_MARGIN: u32 = 16;
+
 /// components marking entities as renderable
 type Renderable<'a> = Or<&'a Sprite, &'a mut Text>;
 
@@ -36,10 +42,22 @@ impl Systemize for Renderer {
   }
 }
 
-/// render entities of layer T using the world origin
-pub fn render_layer<T>(SysArgs { world, camera, render, asset, .. }: &mut SysArgs) where T: Component {
-  for (_, (renderable, position, ..)) in world.query::<QueryRenderableOf<T>>() {
-    let position = camera.translate(Vec2::from(position.0));
+/// render entities of layer T within the camera viewport using the world origin
+pub fn render_layer<T>(SysArgs { world, camera, render, asset, spatial, .. }: &mut SysArgs) where T: Component {
+  let viewport = camera.get_viewport();
+  let visible = SpatialBounds::new(
+    Vec2::<f32>::from(viewport.origin) - CULL_MARGIN as f32,
+    viewport.size + CULL_MARGIN * 2,
+  );
+  for entity in spatial.query(&visible) {
+    if let Ok((renderable, position, ..)) = world.query_entity::<Without<QueryRenderableOf<T>, &Parallax>>(entity) {
+      let position = camera.translate(Vec2::from(position.0));
+      render_renderable(render, asset, renderable, position);
+    }
+  }
+  let origin = Vec2::<f32>::from(camera.get_position());
+  for (_, ((renderable, position, ..), parallax)) in world.query::<(QueryRenderableOf<T>, &Parallax)>() {
+    let position = camera.translate(parallax.apply(position.0, origin));
     render_renderable(render, asset, renderable, position);
   }
 }
@@ -57,11 +75,16 @@ pub fn render_renderable(render: &mut Renderer, asset: &mut AssetManager, mut re
   let texture_key = match renderable {
     Or::Left(sprite) => sprite.texture,
     Or::Right(ref mut text) => {
-      let typeface = asset.typeface
-        .use_store()
-        .get("typeface")
-        .expect(format!("Failed to retrieve typeface at '{}'", "typeface").as_str());
-      if let Some(texture_key) = text.get_content(&typeface, &mut asset.texture) {
+      // bitmap fonts are drawn glyph by glyph from their pages rather than from a texture of the content
+      if let Some(font) = asset.typeface.get_bitmap(text.get_font()) {
+        let glyphs = text.get_glyphs(font);
+        render
+          .draw_glyphs(asset.texture.use_store(), glyphs, position)
+          .map_err(|error| eprintln!("{error}"))
+          .ok();
+        return;
+      }
+      if let Some(texture_key) = text.get_content(&asset.typeface, &mut asset.texture) {
         texture_key
       } else {
         return; // no text content to render; skip this component
@@ -72,12 +95,18 @@ pub fn render_renderable(render: &mut Renderer, asset: &mut AssetManager, mut re
 
   let texture = asset.texture
     .use_store()
-    .get(texture_key)
+    .get_mut(texture_key)
     .expect(format!("Failed to retrieve texture at {}", texture_key).as_str());
 
   match renderable {
+    Or::Left(sprite) if sprite.alpha < u8::MAX => {
+      // textures are shared between sprites, so restore them opaque
+      texture.internal.set_alpha_mod(sprite.alpha);
+      render.draw_from_texture::<i32>(texture, position, sprite.src, sprite.rotation, sprite.centroid, sprite.flip);
+      texture.internal.set_alpha_mod(u8::MAX);
+    }
     Or::Left(sprite) => {
-      render.draw_from_texture::<i32>(texture, position, sprite.src, sprite.rotation, sprite.centroid);
+      render.draw_from_texture::<i32>(texture, position, sprite.src, sprite.rotation, sprite.centroid, sprite.flip);
     }
     Or::Right(..) => {
       render.draw_texture::<i32>(texture, position);
@@ -1,3 +1,5 @@
+use serde::Deserialize;
+
 use crate::engine::geometry::shape::Vec2;
 use crate::engine::utility::alias::Coordinate;
 
@@ -31,7 +33,8 @@ impl AxisDirection {
 // Compass Direction Type //
 
 /// A snap type for directions
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
 pub enum CompassDirectionType {
   Ordinal,
   Cardinal,
@@ -83,7 +86,8 @@ pub const QUARTER_DIRECTION_ROTATION: usize = DIRECTIONS / 4;
 pub const EIGHTH_DIRECTION_ROTATION: usize = DIRECTIONS / 8;
 
 /// A cardinal or ordinal direction
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
 pub enum Direction {
   #[default]
   Up,
@@ -0,0 +1,349 @@
+/**
+ * Lay out styled text into lines that fit a box
+ */
+
+use crate::engine::utility::alias::{Size, Size2};
+use crate::engine::utility::color::{OPAQUE, RGBA};
+use crate::engine::utility::text::NEW_LINE;
+
+const TAG_OPEN: char = '[';
+const TAG_CLOSE: char = ']';
+const TAG_END: char = '/';
+
+/// Horizontal alignment of lines within a box
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum Justify {
+  #[default]
+  Left,
+  Center,
+  Right,
+}
+
+/// Treatment of whitespace between words
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum Whitespace {
+  /// runs of whitespace become a single space
+  #[default]
+  Collapse,
+  /// runs of whitespace keep their width, except where a line wraps
+  Preserve,
+}
+
+/// Style applied to a run of text
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct SpanStyle {
+  /// overrides the color of the text
+  pub color: Option<RGBA>,
+  /// emphasised text is drawn emboldened
+  pub emphasis: bool,
+}
+
+/// A run of text in a single style
+#[derive(Clone, Debug, PartialEq)]
+pub struct Span {
+  pub text: String,
+  pub style: SpanStyle,
+}
+
+/// Parse a hex color of the form `#rrggbb` or `#rrggbbaa`
+fn parse_hex_color(hex: &str) -> Result<RGBA, String> {
+  let digits = hex.strip_prefix('#').ok_or(format!("Color '{}' must start with '#'", hex))?;
+  if !digits.is_ascii() || (digits.len() != 6 && digits.len() != 8) {
+    return Err(format!("Color '{}' must have 6 or 8 hex digits", hex));
+  }
+  let channel = |index: usize| {
+    u8::from_str_radix(&digits[index * 2..index * 2 + 2], 16).map_err(|_| format!("Color '{}' is not valid hex", hex))
+  };
+  let alpha = if digits.len() == 8 { channel(3)? } else { OPAQUE };
+  Ok(RGBA::new(channel(0)?, channel(1)?, channel(2)?, alpha))
+}
+
+/// Check if a tag name is a markup tag
+fn is_tag(name: &str) -> bool { matches!(name, "color" | "em") }
+
+/// Parse text with inline markup into styled spans
+///
+/// - `[color=#rrggbb]...[/color]` colors the enclosed text, alpha may be given as `#rrggbbaa`
+/// - `[em]...[/em]` emphasises the enclosed text
+/// - `[[` is a literal `[`
+///
+/// Tags may be nested, and must be closed in the reverse order they were opened. Unknown and unterminated tags are
+/// kept as literal text, so bracketed prose does not need escaping.
+pub fn parse_markup(markup: &str) -> Result<Vec<Span>, String> {
+  let mut spans = Vec::<Span>::new();
+  let mut stack = Vec::<(String, SpanStyle)>::new();
+  let mut style = SpanStyle::default();
+  let mut text = String::new();
+  let mut chars = markup.chars().peekable();
+
+  let flush = |text: &mut String, style: SpanStyle, spans: &mut Vec<Span>| {
+    if text.is_empty() { return; }
+    spans.push(Span { text: std::mem::take(text), style });
+  };
+
+  while let Some(char) = chars.next() {
+    if char != TAG_OPEN {
+      text.push(char);
+      continue;
+    }
+    if chars.peek() == Some(&TAG_OPEN) {
+      chars.next();
+      text.push(TAG_OPEN);
+      continue;
+    }
+
+    let mut tag = String::new();
+    let mut terminated = false;
+    for char in chars.by_ref() {
+      if char == TAG_CLOSE { terminated = true; break; }
+      tag.push(char);
+    }
+    let name = tag.strip_prefix(TAG_END).unwrap_or(&tag);
+    let name = name.split_once('=').map_or(name, |(name, ..)| name);
+    if !terminated || !is_tag(name) {
+      text.push(TAG_OPEN);
+      text.push_str(&tag);
+      if terminated { text.push(TAG_CLOSE); }
+      continue;
+    }
+    flush(&mut text, style, &mut spans);
+
+    if let Some(name) = tag.strip_prefix(TAG_END) {
+      let (open, previous) = stack.pop().ok_or(format!("Closing tag '[/{}]' was never opened", name))?;
+      if open != name { return Err(format!("Closing tag '[/{}]' does not match '[{}]'", name, open)); }
+      style = previous;
+      continue;
+    }
+
+    let (name, value) = tag.split_once('=').unwrap_or((&tag, ""));
+    stack.push((String::from(name), style));
+    match name {
+      "color" => style.color = Some(parse_hex_color(value)?),
+      "em" => style.emphasis = true,
+      _ => return Err(format!("Unknown tag '[{}]'", tag)),
+    }
+  }
+
+  if let Some((open, ..)) = stack.last() { return Err(format!("Tag '[{}]' was never closed", open)); }
+  flush(&mut text, style, &mut spans);
+  Ok(spans)
+}
+
+/// Count the characters of spans
+pub fn count_chars(spans: &[Span]) -> usize {
+  spans.iter().map(|span| span.text.chars().count()).sum()
+}
+
+/// A run of text positioned on a line
+#[derive(Clone, Debug, PartialEq)]
+pub struct LayoutRun {
+  pub text: String,
+  pub style: SpanStyle,
+  pub x: Size,
+}
+
+/// A line of laid out text
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LayoutLine {
+  pub runs: Vec<LayoutRun>,
+  pub width: Size,
+}
+
+impl LayoutLine {
+  /// Add text to the end of the line, merging it into the last run if the style is the same
+  fn push(&mut self, text: &str, style: SpanStyle, width: Size) {
+    match self.runs.last_mut() {
+      Some(run) if run.style == style => run.text.push_str(text),
+      _ => self.runs.push(LayoutRun { text: String::from(text), style, x: self.width }),
+    }
+    self.width += width;
+  }
+}
+
+/// Text laid out into lines
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Layout {
+  pub lines: Vec<LayoutLine>,
+  pub dimensions: Size2,
+  pub line_height: Size,
+}
+
+/// A word of styled fragments and the whitespace before it
+struct Word {
+  gap: String,
+  fragments: Vec<(String, SpanStyle)>,
+}
+
+/// Split spans into words, and line breaks
+///
+/// Whitespace before a line break is dropped.
+fn split_words(spans: &[Span]) -> Vec<Option<Word>> {
+  let mut words = Vec::new();
+  let mut gap = String::new();
+  let mut word = Vec::<(String, SpanStyle)>::new();
+  for span in spans {
+    let mut fragment = String::new();
+    for char in span.text.chars() {
+      if char.is_whitespace() {
+        if !fragment.is_empty() { word.push((std::mem::take(&mut fragment), span.style)); }
+        if !word.is_empty() { words.push(Some(Word { gap: std::mem::take(&mut gap), fragments: std::mem::take(&mut word) })); }
+        if char == NEW_LINE {
+          gap.clear();
+          words.push(None);
+        } else {
+          gap.push(char);
+        }
+      } else {
+        fragment.push(char);
+      }
+    }
+    if !fragment.is_empty() { word.push((fragment, span.style)); }
+  }
+  if !word.is_empty() { words.push(Some(Word { gap, fragments: word })); }
+  words
+}
+
+/// Lay out spans into lines no wider than `width`, measuring text with `measure`
+///
+/// Words are wrapped onto the next line when they would overflow, and a single word wider than `width` overflows
+/// its line. Whitespace is treated by `whitespace`, and newlines always start a new line. Lines are then offset by
+/// `justify` within `width`.
+pub fn layout_spans(spans: &[Span], width: Size, line_height: Size, justify: Justify, whitespace: Whitespace, measure: impl Fn(&str, SpanStyle) -> Size) -> Layout {
+  let mut lines = vec![LayoutLine::default()];
+  // preserved whitespace is only dropped at the start of wrapped lines
+  let mut wrapped = false;
+  for word in split_words(spans) {
+    let Some(word) = word else {
+      lines.push(LayoutLine::default());
+      wrapped = false;
+      continue;
+    };
+
+    let line = lines.last_mut().expect("Layout has a line");
+    let space_style = line.runs.last().map(|run| run.style).unwrap_or_default();
+    let gap = match whitespace {
+      Whitespace::Collapse if line.runs.is_empty() => "",
+      Whitespace::Collapse => " ",
+      Whitespace::Preserve if line.runs.is_empty() && wrapped => "",
+      Whitespace::Preserve => word.gap.as_str(),
+    };
+    let space = if gap.is_empty() { 0 } else { measure(gap, space_style) };
+    let word_width = word.fragments.iter().map(|(fragment, style)| measure(fragment, *style)).sum::<Size>();
+
+    let line = if !line.runs.is_empty() && line.width + space + word_width > width {
+      lines.push(LayoutLine::default());
+      wrapped = true;
+      lines.last_mut().expect("Layout has a line")
+    } else {
+      if space > 0 { line.push(gap, space_style, space); }
+      line
+    };
+    for (fragment, style) in word.fragments {
+      let fragment_width = measure(&fragment, style);
+      line.push(&fragment, style, fragment_width);
+    }
+  }
+
+  for line in &mut lines {
+    let offset = match justify {
+      Justify::Left => 0,
+      Justify::Center => width.saturating_sub(line.width) / 2,
+      Justify::Right => width.saturating_sub(line.width),
+    };
+    for run in &mut line.runs { run.x += offset; }
+  }
+
+  let widest = lines.iter().map(|line| line.width).max().unwrap_or_default();
+  let dimensions = Size2::new(width.max(widest), lines.len() as Size * line_height);
+  Layout { lines, dimensions, line_height }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const CHAR_WIDTH: Size = 6;
+
+  fn measure(text: &str, style: SpanStyle) -> Size {
+    text.chars().count() as Size * CHAR_WIDTH + if style.emphasis { 1 } else { 0 }
+  }
+
+  fn line_text(line: &LayoutLine) -> String {
+    line.runs.iter().map(|run| run.text.as_str()).collect()
+  }
+
+  #[test]
+  fn test_parse_markup() {
+    let spans = parse_markup("A [color=#ff0000]red [em]bold[/em][/color] [[word]").unwrap();
+    let red = Some(RGBA::new(255, 0, 0, OPAQUE));
+    assert_eq!(spans, vec![
+      Span { text: String::from("A "), style: SpanStyle::default() },
+      Span { text: String::from("red "), style: SpanStyle { color: red, emphasis: false } },
+      Span { text: String::from("bold"), style: SpanStyle { color: red, emphasis: true } },
+      Span { text: String::from(" [word]"), style: SpanStyle::default() },
+    ]);
+    assert_eq!(count_chars(&spans), 17);
+  }
+
+  #[test]
+  fn test_literal_tags() {
+    let spans = parse_markup("Scan [complete] [em]now[/em] [/wave] [open").unwrap();
+    let text = spans.iter().map(|span| span.text.as_str()).collect::<String>();
+    assert_eq!(text, "Scan [complete] now [/wave] [open", "unknown and unterminated tags are text");
+    assert!(spans[1].style.emphasis);
+  }
+
+  #[test]
+  fn test_invalid_markup() {
+    assert!(parse_markup("[em]unclosed").is_err(), "tag is never closed");
+    assert!(parse_markup("[em][color=#fff000]crossed[/em][/color]").is_err(), "tags are closed out of order");
+    assert!(parse_markup("[color=red]named[/color]").is_err(), "color must be hex");
+    assert!(parse_markup("[/em]").is_err(), "tag is never opened");
+  }
+
+  #[test]
+  fn test_wrap() {
+    let spans = parse_markup("The quick brown fox jumps over the lazy dog").unwrap();
+    let layout = layout_spans(&spans, 10 * CHAR_WIDTH, 8, Justify::Left, Whitespace::Collapse, measure);
+    let lines = layout.lines.iter().map(line_text).collect::<Vec<_>>();
+    assert_eq!(lines, vec!["The quick", "brown fox", "jumps over", "the lazy", "dog"]);
+    assert_eq!(layout.dimensions, Size2::new(60, 40), "layout fills the box width");
+  }
+
+  #[test]
+  fn test_wrap_by_style() {
+    // emphasis widens the word so it no longer fits
+    let spans = parse_markup("jumps [em]over[/em]").unwrap();
+    let layout = layout_spans(&spans, 10 * CHAR_WIDTH, 8, Justify::Left, Whitespace::Collapse, measure);
+    assert_eq!(layout.lines.len(), 2, "emphasised word wraps");
+    assert_eq!(layout.lines[1].runs[0].style.emphasis, true, "wrapped word keeps its style");
+  }
+
+  #[test]
+  fn test_newlines_and_overflow() {
+    let spans = parse_markup("Disestablishmentarianism\nis long").unwrap();
+    let layout = layout_spans(&spans, 10 * CHAR_WIDTH, 8, Justify::Left, Whitespace::Collapse, measure);
+    let lines = layout.lines.iter().map(line_text).collect::<Vec<_>>();
+    assert_eq!(lines, vec!["Disestablishmentarianism", "is long"], "long words overflow and newlines break");
+    assert_eq!(layout.dimensions.x, 24 * CHAR_WIDTH, "layout grows to the overflowing word");
+  }
+
+  #[test]
+  fn test_preserve_whitespace() {
+    let spans = parse_markup("Scan    [ok]   \n  - rocky terrain").unwrap();
+    let layout = layout_spans(&spans, 12 * CHAR_WIDTH, 8, Justify::Left, Whitespace::Preserve, measure);
+    let lines = layout.lines.iter().map(line_text).collect::<Vec<_>>();
+    assert_eq!(lines, vec!["Scan    [ok]", "  - rocky", "terrain"], "runs of spaces are kept except where lines wrap");
+    let collapsed = layout_spans(&spans, 20 * CHAR_WIDTH, 8, Justify::Left, Whitespace::Collapse, measure);
+    assert_eq!(line_text(&collapsed.lines[0]), "Scan [ok]");
+  }
+
+  #[test]
+  fn test_justify() {
+    let spans = parse_markup("fox").unwrap();
+    let center = layout_spans(&spans, 10 * CHAR_WIDTH, 8, Justify::Center, Whitespace::Collapse, measure);
+    let right = layout_spans(&spans, 10 * CHAR_WIDTH, 8, Justify::Right, Whitespace::Collapse, measure);
+    assert_eq!(center.lines[0].runs[0].x, 21, "centered in the box");
+    assert_eq!(right.lines[0].runs[0].x, 42, "against the right of the box");
+  }
+}
@@ -9,3 +9,4 @@ pub mod state;
 pub mod store;
 pub mod time;
 pub mod text;
+pub mod layout;
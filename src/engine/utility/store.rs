@@ -80,4 +80,11 @@ impl<K, V> Store<K, V> where
       Err(format!("Failed to get {} from store", key))
     };
   }
+
+  /// Retrieve a mutable reference to item in the store
+  pub fn get_mut(&mut self, key: impl Into<K>) -> Result<&mut V, String> {
+    let key = key.into();
+    let error = format!("Failed to get {} from store", key);
+    self.store.get_mut(&key).ok_or(error)
+  }
 }
@@ -1,13 +1,13 @@
 use crate::engine::geometry::shape::{Rec2, Vec2};
 use crate::engine::math::interpolation::CubicBezierCurve;
+use crate::engine::render::presentation::Presentation;
 use crate::engine::utility::alignment::Aligner;
 
 // Static //
 
-pub const TYPEFACE_PATH: &str = "asset/typography/typeface.ttf";
-pub const TYPEFACE_SIZE: u16 = 5;
 pub const DEV_SAVE_FILE: &str = "data/dev_save.json";
 pub const USER_SAVE_FILE: &str = "user_save.json";
+pub const PREFERENCES_FILE: &str = "preferences.json";
 pub const WORLD_PATH: &str = "asset/world/world.world";
 
 // Tiles //
@@ -19,11 +19,16 @@ pub const TILE_SIZE: Vec2<u32> = Vec2::new(16, 16);
 pub const WINDOW_TITLE: &str = "Mausoleum";
 pub const LOGICAL_SIZE_TILES: Vec2<u32> = Vec2::new(40, 22);
 pub const LOGICAL_SIZE: Vec2<u32> = Vec2::new(LOGICAL_SIZE_TILES.x * TILE_SIZE.x, LOGICAL_SIZE_TILES.y * TILE_SIZE.y);
-pub const WINDOW_SCALE: f32 = 2.0;
-pub const WINDOW_SIZE: Vec2<u32> = Vec2::new(
-  (LOGICAL_SIZE.x as f32 * WINDOW_SCALE) as u32,
-  (LOGICAL_SIZE.y as f32 * WINDOW_SCALE) as u32,
-);
+pub const WINDOW_MULTIPLIER: u32 = 2;
+pub const WINDOW_SIZE: Vec2<u32> = Vec2::new(LOGICAL_SIZE.x * WINDOW_MULTIPLIER, LOGICAL_SIZE.y * WINDOW_MULTIPLIER);
+/// Presentations cycled through by the display setting, the first is used by default
+pub const PRESENTATIONS: [Presentation; 5] = [
+  Presentation::Multiplier(WINDOW_MULTIPLIER),
+  Presentation::Multiplier(WINDOW_MULTIPLIER + 1),
+  Presentation::Multiplier(1),
+  Presentation::Integer,
+  Presentation::Stretch,
+];
 pub const WINDOW: Aligner = Aligner::new(Rec2::new(Vec2::const_default(), LOGICAL_SIZE));
 
 
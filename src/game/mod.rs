@@ -2,5 +2,7 @@ pub mod constant;
 pub mod scene;
 pub mod utility;
 pub mod preferences;
+pub mod sound;
+pub mod music;
 pub mod persistence;
 pub mod ui;
@@ -0,0 +1,287 @@
+/**
+ * Area music that follows the player between rooms
+ */
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use crate::engine::asset::audio::{AudioPlayer, Loop, SoundHandle, SoundType};
+use crate::engine::asset::synth::{load_synth_presets, SynthSequence};
+use crate::engine::ecs::system::{SysArgs, Systemize};
+use crate::engine::asset::vfs::read_to_string;
+use crate::engine::utility::io::deserialize_json;
+use crate::engine::utility::state::State;
+use crate::game::scene::level::scene::LevelState;
+use crate::game::sound::SoundSource;
+
+pub const MUSIC_DATA_PATH: &str = "data/music.json";
+
+/// Where the audio of a track comes from
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum TrackSource {
+  /// music file relative to the asset root
+  File { file: PathBuf },
+  /// a loop sequenced from the synth presets
+  Synth { synth: SynthSequence },
+}
+
+/// A looping music track
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct TrackData {
+  #[serde(flatten)]
+  pub source: TrackSource,
+  /// volume in the unit interval
+  #[serde(default = "default_volume")]
+  pub volume: f32,
+}
+
+/// A short cue played over the music
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct StingerData {
+  #[serde(flatten)]
+  pub source: SoundSource,
+  /// volume in the unit interval
+  #[serde(default = "default_volume")]
+  pub volume: f32,
+}
+
+/// Occurrences in the game that play a stinger
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Stinger {
+  Story,
+  Collect,
+}
+
+/// Tracks of each area, and the stingers played over them
+#[derive(Deserialize, Debug, Default)]
+pub struct MusicData {
+  /// milliseconds to fade between tracks
+  #[serde(default = "default_fade")]
+  pub fade: u64,
+  pub tracks: HashMap<String, TrackData>,
+  /// track of each area, keyed by the prefix of the names of its rooms
+  pub areas: HashMap<String, String>,
+  #[serde(default)]
+  pub story: Option<StingerData>,
+  #[serde(default)]
+  pub collect: Option<StingerData>,
+}
+
+fn default_volume() -> f32 { 1.0 }
+
+fn default_fade() -> u64 { 2000 }
+
+impl MusicData {
+  /// Get the track of a room, the `Music` property of the room overrides the track of its area
+  ///
+  /// The area of a room is the longest prefix of its name in the area table.
+  pub fn get_track<'a>(&'a self, room: &str, music: Option<&'a str>) -> Option<&'a str> {
+    music.or_else(|| {
+      self.areas
+        .iter()
+        .filter(|(prefix, ..)| room.starts_with(prefix.as_str()))
+        .max_by_key(|(prefix, ..)| prefix.len())
+        .map(|(.., track)| track.as_str())
+    })
+  }
+  /// Get the data of a stinger
+  pub fn get_stinger(&self, stinger: Stinger) -> Option<&StingerData> {
+    match stinger {
+      Stinger::Story => self.story.as_ref(),
+      Stinger::Collect => self.collect.as_ref(),
+    }
+  }
+}
+
+/// Parse music data, checking every area has a track
+pub fn parse_music_data(data: &str) -> Result<MusicData, String> {
+  let music = deserialize_json::<MusicData>(data)?;
+  if let Some((prefix, track)) = music.areas.iter().find(|(.., track)| !music.tracks.contains_key(*track)) {
+    return Err(format!("Area '{}' has an unknown track '{}'", prefix, track));
+  }
+  Ok(music)
+}
+
+/// Fades to the track of the current room, and pauses the music while stingers play
+///
+/// Tracks and stingers whose file failed to load are silent, and a room without a track fades the music out.
+#[derive(Debug, Default)]
+pub struct MusicDirector {
+  data: MusicData,
+  /// audio names of the loaded tracks and stingers
+  loaded: HashMap<String, String>,
+  room: Option<String>,
+  queue: Vec<Stinger>,
+  stinger: Option<SoundHandle>,
+  paused: Option<SoundHandle>,
+}
+
+impl MusicDirector {
+  /// Load the tracks and stingers of a data file, reporting the files that fail to load
+  ///
+  /// Synthesized tracks are sequenced from the presets of `synth_filepath`, and synthesized stingers must already be
+  /// in the audio store.
+  pub fn load(filepath: &str, synth_filepath: &str, audio: &mut AudioPlayer) -> Result<Self, String> {
+    let data = parse_music_data(&read_to_string(filepath)?).map_err(|e| format!("Failed to parse music {}: {}", filepath, e))?;
+    let presets = load_synth_presets(synth_filepath)?;
+    let mut loaded = HashMap::new();
+    for (track, TrackData { source, .. }) in &data.tracks {
+      let name = match source {
+        TrackSource::File { file } => audio.load(SoundType::Music, file),
+        TrackSource::Synth { synth } => audio.synthesize_music(track, synth, &presets, filepath),
+      };
+      match name {
+        Ok(name) => { loaded.insert(track.clone(), name); }
+        Err(error) => eprintln!("Track '{}' is silent: {}", track, error),
+      }
+    }
+    for (stinger, StingerData { source, .. }) in [("story", &data.story), ("collect", &data.collect)]
+      .into_iter()
+      .filter_map(|(stinger, data)| data.as_ref().map(|data| (stinger, data)))
+    {
+      let name = match source {
+        SoundSource::File { file } => audio.load(SoundType::Effect, file),
+        SoundSource::Synth { synth } if audio.is_loaded(synth) => Ok(synth.clone()),
+        SoundSource::Synth { synth } => Err(format!("Synth preset {} is missing", synth)),
+      };
+      match name {
+        Ok(name) => { loaded.insert(String::from(stinger), name); }
+        Err(error) => eprintln!("Stinger '{}' is silent: {}", stinger, error),
+      }
+    }
+    Ok(Self { data, loaded, ..Default::default() })
+  }
+
+  /// Get the duration of a fade between tracks
+  fn get_fade(&self) -> Duration { Duration::from_millis(self.data.fade) }
+
+  /// Fade to the track of a room, music keeps playing if the track is the same
+  fn enter(&mut self, room: &str, music: Option<&str>, audio: &mut AudioPlayer) -> Result<(), String> {
+    let track = self.data.get_track(room, music);
+    let volume = track.and_then(|track| self.data.tracks.get(track)).map_or(1.0, |track| track.volume);
+    match track.and_then(|track| self.loaded.get(track)) {
+      Some(name) => audio.fade_to(name, volume, self.get_fade()),
+      None => audio.fade_out_music(self.get_fade()),
+    }
+  }
+  /// Fade out the music and forget the current room, so the track starts again when a room is entered
+  pub fn leave(&mut self, audio: &mut AudioPlayer) -> Result<(), String> {
+    self.room = None;
+    audio.fade_out_music(self.get_fade())
+  }
+
+  /// Queue a stinger to play over the music at the end of the frame
+  pub fn play_stinger(&mut self, stinger: Stinger) { self.queue.push(stinger); }
+  /// Play queued stingers, pausing the music until the last has finished
+  fn update_stingers(&mut self, audio: &mut AudioPlayer) -> Result<(), String> {
+    for stinger in std::mem::take(&mut self.queue) {
+      let key = match stinger {
+        Stinger::Story => "story",
+        Stinger::Collect => "collect",
+      };
+      let (Some(name), Some(data)) = (self.loaded.get(key), self.data.get_stinger(stinger)) else { continue; };
+      if let Some(handle) = self.stinger.take() { audio.stop(handle); }
+      self.stinger = Some(audio.play(name, data.volume, Loop::Once)?);
+      if self.paused.is_none() {
+        self.paused = audio.get_music_handle();
+        if let Some(music) = self.paused { audio.pause(music); }
+      }
+    }
+
+    if self.stinger.is_some_and(|stinger| !audio.is_playing(stinger)) {
+      self.stinger = None;
+      if let Some(music) = self.paused.take() { audio.resume(music); }
+    }
+    Ok(())
+  }
+}
+
+impl Systemize for MusicDirector {
+  /// Switch tracks once a room transition completes, and play stingers
+  fn system(SysArgs { state, asset, .. }: &mut SysArgs) -> Result<(), String> {
+    let room_registry = &state.get::<LevelState>()?.room_registry;
+    let room = room_registry
+      .get_current()
+      .filter(|_| !room_registry.is_transitioning())
+      .map(|room| (room.get_name(), room.get_music().map(String::from)));
+
+    let director = use_music(state);
+    if let Some((room, music)) = room.filter(|(room, ..)| director.room.as_ref() != Some(room)) {
+      director.enter(&room, music.as_deref(), &mut asset.audio)?;
+      director.room = Some(room);
+    }
+    director.update_stingers(&mut asset.audio)
+  }
+}
+
+/// Mutably borrows the music director from the engine
+pub fn use_music(state: &mut State) -> &mut MusicDirector {
+  state.get_mut::<MusicDirector>().expect("Failed to get music director")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const MUSIC: &str = r#"{
+    "tracks": {
+      "surface": { "file": "asset/music/surface.ogg" },
+      "shrine": { "file": "asset/music/shrine.ogg", "volume": 0.5 }
+    },
+    "areas": { "surface_": "surface", "surface_shrine": "shrine" },
+    "collect": { "synth": "fanfare" }
+  }"#;
+
+  #[test]
+  fn test_area_track() {
+    let music = parse_music_data(MUSIC).unwrap();
+    assert_eq!(music.get_track("surface_3", None), Some("surface"));
+    assert_eq!(music.get_track("surface_shrine_0", None), Some("shrine"), "longest prefix wins");
+    assert_eq!(music.get_track("surface_3", Some("shrine")), Some("shrine"), "room property overrides its area");
+    assert_eq!(music.get_track("cavern_0", None), None, "room has no area");
+    assert_eq!(music.fade, 2000);
+    assert!(music.get_stinger(Stinger::Collect).is_some());
+    assert!(music.get_stinger(Stinger::Story).is_none(), "stingers are optional");
+  }
+
+  /// The music table only references track files and synth presets that ship with the game, and every room has a track
+  #[test]
+  fn test_shipped_music() {
+    use crate::engine::asset::synth::{synthesize_sequence, SynthParams};
+    let root = std::path::Path::new(env!("CARGO_MANIFEST_DIR"));
+    let read = |path: &str| std::fs::read_to_string(root.join(path)).unwrap();
+    let presets = deserialize_json::<HashMap<String, SynthParams>>(&read(crate::game::sound::SYNTH_DATA_PATH)).unwrap();
+    let music = parse_music_data(&read(MUSIC_DATA_PATH)).unwrap();
+    for (track, TrackData { source, .. }) in &music.tracks {
+      match source {
+        TrackSource::File { file } => assert!(root.join(file).is_file(), "Track '{}' is missing {}", track, file.display()),
+        TrackSource::Synth { synth } => {
+          let sequenced = synthesize_sequence(synth, &presets, 1000, 1);
+          assert!(sequenced.is_ok(), "Track '{}' cannot be synthesized: {}", track, sequenced.unwrap_err());
+        }
+      }
+    }
+    let world = deserialize_json::<serde_json::Value>(&read("asset/world/world.world")).unwrap();
+    for map in world["maps"].as_array().unwrap() {
+      let room = map["fileName"].as_str().unwrap().trim_end_matches(".tmx");
+      assert!(music.get_track(room, None).is_some(), "Room {} has no area track", room);
+    }
+    for stinger in [Stinger::Story, Stinger::Collect] {
+      match music.get_stinger(stinger).map(|data| &data.source) {
+        Some(SoundSource::File { file }) => assert!(root.join(file).is_file(), "Stinger {:?} is missing {}", stinger, file.display()),
+        Some(SoundSource::Synth { synth }) => assert!(presets.contains_key(synth), "Stinger {:?} has no synth preset {}", stinger, synth),
+        None => (),
+      }
+    }
+  }
+
+  #[test]
+  fn test_unknown_track() {
+    let music = r#"{ "tracks": {}, "areas": { "basin_": "basin" } }"#;
+    assert!(parse_music_data(music).is_err(), "area has no track");
+  }
+}
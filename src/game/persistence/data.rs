@@ -101,7 +101,7 @@ mod tests {
   const MOCK_ROOM: &str = "mock_room";
 
   fn mock_item_data(collectable: Collectable) -> Item {
-    Item { collectable, map_index: MapIndex::default(), room_name: String::from(MOCK_ROOM) }
+    Item { collectable, map_index: MapIndex::default(), room_name: String::from(MOCK_ROOM), layer_name: None }
   }
 
   fn mock_items_data(collectables: impl IntoIterator<Item=Collectable>) -> impl Iterator<Item=Item> {
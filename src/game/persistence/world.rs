@@ -1,4 +1,5 @@
 use hecs::Entity;
+use serde::Deserialize;
 
 use crate::engine::component::position::Position;
 use crate::engine::ecs::system::{SysArgs, Systemize};
@@ -13,10 +14,12 @@ use crate::game::persistence::data::SaveData;
 use crate::game::scene::level::physics::collision::{Collider, make_collision_box};
 use crate::game::scene::level::player::world::{PlayerQuery, use_player};
 use crate::game::scene::level::room::collision::{CollisionBox, CollisionMask, rec2_collision};
+use crate::game::scene::level::room::object::{ObjectClass, ObjectData, SpawnArgs};
 use crate::game::scene::level::room::room::use_room;
 use crate::game::scene::level::scene::LevelScene;
 use crate::game::scene::level::story::data::StoryItem;
 use crate::game::scene::level::story::modal::make_story_modal;
+use crate::game::sound::{SoundEvent, use_sounds};
 use crate::game::ui::text_builder::TextBuilder;
 use crate::game::utility::controls::{Behaviour, Control, is_control};
 
@@ -80,10 +83,7 @@ impl Systemize for SaveArea {
 
     if let Some(area) = active_save_rooms.first() {
       if area.active {
-        let typeface = asset.typeface
-          .use_store()
-          .get("typeface")?;
-        let mut builder: TextBuilder<'_, '_, Sticky1> = TextBuilder::new(&typeface, &mut asset.texture, color::TEXT, WINDOW);
+        let mut builder: TextBuilder<'_, '_, Sticky1> = TextBuilder::new(&asset.typeface, &mut asset.texture, color::TEXT, WINDOW);
         world.add(builder.make_text::<SaveText>("Press up to save", Alignment::new(Align::Center(0.0), Align::Center(0.0))));
 
         if area.room != INITIAL_SAVE_ROOM {
@@ -113,6 +113,7 @@ impl Systemize for SaveArea {
 
       let save_data = SaveData::build(save_room, collection, advancement.clone().into(), saved_position)?;
       save_data.to_file(USER_SAVE_FILE)?;
+      use_sounds(state).play(SoundEvent::Save);
 
       scene.queue_next(LevelScene::new(save_data));
     }
@@ -124,6 +125,23 @@ impl Systemize for SaveArea {
 /// Save area components
 type SaveAreaBundle = (SaveArea, Position, Collider);
 
+/// Properties of a Save object in Tiled, the area the player saves their progress in
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SaveObject {}
+
+impl ObjectClass for SaveObject {
+  const CLASS: &'static str = "Save";
+  fn check(object: &ObjectData) -> Result<(), String> {
+    object.get_rectangle().map(|_| ()).ok_or(String::from("Save area must be a rectangle with a width and height"))
+  }
+  fn spawn(&self, object: &ObjectData, SpawnArgs { world, story, room, .. }: &mut SpawnArgs) -> Result<Option<Entity>, String> {
+    let bounds = object.get_rectangle().ok_or("Save area must be a rectangle with a width and height")?;
+    let area = make_save_area(String::from(*room), CollisionBox::new(bounds.origin, bounds.size), story.get_entry("save"))?;
+    Ok(Some(world.add(area)))
+  }
+}
+
 /// Compose save area components from a save room and collision box
 pub fn make_save_area(save_room: String, area: CollisionBox, story: Option<StoryItem>) -> Result<SaveAreaBundle, String> {
   Ok((
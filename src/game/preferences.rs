@@ -1,20 +1,69 @@
+/**
+ * Preferences persisted between sessions
+ */
+
+use serde::{Deserialize, Serialize};
+
+use crate::engine::asset::audio::Volumes;
+use crate::engine::render::presentation::Presentation;
+use crate::engine::utility::io::{deserialize_json, read_file, serialize_json, write_file};
 use crate::engine::utility::state::State;
+use crate::game::constant::PRESENTATIONS;
 
 /// Stores global preferences for the game
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Preferences {
-  pub debug: bool,
+  pub presentation: Presentation,
+  pub volumes: Volumes,
 }
 
 impl Default for Preferences {
   /// Apply default preferences to the game
   fn default() -> Self {
     Self {
-      debug: false,
+      presentation: PRESENTATIONS[0],
+      volumes: Volumes::default(),
     }
   }
 }
 
+impl Preferences {
+  /// Load preferences from a file
+  pub fn from_file(filepath: impl AsRef<std::path::Path>) -> Result<Self, String> {
+    read_file(filepath).and_then(|data| deserialize_json(&data))
+  }
+  /// Save the preferences to a file
+  pub fn to_file(&self, filepath: impl AsRef<std::path::Path>) -> Result<(), String> {
+    serialize_json(self).and_then(|data| write_file(filepath, data))
+  }
+  /// Select the presentation after the current one
+  pub fn cycle_presentation(&mut self) -> Presentation {
+    let index = PRESENTATIONS
+      .iter()
+      .position(|presentation| *presentation == self.presentation)
+      .map_or(0, |index| (index + 1) % PRESENTATIONS.len());
+    self.presentation = PRESENTATIONS[index];
+    self.presentation
+  }
+}
+
 /// Mutably borrows the preference state from the engine
 pub fn use_preferences(state: &mut State) -> &mut Preferences {
   state.get_mut::<Preferences>().expect("Failed to get preferences")
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_cycle_presentation() {
+    let mut preferences = Preferences::default();
+    let cycled = (0..PRESENTATIONS.len()).map(|_| preferences.cycle_presentation()).collect::<Vec<_>>();
+    assert_eq!(cycled.last(), Some(&PRESENTATIONS[0]), "cycling wraps to the first presentation");
+
+    preferences.presentation = Presentation::Multiplier(7);
+    assert_eq!(preferences.cycle_presentation(), PRESENTATIONS[0], "unlisted presentations cycle from the start");
+  }
+}
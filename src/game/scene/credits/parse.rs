@@ -1,11 +1,11 @@
 use std::path::Path;
 
-use crate::engine::utility::io::read_file;
+use crate::engine::asset::vfs::read_to_string;
 
 const CREDITS_FILE: &str = "data/credits.txt";
 
 pub fn load_credits() -> Result<Vec<String>, String> {
-  let contents = read_file(Path::new(CREDITS_FILE))?;
+  let contents = read_to_string(Path::new(CREDITS_FILE))?;
 
   Ok(contents
     .lines()
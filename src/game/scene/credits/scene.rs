@@ -27,8 +27,7 @@ pub struct CreditState {
 
 /// Add the credit text to the world
 fn add_credits(world: &mut World, camera: &Camera, asset: &mut AssetManager) -> Duration {
-  let typeface = asset.typeface.use_store().get("typeface").expect("Failed to get typeface");
-  let mut builder = TextBuilder::<Sticky1>::new(typeface, &mut asset.texture, color::TEXT, WINDOW);
+  let mut builder = TextBuilder::<Sticky1>::new(&asset.typeface, &mut asset.texture, color::TEXT, WINDOW);
 
   let lines = load_credits().expect("Failed to load credits");
   let line_count = lines.len();
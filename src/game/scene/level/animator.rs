@@ -0,0 +1,41 @@
+/**
+ * Drive animator parameters from the state of the level
+ */
+
+use crate::engine::component::animator::Animator;
+use crate::engine::ecs::system::SysArgs;
+use crate::game::scene::level::physics::frozen::Frozen;
+use crate::game::scene::level::physics::velocity::Velocity;
+use crate::game::scene::level::player::controller::PlayerController;
+use crate::game::scene::level::room::collision::Grounded;
+
+/// The entity stands on a tile
+pub const PARAM_GROUNDED: &str = "grounded";
+/// The horizontal speed of the entity
+pub const PARAM_SPEED: &str = "speed";
+/// The vertical velocity of the entity, negative when moving up
+pub const PARAM_RISE: &str = "rise";
+/// The vertical component of the direction the player aims, negative when aiming up
+pub const PARAM_AIM: &str = "aim";
+/// The entity is frozen
+pub const PARAM_FROZEN: &str = "frozen";
+/// The entity was damaged
+pub const TRIGGER_HIT: &str = "hit";
+
+/// Set the parameters of animators each frame
+pub fn sys_animator_params(SysArgs { world, .. }: &mut SysArgs) -> Result<(), String> {
+  for (_, (animator, velocity, grounded, frozen)) in world.query::<(&mut Animator, Option<&Velocity>, Option<&Grounded>, Option<&Frozen>)>() {
+    if let Some(grounded) = grounded { animator.set_bool(PARAM_GROUNDED, grounded.0); }
+    if let Some(velocity) = velocity {
+      animator.set_float(PARAM_SPEED, velocity.0.x.abs());
+      animator.set_float(PARAM_RISE, velocity.0.y);
+    }
+    animator.set_bool(PARAM_FROZEN, frozen.is_some());
+  }
+
+  for (_, (animator, controller)) in world.query::<(&mut Animator, &PlayerController)>() {
+    animator.set_float(PARAM_AIM, controller.get_aim().to_coordinate().y as f32);
+  }
+
+  Ok(())
+}
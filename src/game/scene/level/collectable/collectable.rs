@@ -14,6 +14,7 @@ use crate::game::scene::level::scene::LevelState;
 use crate::game::scene::level::tile::query::{TileHandle, TileQuery};
 use crate::game::scene::level::tile::tile::TileCollider;
 use crate::game::scene::level::tile::tilemap::TilemapMutation;
+use crate::game::sound::{SoundEvent, use_sounds};
 
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct Collection(Vec<Item>);
@@ -53,8 +54,11 @@ impl Systemize for Collection {
       .query::<(&Collectable, &Position, &TileCollider)>()
       .into_iter()
       .map(|(entity, (collectable, position, collider))| {
-        let collision_box = CollisionBox::new(position.0 + collider.collision_box.origin, collider.collision_box.size);
-        (entity, collectable.clone(), collision_box)
+        let collision_boxes = collider.collision_boxes
+          .iter()
+          .map(|collision_box| CollisionBox::new(position.0 + collision_box.origin, collision_box.size))
+          .collect::<Vec<_>>();
+        (entity, collectable.clone(), collision_boxes)
       })
       .collect::<Vec<_>>();
 
@@ -64,12 +68,12 @@ impl Systemize for Collection {
 
     let collected = collectables
       .into_iter()
-      .filter_map(|(collectable_entity, collectable, collectable_box)| {
+      .filter_map(|(collectable_entity, collectable, collectable_boxes)| {
         for (_, (collector_position, collector_collider, collection)) in world
           .query::<(&Position, &Collider, &mut Collection)>()
         {
           let collector_box = make_collision_box(collector_position, collector_collider);
-          if rec2_collision(&collector_box, &collectable_box, CollisionMask::full()).is_some() {
+          if collectable_boxes.iter().any(|collectable_box| rec2_collision(&collector_box, collectable_box, CollisionMask::full()).is_some()) {
             if !collection.has(&collectable) {
               if modal_data.is_some() {
                 eprintln!("Collection gained more than one new collectable in the same frame!");
@@ -87,6 +91,7 @@ impl Systemize for Collection {
               collectable,
               map_index: tile_handle.index,
               room_name: room.get_name(),
+              layer_name: room.get_layer_name(tile_handle.layer_index),
             });
 
             return Some(tile_handle);
@@ -97,9 +102,11 @@ impl Systemize for Collection {
       .collect::<Vec<_>>();
 
     let room = use_room(state);
+    let collected_any = !collected.is_empty();
     for handle in collected {
       room.remove_tile(world, handle, TilemapMutation::Session);
     }
+    if collected_any { use_sounds(state).play(SoundEvent::Collect); }
 
     if let Some(collected) = modal_data {
       make_collectable_modal(world, event, asset, state, &collected);
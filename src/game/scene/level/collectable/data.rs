@@ -4,14 +4,16 @@
   */
 
 use serde::Deserialize;
-use crate::engine::utility::io::{deserialize_json, read_file};
+use crate::engine::asset::vfs::read_to_string;
+use crate::engine::utility::io::deserialize_json;
 use crate::game::scene::level::room::meta::Collectable;
-use crate::game::scene::level::tile::tile::TileKey;
+use crate::game::scene::level::tile::tile::{TileKey, TilesetKey};
+use crate::game::scene::level::tile::tileset::DEFAULT_TILESET;
 
 pub const COLLECTABLE_DATA_PATH: &str = "data/collectable.json";
 
 pub fn deserialize_weapon_data() -> Result<CollectableData, String> {
-  let serialized = read_file(COLLECTABLE_DATA_PATH)?;
+  let serialized = read_to_string(COLLECTABLE_DATA_PATH)?;
   deserialize_json(&serialized)
 }
 
@@ -21,8 +23,13 @@ pub struct CollectableItemData {
   pub description: String,
   pub key: Option<String>,
   pub tile: TileKey,
+  /// tileset the icon tile belongs to
+  #[serde(default = "default_tileset")]
+  pub tileset: TilesetKey,
 }
 
+fn default_tileset() -> TilesetKey { TilesetKey::from(DEFAULT_TILESET) }
+
 #[derive(Deserialize, Debug)]
 pub struct CollectableData {
   pub bullet: CollectableItemData,
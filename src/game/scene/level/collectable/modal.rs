@@ -5,16 +5,19 @@
 use std::time::Duration;
 
 use crate::engine::asset::asset::AssetManager;
+use crate::engine::asset::manifest::AssetManifest;
 use crate::engine::component::position::Position;
 use crate::engine::component::sprite::Sprite;
+use crate::engine::component::text::TextLayout;
 use crate::engine::core::event::EventStore;
 use crate::engine::ecs::system::{SysArgs, Systemize};
 use crate::engine::ecs::world::World;
 use crate::engine::render::camera::Sticky2;
 use crate::engine::utility::alias::Size2;
 use crate::engine::utility::alignment::{Align, Alignment};
+use crate::engine::utility::layout::Justify;
 use crate::engine::utility::state::State;
-use crate::engine::utility::text::split_text;
+use crate::game::music::{Stinger, use_music};
 use crate::game::scene::level::collectable::data::CollectableItemData;
 use crate::game::scene::level::room::meta::TileMeta;
 use crate::game::scene::level::room::room::use_tileset;
@@ -29,9 +32,8 @@ const MODAL_MARGIN: f32 = 8.0;
 
 const ICON_TOP_OFFSET: f32 = 32.0;
 const DESCRIPTION_TOP_OFFSET: f32 = 64.0;
-const DESCRIPTION_LINE_HEIGHT: f32 = 8.0;
-const CHAR_WIDTH: f32 = 5.0 + 1.0;
-const DESCRIPTION_LINE_LENGTH: usize = (MODAL_SIZE.x as usize - (MODAL_MARGIN * 2.0) as usize) / CHAR_WIDTH as usize;
+const DESCRIPTION_LINE_HEIGHT: u32 = 8;
+const DESCRIPTION_WIDTH: u32 = MODAL_SIZE.x - (MODAL_MARGIN * 2.0) as u32;
 
 const CHAR_ITERATION_MS: Duration = Duration::from_millis(32);
 
@@ -44,33 +46,31 @@ pub fn make_tile_sprite(tileset: &Tileset<TileMeta>, tile: TileKey) -> Sprite {
   Sprite::new(tileset.texture, src)
 }
 
+/// List the assets of the collectable modal
+pub fn manifest() -> AssetManifest {
+  AssetManifest::default().with_texture(MODAL_BACKGROUND)
+}
+
 /// Create a modal to display collectable information
 pub fn make_collectable_modal(world: &mut World, events: &mut EventStore, asset: &mut AssetManager, state: &mut State, data: &CollectableItemData) {
   let CollectableItemData { name, key, .. } = data;
   let background = asset.texture.load(MODAL_BACKGROUND).expect("Failed to load collectable modal background");
-  let (aligner, mut builder) = make_modal(world, events, asset, name.clone(), MODAL_SIZE, background);
+  let (aligner, mut builder) = make_modal(world, events, asset, state, name.clone(), MODAL_SIZE, background);
+  use_music(state).play_stinger(Stinger::Collect);
 
-  let tileset = use_tileset(state);
+  let tileset = use_tileset(state, &data.tileset);
   let icon = make_tile_sprite(tileset, data.tile);
   let icon_position = aligner.align(Alignment::new(Align::Center(0.0), Align::Start(ICON_TOP_OFFSET)), icon.src.size);
   world.add((icon, Position::from(icon_position), Sticky2::default(), Modal));
 
-  let description_lines = split_text(&data.description, DESCRIPTION_LINE_LENGTH);
-  let mut accumulated_duration = Duration::from_millis(0);
-
-  for (index, line) in description_lines.iter().enumerate() {
-    let y = DESCRIPTION_TOP_OFFSET + index as f32 * DESCRIPTION_LINE_HEIGHT;
-    let line_alignment = Alignment::new(Align::Start(MODAL_MARGIN), Align::Start(y));
-    let line_entity = world.add(builder.make_text::<Modal>(line, line_alignment));
-    let line_duration = CHAR_ITERATION_MS * line.len() as u32;
-    IterativeTextBuilder::build(world, line_entity)
-      .expect("Failed to build iterative text")
-      .with_duration(CHAR_ITERATION_MS)
-      .with_delay(accumulated_duration)
-      .start()
-      .expect("Failed to start iterative text");
-    accumulated_duration += line_duration + CHAR_ITERATION_MS;
-  };
+  let layout = TextLayout::new(DESCRIPTION_WIDTH, Justify::Left).with_line_height(DESCRIPTION_LINE_HEIGHT);
+  let description_alignment = Alignment::new(Align::Start(MODAL_MARGIN), Align::Start(DESCRIPTION_TOP_OFFSET));
+  let description = world.add(builder.make_paragraph::<Modal>(&data.description, description_alignment, layout));
+  IterativeTextBuilder::build(world, description)
+    .expect("Failed to build iterative text")
+    .with_duration(CHAR_ITERATION_MS)
+    .start()
+    .expect("Failed to start iterative text");
 
   if let Some(key) = key {
     let key_text = format!("Press {}", key);
@@ -4,20 +4,24 @@
  */
 
 use hecs::{Component, Entity, Or};
+use crate::engine::component::animator::Animator;
 use crate::engine::component::position::Position;
 use crate::engine::ecs::system::{SysArgs, Systemize};
 use crate::engine::ecs::world::World;
 use crate::engine::utility::state::State;
 use crate::engine::utility::time::ConsumeAction;
+use crate::game::scene::level::animator::TRIGGER_HIT;
 use crate::game::scene::level::combat::health::{Health, LiveState};
 use crate::game::scene::level::physics::collision::{Collider, make_collision_box};
 use crate::game::scene::level::physics::frozen::{freeze_entity, Frozen};
 use crate::game::scene::level::player::combat::{CreatureHostile, IceBeam, PlayerHostile, THAW_DURATION};
+use crate::game::scene::level::player::controller::PlayerController;
 use crate::game::scene::level::player::world::{PlayerQuery, use_player};
 use crate::game::scene::level::room::collision::{CollisionBox, CollisionMask, rec2_collision};
 use crate::game::scene::level::room::room::use_room;
 use crate::game::scene::level::tile::tile::TileCollider;
 use crate::game::scene::level::tile::tilemap::TilemapMutation;
+use crate::game::sound::{SoundEvent, use_sounds};
 
 pub struct Damage {
   pub amount: u32,
@@ -35,15 +39,17 @@ fn get_damage<Mask>(world: &mut World, collision_box: &CollisionBox) -> Option<(
     .with::<&Mask>()
     .without::<&Frozen>() // frozen entities cannot deal damage
   {
-    let (collider, mask) = match collider {
-      Or::Left(collider) => (collider.collision_box, collider.mask),
-      Or::Right(collider) => (collider.0, CollisionMask::default()),
+    let (colliders, mask) = match collider {
+      Or::Left(collider) => (collider.collision_boxes.as_slice(), collider.mask),
+      Or::Right(collider) => (std::slice::from_ref(&collider.0), CollisionMask::default()),
       _ => panic!("Cannot have both tile collider and collider")
     };
 
-    let creature_box = CollisionBox::new(collider.origin + position.0, collider.size);
-    if rec2_collision(collision_box, &creature_box, mask).is_some() {
-      return Some((damage.amount, entity));
+    for collider in colliders {
+      let creature_box = CollisionBox::new(collider.origin + position.0, collider.size);
+      if rec2_collision(collision_box, &creature_box, mask).is_some() {
+        return Some((damage.amount, entity));
+      }
     }
   }
   None
@@ -52,13 +58,13 @@ fn get_damage<Mask>(world: &mut World, collision_box: &CollisionBox) -> Option<(
 impl Systemize for Damage {
   /// Process damage each frame
   fn system(SysArgs { world, state, .. }: &mut SysArgs) -> Result<(), String> {
-    player_damage(world)?;
+    player_damage(world, state)?;
     creature_damage(world, state)
   }
 }
 
 // Damage the player when colliding with dangerous entities
-pub fn player_damage(world: &mut World) -> Result<(), String> {
+pub fn player_damage(world: &mut World, state: &mut State) -> Result<(), String> {
   let PlayerQuery { position, collider, .. } = use_player(world);
   let player_box = CollisionBox::new(position.0, collider.0.size);
 
@@ -67,6 +73,10 @@ pub fn player_damage(world: &mut World) -> Result<(), String> {
     let PlayerQuery { combat, health, .. } = use_player(world);
     if combat.hit_cooldown.consume_map(ConsumeAction::Restart, || { health.deal(damage); }) {
       combat.hit_cooldown.reset();
+      use_sounds(state).play(SoundEvent::PlayerHurt);
+      for (_, animator) in world.query::<&mut Animator>().with::<&PlayerController>() {
+        animator.trigger(TRIGGER_HIT);
+      }
     }
   }
 
@@ -86,6 +96,7 @@ pub fn creature_damage(world: &mut World, state: &mut State) -> Result<(), Strin
 
   if creatures.is_empty() { return Ok(()); }
 
+  let mut sounds = Vec::new();
   let dead_creatures = creatures
     .iter()
     .filter_map(|(creature, creature_position, creature_collider)| {
@@ -99,15 +110,21 @@ pub fn creature_damage(world: &mut World, state: &mut State) -> Result<(), Strin
 
         if frosty_projectile {
           if freeze_entity(*creature, creature_collider.0, world, THAW_DURATION).expect("Failed to freeze entity") {
-            // freeze sfx
+            sounds.push(SoundEvent::CreatureFreeze);
           } else {
-            // invalid sfx
+            sounds.push(SoundEvent::FreezeInvalid);
           }
         } else if !creature_frozen {
           let mut health = world
             .get_component_mut::<Health>(*creature)
             .expect("Creature should have health");
-          if health.deal(damage) == LiveState::Dead { return Some(*creature); }
+          if health.deal(damage) == LiveState::Dead {
+            sounds.push(SoundEvent::CreatureDeath);
+            return Some(*creature);
+          }
+          drop(health);
+          sounds.push(SoundEvent::CreatureHit);
+          if let Ok(mut animator) = world.get_component_mut::<Animator>(*creature) { animator.trigger(TRIGGER_HIT); }
         }
       }
       return None;
@@ -119,5 +136,8 @@ pub fn creature_damage(world: &mut World, state: &mut State) -> Result<(), Strin
     room.remove_entity(entity, world, TilemapMutation::Session)?; // creatures stay dead during the session
   }
 
+  let bank = use_sounds(state);
+  for sound in sounds { bank.play(sound); }
+
   Ok(())
 }
\ No newline at end of file
@@ -5,23 +5,25 @@
 use std::path::Path;
 use std::time::Duration;
 
-use hecs::DynamicBundle;
+use hecs::{DynamicBundle, Entity};
+use serde::Deserialize;
+
 use crate::engine::asset::asset::AssetManager;
-use crate::engine::asset::texture::TextureKey;
+use crate::engine::asset::manifest::AssetManifest;
+use crate::engine::component::animator::{Animator, Condition, Transition};
 use crate::engine::component::position::Position;
 use crate::engine::component::sprite::Sprite;
 use crate::engine::ecs::system::{SysArgs, Systemize};
-use crate::engine::geometry::shape::{Rec2, Vec2};
+use crate::engine::geometry::shape::Vec2;
 use crate::engine::utility::alias::Size2;
 use crate::engine::utility::color::color;
 use crate::engine::utility::time::{ConsumeAction, Timer};
-use crate::game::preferences::use_preferences;
 
 use crate::game::scene::level::combat::damage::Damage;
 use crate::game::scene::level::combat::health::Health;
 use crate::game::scene::level::combat::ttl::TimeToLive;
 use crate::game::scene::level::creature::buzz::BuzzState;
-use crate::game::scene::level::creature::CreatureLayer;
+use crate::game::scene::level::creature::{CreatureLayer, IDLE_FRAME};
 use crate::game::scene::level::physics::collision::{Collider, Fragile, make_collision_box};
 use crate::game::scene::level::physics::frozen::Frozen;
 use crate::game::scene::level::physics::gravity::Gravity;
@@ -29,16 +31,23 @@ use crate::game::scene::level::physics::velocity::Velocity;
 use crate::game::scene::level::player::combat::PlayerHostile;
 use crate::game::scene::level::player::world::{PlayerQuery, use_player};
 use crate::game::scene::level::room::collision::{CollisionBox, RoomCollision};
+use crate::game::scene::level::room::object::{ObjectClass, ObjectData, SpawnArgs};
+use crate::game::utility::debug::DEBUG_CREATURE;
 
 const SPEED: f32 = 64.0;
-const ASSET: &str = "asset/sprite/angry_buzz.png";
+const ASSET: &str = "asset/sprite/angry_buzz.json";
 const HEALTH: u32 = 80;
 const DAMAGE: u32 = 12;
 const DIMENSIONS: Size2 = Size2::new(24, 24);
 
+const STATE_IDLE: &str = "idle";
+const STATE_ATTACK: &str = "attack";
+const TRIGGER_ATTACK: &str = "attack";
+const EVENT_SPIT: &str = "spit";
+
 const SPIT_COOLDOWN: u64 = 2_000;
 const SPIT_DAMAGE: u32 = 10;
-const SPIT_ASSET: &str = "asset/sprite/angry_buzz_spit.png";
+const SPIT_ASSET: &str = "asset/sprite/angry_buzz_spit.json";
 const SPIT_DIMENSIONS: Size2 = Size2::new(8, 8);
 const SPIT_DURATION_MS: u64 = 2_000;
 const SPIT_GRAVITY: Vec2<f32> = Vec2::new(0.0, 96.0);
@@ -51,50 +60,51 @@ pub struct AngryBuzz {
   state: AngryBuzzState,
   #[allow(dead_code)]
   spit_cooldown: Timer,
-  spit_asset: TextureKey,
+  spit_sprite: Sprite,
 }
 
 impl AngryBuzz {
-  fn new(asset_manager: &mut AssetManager) -> Self {
-    Self {
+  fn build(asset_manager: &mut AssetManager) -> Result<Self, String> {
+    let spit = asset_manager.sheet.load(Path::new(SPIT_ASSET), &mut asset_manager.texture)?;
+    Ok(Self {
       state: AngryBuzzState::Idle,
       spit_cooldown: Timer::new(Duration::from_millis(SPIT_COOLDOWN), true),
-      spit_asset: asset_manager.texture.load(Path::new(SPIT_ASSET)).unwrap(),
-    }
+      spit_sprite: Sprite::from_frame(asset_manager.sheet.get(spit)?, IDLE_FRAME)?,
+    })
   }
 }
 
 /// Buzz system
 impl Systemize for AngryBuzz {
   /// Process Buzz logic each frame
-  fn system(SysArgs { state, camera, render, world, .. }: &mut SysArgs) -> Result<(), String> {
+  fn system(SysArgs { world, debug, .. }: &mut SysArgs) -> Result<(), String> {
     let PlayerQuery { position: player_position, collider, .. } = use_player(world);
-    let debug = use_preferences(state).debug;
     let player_centroid = make_collision_box(player_position, collider).centroid();
 
     let spits =
       world
-        .query::<(&mut AngryBuzz, &Position, &mut Velocity, &Collider)>()
+        .query::<(&mut AngryBuzz, &mut Animator, &Position, &mut Velocity, &Collider)>()
         .without::<&Frozen>()
         .into_iter()
-        .filter_map(|(_, (angry_buzz, angry_buzz_position, angry_buzz_velocity, angry_buzz_collider))| {
+        .filter_map(|(_, (angry_buzz, animator, angry_buzz_position, angry_buzz_velocity, angry_buzz_collider))| {
           let angry_buzz_centroid = make_collision_box(angry_buzz_position, angry_buzz_collider).centroid();
           let unit_transform = (player_centroid - angry_buzz_centroid).normalize();
           if angry_buzz.state.update(angry_buzz_centroid, player_centroid) == AngryBuzzState::Follow {
             angry_buzz_velocity.0 = unit_transform * SPEED;
-            if debug {
-              render.draw_line(camera.translate(angry_buzz_centroid), camera.translate(player_centroid), color::PRIMARY);
-            }
-
-            if angry_buzz.spit_cooldown.consume(ConsumeAction::Restart) {
-              let spit_unit_transform = Vec2::new(unit_transform.x, 0.0);
-              return Some(make_spit(angry_buzz_centroid, angry_buzz.spit_asset, spit_unit_transform.to_degrees()));
-            }
+            debug.line(DEBUG_CREATURE, angry_buzz_centroid, player_centroid, color::PRIMARY);
+
+            // tell the player an attack is coming, then spit once the timed attack clip completes
+            if angry_buzz.spit_cooldown.consume(ConsumeAction::Restart) { animator.trigger(TRIGGER_ATTACK); }
           } else {
             // todo: implement idle behavior
             angry_buzz_velocity.0 = Vec2::default();
           }
 
+          if animator.take_event(EVENT_SPIT) {
+            let spit_unit_transform = Vec2::new(unit_transform.x, 0.0);
+            return Some(make_spit(angry_buzz_centroid, angry_buzz.spit_sprite, spit_unit_transform.to_degrees()));
+          }
+
           None
         })
         .collect::<Vec<_>>();
@@ -107,13 +117,37 @@ impl Systemize for AngryBuzz {
   }
 }
 
+/// Properties of an AngryBuzz object in Tiled
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct AngryBuzzObject {}
+
+impl ObjectClass for AngryBuzzObject {
+  const CLASS: &'static str = "AngryBuzz";
+  fn manifest() -> AssetManifest { manifest() }
+  fn spawn(&self, object: &ObjectData, SpawnArgs { world, assets, .. }: &mut SpawnArgs) -> Result<Option<Entity>, String> {
+    Ok(Some(world.add(make_angry_buzz(assets, object.position)?)))
+  }
+}
+
+/// List the assets of an AngryBuzz
+pub fn manifest() -> AssetManifest {
+  AssetManifest::default().with_sheet(ASSET).with_sheet(SPIT_ASSET)
+}
+
 /// Compose the components for an AngryBuzz
 pub fn make_angry_buzz(asset_manager: &mut AssetManager, position: Vec2<f32>) -> Result<impl DynamicBundle, String> {
-  let buzz = asset_manager.texture.load(Path::new(ASSET))?;
+  let buzz = asset_manager.sheet.load(Path::new(ASSET), &mut asset_manager.texture)?;
+  let angry_buzz = AngryBuzz::build(asset_manager)?;
+  let sheet = asset_manager.sheet.get(buzz)?;
+  let animator = Animator::build(sheet, STATE_IDLE)?
+    .with_transition(Transition::to(STATE_ATTACK).when(Condition::Triggered(String::from(TRIGGER_ATTACK))))?
+    .with_completion(STATE_ATTACK, EVENT_SPIT)?;
   Ok((
     PlayerHostile,
-    AngryBuzz::new(asset_manager),
-    Sprite::new(buzz, Rec2::new(Vec2::default(), DIMENSIONS)),
+    angry_buzz,
+    Sprite::from_frame(sheet, IDLE_FRAME)?,
+    animator,
     Position::from(position),
     Velocity::default(),
     Collider::new(CollisionBox::new(Vec2::default(), DIMENSIONS)),
@@ -125,12 +159,12 @@ pub fn make_angry_buzz(asset_manager: &mut AssetManager, position: Vec2<f32>) ->
 }
 
 /// Compose the components for a Buzz's spit
-pub fn make_spit(position: Vec2<f32>, spit_texture: TextureKey, angle: f32) -> impl DynamicBundle {
+pub fn make_spit(position: Vec2<f32>, sprite: Sprite, angle: f32) -> impl DynamicBundle {
   (
     PlayerHostile,
     Position::from(position),
     Velocity::from(Vec2::from_degrees(angle) * SPIT_SPEED),
-    Sprite::new(spit_texture, Rec2::new(Vec2::default(), SPIT_DIMENSIONS)),
+    sprite,
     Collider::new(CollisionBox::new(Vec2::default(), SPIT_DIMENSIONS)),
     CreatureLayer::default(),
     Damage::new(SPIT_DAMAGE),
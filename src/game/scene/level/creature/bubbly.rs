@@ -4,27 +4,30 @@
 
 use std::path::Path;
 
-use hecs::DynamicBundle;
+use hecs::{DynamicBundle, Entity};
+use serde::Deserialize;
 
 use crate::engine::asset::asset::AssetManager;
+use crate::engine::asset::manifest::AssetManifest;
 use crate::engine::component::position::Position;
 use crate::engine::component::sprite::Sprite;
 use crate::engine::ecs::system::{SysArgs, Systemize};
-use crate::engine::geometry::shape::{Rec2, Vec2};
+use crate::engine::geometry::shape::Vec2;
 use crate::engine::utility::alias::Size2;
 use crate::engine::utility::direction::Direction;
 use crate::game::scene::level::combat::damage::Damage;
 use crate::game::scene::level::combat::health::Health;
-use crate::game::scene::level::creature::CreatureLayer;
+use crate::game::scene::level::creature::{CreatureLayer, IDLE_FRAME};
 use crate::game::scene::level::physics::collision::Collider;
 use crate::game::scene::level::physics::frozen::Frozen;
 use crate::game::scene::level::physics::velocity::Velocity;
 use crate::game::scene::level::player::combat::PlayerHostile;
 use crate::game::scene::level::room::collision::{CollisionBox, RoomCollision};
+use crate::game::scene::level::room::object::{ObjectClass, ObjectData, SpawnArgs};
 use crate::game::utility::math::floor_to_tile;
 
 const SPEED: f32 = 40.0;
-const ASSET: &str = "asset/sprite/bubbly.png";
+const ASSET: &str = "asset/sprite/bubbly.json";
 const HEALTH: u32 = 30;
 const DAMAGE: u32 = 15;
 const DIMENSIONS: Size2 = Size2::new(16, 16);
@@ -47,15 +50,39 @@ impl Bubbly {
   }
 }
 
+/// Properties of a Bubbly object in Tiled
+#[derive(Deserialize, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct BubblyObject {
+  pub direction: Direction,
+}
+
+impl Default for BubblyObject {
+  fn default() -> Self { Self { direction: Direction::UpRight } }
+}
+
+impl ObjectClass for BubblyObject {
+  const CLASS: &'static str = "Bubbly";
+  fn manifest() -> AssetManifest { manifest() }
+  fn spawn(&self, object: &ObjectData, SpawnArgs { world, assets, .. }: &mut SpawnArgs) -> Result<Option<Entity>, String> {
+    Ok(Some(world.add(make_bubbly(assets, object.position, self.direction)?)))
+  }
+}
+
+/// List the assets of a Bubbly
+pub fn manifest() -> AssetManifest {
+  AssetManifest::default().with_sheet(ASSET)
+}
+
 /// Add a Bubbly to the world
 pub fn make_bubbly(asset_manager: &mut AssetManager, position: Vec2<f32>, direction: Direction) -> Result<impl DynamicBundle, String> {
-  let creature = asset_manager.texture.load(Path::new(ASSET))?;
+  let creature = asset_manager.sheet.load(Path::new(ASSET), &mut asset_manager.texture)?;
   let velocity = Velocity::from(Vec2::from(direction.to_coordinate()) * SPEED);
   let floored_position = floor_to_tile(position);
   Ok((
     PlayerHostile,
     Bubbly::new(velocity),
-    Sprite::new(creature, Rec2::new(Vec2::default(), DIMENSIONS)),
+    Sprite::from_frame(asset_manager.sheet.get(creature)?, IDLE_FRAME)?,
     Position::from(floored_position),
     velocity,
     Collider::new(CollisionBox::new(Vec2::default(), DIMENSIONS)),
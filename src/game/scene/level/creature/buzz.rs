@@ -5,27 +5,31 @@
 
 use std::path::Path;
 
-use hecs::DynamicBundle;
+use hecs::{DynamicBundle, Entity};
+use serde::Deserialize;
+
 use crate::engine::asset::asset::AssetManager;
+use crate::engine::asset::manifest::AssetManifest;
 use crate::engine::component::position::Position;
 use crate::engine::component::sprite::Sprite;
 use crate::engine::ecs::system::{SysArgs, Systemize};
-use crate::engine::geometry::shape::{Rec2, Vec2};
+use crate::engine::geometry::shape::Vec2;
 use crate::engine::utility::alias::Size2;
 use crate::engine::utility::color::color;
-use crate::game::preferences::use_preferences;
 use crate::game::scene::level::combat::damage::Damage;
 use crate::game::scene::level::combat::health::Health;
-use crate::game::scene::level::creature::CreatureLayer;
+use crate::game::scene::level::creature::{CreatureLayer, IDLE_FRAME};
 use crate::game::scene::level::physics::collision::{Collider, make_collision_box};
 use crate::game::scene::level::physics::frozen::Frozen;
 use crate::game::scene::level::physics::velocity::Velocity;
 use crate::game::scene::level::player::combat::PlayerHostile;
 use crate::game::scene::level::player::world::{PlayerQuery, use_player};
 use crate::game::scene::level::room::collision::{CollisionBox, RoomCollision};
+use crate::game::scene::level::room::object::{ObjectClass, ObjectData, SpawnArgs};
+use crate::game::utility::debug::DEBUG_CREATURE;
 
 const BUZZ_SPEED: f32 = 96.0;
-const BUZZ_ASSET: &str = "asset/sprite/buzz.png";
+const BUZZ_ASSET: &str = "asset/sprite/buzz.json";
 const BUZZ_HEALTH: u32 = 20;
 const BUZZ_DAMAGE: u32 = 8;
 const DIMENSIONS: Size2 = Size2::new(8, 8);
@@ -62,13 +66,31 @@ impl BuzzState {
 #[derive(Default)]
 pub struct Buzz(pub BuzzState);
 
+/// Properties of a Buzz object in Tiled
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct BuzzObject {}
+
+impl ObjectClass for BuzzObject {
+  const CLASS: &'static str = "Buzz";
+  fn manifest() -> AssetManifest { manifest() }
+  fn spawn(&self, object: &ObjectData, SpawnArgs { world, assets, .. }: &mut SpawnArgs) -> Result<Option<Entity>, String> {
+    Ok(Some(world.add(make_buzz(assets, object.position)?)))
+  }
+}
+
+/// List the assets of a Buzz
+pub fn manifest() -> AssetManifest {
+  AssetManifest::default().with_sheet(BUZZ_ASSET)
+}
+
 /// Add a Buzz to the world
 pub fn make_buzz(asset_manager: &mut AssetManager, position: Vec2<f32>) -> Result<impl DynamicBundle, String> {
-  let buzz = asset_manager.texture.load(Path::new(BUZZ_ASSET))?;
+  let buzz = asset_manager.sheet.load(Path::new(BUZZ_ASSET), &mut asset_manager.texture)?;
   Ok((
     PlayerHostile,
     Buzz::default(),
-    Sprite::new(buzz, Rec2::new(Vec2::default(), DIMENSIONS)),
+    Sprite::from_frame(asset_manager.sheet.get(buzz)?, IDLE_FRAME)?,
     Position::from(position),
     Velocity::default(),
     Collider::new(CollisionBox::new(Vec2::default(), DIMENSIONS)),
@@ -81,9 +103,8 @@ pub fn make_buzz(asset_manager: &mut AssetManager, position: Vec2<f32>) -> Resul
 
 impl Systemize for Buzz {
   /// Process Buzz logic each frame
-  fn system(SysArgs { state, camera, render, world, .. }: &mut SysArgs) -> Result<(), String> {
+  fn system(SysArgs { world, debug, .. }: &mut SysArgs) -> Result<(), String> {
     let PlayerQuery { position: player_position, collider, .. } = use_player(world);
-    let debug = use_preferences(state).debug;
     let player_centroid = make_collision_box(player_position, collider).centroid();
 
     for (_, (buzz, buzz_position, buzz_velocity, collider)) in world
@@ -94,9 +115,7 @@ impl Systemize for Buzz {
       let unit_transform = (player_centroid - buzz_centroid).normalize();
       if buzz.0.update(buzz_centroid, player_centroid) == BuzzState::Follow {
         buzz_velocity.0 = unit_transform * BUZZ_SPEED;
-        if debug {
-          render.draw_line(camera.translate(buzz_centroid), camera.translate(player_centroid), color::PRIMARY);
-        }
+        debug.line(DEBUG_CREATURE, buzz_centroid, player_centroid, color::PRIMARY);
       } else {
         buzz_velocity.0 = Vec2::default();
       }
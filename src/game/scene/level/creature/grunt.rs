@@ -5,21 +5,23 @@
 use std::path::Path;
 use std::time::Duration;
 
-use hecs::DynamicBundle;
+use hecs::{DynamicBundle, Entity};
+use serde::Deserialize;
+
 use crate::engine::asset::asset::AssetManager;
+use crate::engine::asset::manifest::AssetManifest;
 use crate::engine::component::position::Position;
 use crate::engine::component::sprite::Sprite;
 use crate::engine::ecs::system::{SysArgs, Systemize};
-use crate::engine::geometry::shape::{Rec2, Vec2};
+use crate::engine::geometry::shape::Vec2;
 use crate::engine::math::random::random;
 use crate::engine::utility::alias::Size2;
 use crate::engine::utility::color::color;
 use crate::engine::utility::direction::Direction;
 use crate::engine::utility::time::Timer;
-use crate::game::preferences::use_preferences;
 use crate::game::scene::level::combat::damage::Damage;
 use crate::game::scene::level::combat::health::Health;
-use crate::game::scene::level::creature::CreatureLayer;
+use crate::game::scene::level::creature::{CreatureLayer, IDLE_FRAME};
 use crate::game::scene::level::physics::collision::{Collider, make_collision_box};
 use crate::game::scene::level::physics::frozen::Frozen;
 use crate::game::scene::level::physics::gravity::Gravity;
@@ -27,13 +29,15 @@ use crate::game::scene::level::physics::velocity::Velocity;
 use crate::game::scene::level::player::combat::PlayerHostile;
 use crate::game::scene::level::player::world::{PlayerQuery, use_player};
 use crate::game::scene::level::room::collision::{CollisionBox, RoomCollision};
+use crate::game::scene::level::room::object::{ObjectClass, ObjectData, SpawnArgs};
+use crate::game::utility::debug::DEBUG_CREATURE;
 
 const GRUNT_IDLE_SPEED: f32 = 64.0;
 const GRUNT_CHARGE_SPEED: f32 = 192.0;
 
 const GRUNT_GRAVITY: Vec2<f32> = Vec2::new(0.0, 256.0);
 
-const GRUNT_ASSET: &str = "asset/sprite/grunt.png";
+const GRUNT_ASSET: &str = "asset/sprite/grunt.json";
 const GRUNT_HEALTH: u32 = 128;
 const GRUNT_DAMAGE_IDLE: u32 = 15;
 const GRUNT_DAMAGE_CHARGE: u32 = 25;
@@ -116,9 +120,8 @@ pub struct Grunt(pub GruntState);
 
 impl Systemize for Grunt {
   /// Process Grunt logic each frame
-  fn system(SysArgs { state, camera, render, world, .. }: &mut SysArgs) -> Result<(), String> {
+  fn system(SysArgs { world, debug, .. }: &mut SysArgs) -> Result<(), String> {
     let PlayerQuery { position: player_position, collider: player_collider, .. } = use_player(world);
-    let debug = use_preferences(state).debug;
     let player_centroid = make_collision_box(player_position, player_collider).centroid();
     for (_, (grunt, grunt_position, grunt_damage, grunt_velocity, grunt_collider)) in world
       .query::<(&mut Grunt, &Position, &mut Damage, &mut Velocity, &Collider)>()
@@ -132,7 +135,7 @@ impl Systemize for Grunt {
       let (direction, speed, damage) = match next_state {
         GruntState::Idle { direction, .. } => (direction, GRUNT_IDLE_SPEED, GRUNT_DAMAGE_IDLE),
         GruntState::Charge { direction, .. } => {
-          if debug { render.draw_line(camera.translate(grunt_centroid), camera.translate(player_centroid), color::PRIMARY); }
+          debug.arrow(DEBUG_CREATURE, grunt_centroid, player_centroid, color::PRIMARY);
           (Some(direction), GRUNT_CHARGE_SPEED, GRUNT_DAMAGE_CHARGE)
         }
       };
@@ -150,13 +153,31 @@ impl Systemize for Grunt {
   }
 }
 
+/// Properties of a Grunt object in Tiled
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct GruntObject {}
+
+impl ObjectClass for GruntObject {
+  const CLASS: &'static str = "Grunt";
+  fn manifest() -> AssetManifest { manifest() }
+  fn spawn(&self, object: &ObjectData, SpawnArgs { world, assets, .. }: &mut SpawnArgs) -> Result<Option<Entity>, String> {
+    Ok(Some(world.add(make_grunt(assets, object.position)?)))
+  }
+}
+
+/// List the assets of a Grunt
+pub fn manifest() -> AssetManifest {
+  AssetManifest::default().with_sheet(GRUNT_ASSET)
+}
+
 /// Compose the components for a Grunt
 pub fn make_grunt(asset_manager: &mut AssetManager, position: Vec2<f32>) -> Result<impl DynamicBundle, String> {
-  let grunt = asset_manager.texture.load(Path::new(GRUNT_ASSET))?;
+  let grunt = asset_manager.sheet.load(Path::new(GRUNT_ASSET), &mut asset_manager.texture)?;
   Ok((
     PlayerHostile,
     Grunt::default(),
-    Sprite::new(grunt, Rec2::new(Vec2::default(), GRUNT_DIMENSIONS)),
+    Sprite::from_frame(asset_manager.sheet.get(grunt)?, IDLE_FRAME)?,
     Position::from(position),
     Velocity::default(),
     Collider::new(CollisionBox::new(Vec2::default(), GRUNT_DIMENSIONS)),
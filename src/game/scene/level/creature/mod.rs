@@ -10,4 +10,7 @@ pub mod spore;
 pub mod bubbly;
 pub mod rotund;
 
-pub type CreatureLayer = layer::Layer4;
\ No newline at end of file
+pub type CreatureLayer = layer::Layer4;
+
+/// Name of the frame of a creature sheet shown while it has no animation
+pub const IDLE_FRAME: &str = "idle";
@@ -4,27 +4,30 @@
 
 use std::path::Path;
 
-use hecs::DynamicBundle;
+use hecs::{DynamicBundle, Entity};
+use serde::Deserialize;
 
 use crate::engine::asset::asset::AssetManager;
+use crate::engine::asset::manifest::AssetManifest;
 use crate::engine::component::position::Position;
 use crate::engine::component::sprite::Sprite;
 use crate::engine::ecs::system::{SysArgs, Systemize};
-use crate::engine::geometry::shape::{Rec2, Vec2};
+use crate::engine::geometry::shape::Vec2;
 use crate::engine::utility::alias::Size2;
 use crate::engine::utility::direction::Direction;
 use crate::game::scene::level::combat::damage::Damage;
 use crate::game::scene::level::combat::health::Health;
-use crate::game::scene::level::creature::CreatureLayer;
+use crate::game::scene::level::creature::{CreatureLayer, IDLE_FRAME};
 use crate::game::scene::level::physics::collision::Collider;
 use crate::game::scene::level::physics::frozen::Frozen;
 use crate::game::scene::level::physics::velocity::Velocity;
 use crate::game::scene::level::player::combat::PlayerHostile;
 use crate::game::scene::level::room::collision::{CollisionBox, RoomCollision};
+use crate::game::scene::level::room::object::{ObjectClass, ObjectData, SpawnArgs};
 use crate::game::utility::math::floor_to_tile;
 
 const RIPPER_SPEED: f32 = 64.0;
-const RIPPER_ASSET: &str = "asset/sprite/ripper.png";
+const RIPPER_ASSET: &str = "asset/sprite/ripper.json";
 const RIPPER_HEALTH: u32 = 30;
 const RIPPER_DAMAGE: u32 = 10;
 const DIMENSIONS: Size2 = Size2::new(16, 8);
@@ -46,13 +49,37 @@ impl Systemize for Ripper {
   }
 }
 
+/// Properties of a Ripper object in Tiled
+#[derive(Deserialize, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct RipperObject {
+  pub direction: Direction,
+}
+
+impl Default for RipperObject {
+  fn default() -> Self { Self { direction: Direction::Right } }
+}
+
+impl ObjectClass for RipperObject {
+  const CLASS: &'static str = "Ripper";
+  fn manifest() -> AssetManifest { manifest() }
+  fn spawn(&self, object: &ObjectData, SpawnArgs { world, assets, .. }: &mut SpawnArgs) -> Result<Option<Entity>, String> {
+    Ok(Some(world.add(make_ripper(assets, object.position, self.direction)?)))
+  }
+}
+
+/// List the assets of a Ripper
+pub fn manifest() -> AssetManifest {
+  AssetManifest::default().with_sheet(RIPPER_ASSET)
+}
+
 /// Add a Ripper to the world
 pub fn make_ripper(asset_manager: &mut AssetManager, position: Vec2<f32>, initial_direction: Direction) -> Result<impl DynamicBundle, String> {
   if initial_direction != Direction::Left && initial_direction != Direction::Right {
     return Err(String::from("Ripper must be initialized with a horizontal direction"));
   }
 
-  let ripper = asset_manager.texture.load(Path::new(RIPPER_ASSET))?;
+  let ripper = asset_manager.sheet.load(Path::new(RIPPER_ASSET), &mut asset_manager.texture)?;
   let floored_position = floor_to_tile(position);
 
   let velocity = Vec2::from(initial_direction.to_coordinate()) * RIPPER_SPEED;
@@ -60,7 +87,7 @@ pub fn make_ripper(asset_manager: &mut AssetManager, position: Vec2<f32>, initia
   Ok((
     PlayerHostile,
     Ripper { last_velocity: velocity },
-    Sprite::new(ripper, Rec2::new(Vec2::default(), DIMENSIONS)),
+    Sprite::from_frame(asset_manager.sheet.get(ripper)?, IDLE_FRAME)?,
     Position(floored_position),
     Velocity::from(Vec2::<f32>::from(initial_direction.to_coordinate()) * RIPPER_SPEED),
     Collider::new(CollisionBox::new(Vec2::default(), DIMENSIONS)),
@@ -6,35 +6,38 @@
 use std::path::Path;
 use std::time::Duration;
 
-use hecs::DynamicBundle;
+use hecs::{DynamicBundle, Entity};
+use serde::Deserialize;
+
 use crate::engine::asset::asset::AssetManager;
-use crate::engine::asset::texture::TextureKey;
+use crate::engine::asset::manifest::AssetManifest;
 use crate::engine::component::position::Position;
 use crate::engine::component::sprite::Sprite;
 use crate::engine::ecs::system::{SysArgs, Systemize};
-use crate::engine::geometry::shape::{Rec2, Vec2};
+use crate::engine::geometry::shape::Vec2;
 use crate::engine::utility::alias::Size2;
 use crate::engine::utility::direction::{CompassDirectionType, Direction, EIGHTH_ROTATION_DEG, FULL_ROTATION_DEG, QUARTER_ROTATION_DEG};
 use crate::engine::utility::time::{ConsumeAction, Timer};
 use crate::game::scene::level::combat::damage::Damage;
 use crate::game::scene::level::combat::health::Health;
 use crate::game::scene::level::combat::ttl::TimeToLive;
-use crate::game::scene::level::creature::CreatureLayer;
+use crate::game::scene::level::creature::{CreatureLayer, IDLE_FRAME};
 use crate::game::scene::level::physics::collision::{Collider, Fragile, make_collision_box};
 use crate::game::scene::level::physics::frozen::Frozen;
 use crate::game::scene::level::physics::gravity::Gravity;
 use crate::game::scene::level::physics::velocity::Velocity;
 use crate::game::scene::level::player::combat::PlayerHostile;
 use crate::game::scene::level::room::collision::{CollisionBox, RoomCollision};
+use crate::game::scene::level::room::object::{ObjectClass, ObjectData, SpawnArgs};
 use crate::game::utility::math::floor_to_tile;
 
 const SPEED: f32 = 30.0;
-const ASSET: &str = "asset/sprite/bubbly.png";
+const ASSET: &str = "asset/sprite/bubbly.json";
 const HEALTH: u32 = 50;
 const DAMAGE: u32 = 20;
 const DIMENSIONS: Size2 = Size2::new(24, 24);
 
-const SPIT_ASSET: &str = "asset/sprite/angry_buzz_spit.png";
+const SPIT_ASSET: &str = "asset/sprite/angry_buzz_spit.json";
 const SPIT_DAMAGE: u32 = 13;
 const SPIT_COOLDOWN_MS: u64 = 3_000;
 const SPIT_SPEED: f32 = 200.0;
@@ -45,18 +48,18 @@ const SPIT_TTL: u64 = 2_000;
 // Rotund component
 pub struct Rotund {
   previous_velocity: Velocity,
-  spit_texture: TextureKey,
+  spit_sprite: Sprite,
   spit_cooldown: Timer,
   spit_axis: CompassDirectionType,
 }
 
 impl Rotund {
   /// Instantiate a new Rotund component
-  pub fn new(initial_velocity: Velocity, spit_texture: TextureKey, spit_axis: CompassDirectionType) -> Self {
+  pub fn new(initial_velocity: Velocity, spit_sprite: Sprite, spit_axis: CompassDirectionType) -> Self {
     Self {
       previous_velocity: initial_velocity,
       spit_cooldown: Timer::new(Duration::from_millis(SPIT_COOLDOWN_MS), true),
-      spit_texture,
+      spit_sprite,
       spit_axis,
     }
   }
@@ -69,16 +72,41 @@ impl Rotund {
   }
 }
 
+/// Properties of a Rotund object in Tiled
+#[derive(Deserialize, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct RotundObject {
+  pub direction: Direction,
+  pub spit_axis: CompassDirectionType,
+}
+
+impl Default for RotundObject {
+  fn default() -> Self { Self { direction: Direction::UpRight, spit_axis: CompassDirectionType::Ordinal } }
+}
+
+impl ObjectClass for RotundObject {
+  const CLASS: &'static str = "Rotund";
+  fn manifest() -> AssetManifest { manifest() }
+  fn spawn(&self, object: &ObjectData, SpawnArgs { world, assets, .. }: &mut SpawnArgs) -> Result<Option<Entity>, String> {
+    Ok(Some(world.add(make_rotund(assets, object.position, self.direction, self.spit_axis)?)))
+  }
+}
+
+/// List the assets of a Rotund
+pub fn manifest() -> AssetManifest {
+  AssetManifest::default().with_sheet(ASSET).with_sheet(SPIT_ASSET)
+}
+
 /// Add a Rotund to the world
 pub fn make_rotund(asset_manager: &mut AssetManager, position: Vec2<f32>, direction: Direction, spit_axis: CompassDirectionType) -> Result<impl DynamicBundle, String> {
-  let rotund = asset_manager.texture.load(Path::new(ASSET))?;
-  let spit = asset_manager.texture.load(Path::new(SPIT_ASSET))?;
+  let rotund = asset_manager.sheet.load(Path::new(ASSET), &mut asset_manager.texture)?;
+  let spit = asset_manager.sheet.load(Path::new(SPIT_ASSET), &mut asset_manager.texture)?;
   let velocity = Velocity::from(Vec2::from(direction.to_coordinate()) * SPEED);
   let floored_position = floor_to_tile(position);
   Ok((
     PlayerHostile,
-    Rotund::new(velocity, spit, spit_axis),
-    Sprite::new(rotund, Rec2::new(Vec2::default(), DIMENSIONS)),
+    Rotund::new(velocity, Sprite::from_frame(asset_manager.sheet.get(spit)?, IDLE_FRAME)?, spit_axis),
+    Sprite::from_frame(asset_manager.sheet.get(rotund)?, IDLE_FRAME)?,
     Position::from(floored_position),
     velocity,
     Collider::new(CollisionBox::new(Vec2::default(), DIMENSIONS)),
@@ -90,12 +118,12 @@ pub fn make_rotund(asset_manager: &mut AssetManager, position: Vec2<f32>, direct
 }
 
 /// Compose the components for a Rotund's spit
-pub fn make_spit(position: Vec2<f32>, spit_texture: TextureKey, angle: f32) -> impl DynamicBundle {
+pub fn make_spit(position: Vec2<f32>, sprite: Sprite, angle: f32) -> impl DynamicBundle {
   (
     PlayerHostile,
     Position::from(position),
     Velocity::from(Vec2::from_degrees(angle) * SPIT_SPEED),
-    Sprite::new(spit_texture, Rec2::new(Vec2::default(), SPIT_DIMENSIONS)),
+    sprite,
     Collider::new(CollisionBox::new(Vec2::default(), SPIT_DIMENSIONS)),
     Damage::new(SPIT_DAMAGE),
     CreatureLayer::default(),
@@ -127,7 +155,7 @@ impl Systemize for Rotund {
             .into_iter()
             .map(|mut angle| {
               if rotund.spit_axis == CompassDirectionType::Ordinal { angle += EIGHTH_ROTATION_DEG; };
-              make_spit(spit_position, rotund.spit_texture, angle as f32)
+              make_spit(spit_position, rotund.spit_sprite, angle as f32)
             })
             .collect::<Vec<_>>();
           return Some(spits);
@@ -1,7 +1,10 @@
 use std::path::Path;
 
-use hecs::DynamicBundle;
+use hecs::{DynamicBundle, Entity};
+use serde::Deserialize;
+
 use crate::engine::asset::asset::AssetManager;
+use crate::engine::asset::manifest::AssetManifest;
 use crate::engine::component::position::Position;
 use crate::engine::component::sprite::Sprite;
 use crate::engine::ecs::system::{SysArgs, Systemize};
@@ -10,10 +13,9 @@ use crate::engine::utility::alias::Size2;
 use crate::engine::utility::color::{OPAQUE, RGBA};
 use crate::engine::utility::direction::Direction;
 use crate::game::constant::TILE_SIZE;
-use crate::game::preferences::use_preferences;
 use crate::game::scene::level::combat::damage::Damage;
 use crate::game::scene::level::combat::health::Health;
-use crate::game::scene::level::creature::CreatureLayer;
+use crate::game::scene::level::creature::{CreatureLayer, IDLE_FRAME};
 use crate::game::scene::level::physics::collision::Collider;
 use crate::game::scene::level::physics::frozen::FreezeResistant;
 use crate::game::scene::level::physics::gravity::Gravity;
@@ -22,7 +24,9 @@ use crate::game::scene::level::player::combat::PlayerHostile;
 use crate::game::scene::level::room::collision::CollisionBox;
 use crate::game::scene::level::room::meta::TileLayerType;
 use crate::game::scene::level::room::room::use_room;
+use crate::game::scene::level::room::object::{ObjectClass, ObjectData, SpawnArgs};
 use crate::game::scene::level::tile::query::TileQuery;
+use crate::game::utility::debug::DEBUG_CREATURE;
 use crate::game::utility::math::floor_to_tile;
 
 /**
@@ -30,7 +34,7 @@ use crate::game::utility::math::floor_to_tile;
  */
 
 const SPIKY_SPEED: f32 = 48.0;
-const SPIKY_ASSET: &str = "asset/sprite/spiky.png";
+const SPIKY_ASSET: &str = "asset/sprite/spiky.json";
 const SPIKY_HEALTH: u32 = 30;
 const SPIKY_DAMAGE: u32 = 10;
 const DIMENSIONS: Size2 = Size2::new(16, 16);
@@ -42,8 +46,7 @@ pub struct Spiky;
 
 impl Systemize for Spiky {
   /// Process Spiky logic each frame
-  fn system(SysArgs { world, state, render, camera, .. }: &mut SysArgs) -> Result<(), String> {
-    let debug = use_preferences(state).debug;
+  fn system(SysArgs { world, state, debug, .. }: &mut SysArgs) -> Result<(), String> {
     let room = use_room(state);
     for (_, (velocity, position)) in world
       .query::<(&mut Velocity, &Position)>()
@@ -53,11 +56,11 @@ impl Systemize for Spiky {
       let leading_bottom_corner = leading_top_corner + HEIGHT;
 
       let result = room.query_tile(TileLayerType::Collision, TileQuery::Position(leading_top_corner));
-      if debug { render.draw_rect(Rec2::new(camera.translate(result.position), TILE_SIZE), RGBA::new(255, 128, 0, OPAQUE)); }
+      debug.rect(DEBUG_CREATURE, Rec2::new(result.position, TILE_SIZE), RGBA::new(255, 128, 0, OPAQUE));
       if result.concept.is_some() { velocity.reverse_x(); }
 
       let result = room.query_tile(TileLayerType::Collision, TileQuery::Position(leading_bottom_corner));
-      if debug { render.draw_rect(Rec2::new(camera.translate(result.position), TILE_SIZE), RGBA::new(0, 255, 128, OPAQUE)); }
+      debug.rect(DEBUG_CREATURE, Rec2::new(result.position, TILE_SIZE), RGBA::new(0, 255, 128, OPAQUE));
       if result.concept.is_none() { velocity.reverse_x(); }
     }
 
@@ -65,17 +68,41 @@ impl Systemize for Spiky {
   }
 }
 
+/// Properties of a Spiky object in Tiled
+#[derive(Deserialize, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct SpikyObject {
+  pub direction: Direction,
+}
+
+impl Default for SpikyObject {
+  fn default() -> Self { Self { direction: Direction::Right } }
+}
+
+impl ObjectClass for SpikyObject {
+  const CLASS: &'static str = "Spiky";
+  fn manifest() -> AssetManifest { manifest() }
+  fn spawn(&self, object: &ObjectData, SpawnArgs { world, assets, .. }: &mut SpawnArgs) -> Result<Option<Entity>, String> {
+    Ok(Some(world.add(make_spiky(assets, object.position, self.direction)?)))
+  }
+}
+
+/// List the assets of a Spiky
+pub fn manifest() -> AssetManifest {
+  AssetManifest::default().with_sheet(SPIKY_ASSET)
+}
+
 /// Add a Spiky to the world
 pub fn make_spiky(asset_manager: &mut AssetManager, position: Vec2<f32>, initial_direction: Direction) -> Result<impl DynamicBundle, String> {
   if initial_direction != Direction::Left && initial_direction != Direction::Right {
     return Err(String::from("Spiky must be initialized with a horizontal direction"));
   }
-  let spiky = asset_manager.texture.load(Path::new(SPIKY_ASSET))?;
+  let spiky = asset_manager.sheet.load(Path::new(SPIKY_ASSET), &mut asset_manager.texture)?;
   let floored_position = floor_to_tile(position);
   Ok((
     PlayerHostile,
     Spiky,
-    Sprite::new(spiky, Rec2::new(Vec2::default(), DIMENSIONS)),
+    Sprite::from_frame(asset_manager.sheet.get(spiky)?, IDLE_FRAME)?,
     Position(floored_position),
     Gravity::new(Vec2::new(0.0, 0.0)),
     Velocity::from(Vec2::<f32>::from(initial_direction.to_coordinate()) * SPIKY_SPEED),
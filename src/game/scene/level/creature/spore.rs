@@ -5,37 +5,40 @@
 use std::path::Path;
 use std::time::Duration;
 
-use hecs::DynamicBundle;
+use hecs::{DynamicBundle, Entity};
+use serde::Deserialize;
+
 use crate::engine::asset::asset::AssetManager;
-use crate::engine::asset::texture::TextureKey;
+use crate::engine::asset::manifest::AssetManifest;
 use crate::engine::component::position::Position;
 use crate::engine::component::sprite::Sprite;
 use crate::engine::ecs::system::{SysArgs, Systemize};
-use crate::engine::geometry::shape::{Rec2, Vec2};
+use crate::engine::geometry::shape::Vec2;
 use crate::engine::utility::alias::{Size, Size2};
 use crate::engine::utility::direction::Direction;
 use crate::engine::utility::invariant::invariant;
 use crate::engine::utility::time::{ConsumeAction, Timer};
 use crate::game::scene::level::combat::damage::Damage;
 use crate::game::scene::level::combat::health::Health;
-use crate::game::scene::level::creature::CreatureLayer;
+use crate::game::scene::level::creature::{CreatureLayer, IDLE_FRAME};
 use crate::game::scene::level::physics::collision::{Collider, Fragile};
 use crate::game::scene::level::physics::frozen::Frozen;
 use crate::game::scene::level::physics::gravity::Gravity;
 use crate::game::scene::level::physics::velocity::Velocity;
 use crate::game::scene::level::player::combat::PlayerHostile;
 use crate::game::scene::level::room::collision::{CollisionBox, RoomCollision};
+use crate::game::scene::level::room::object::{ObjectClass, ObjectData, SpawnArgs};
 use crate::game::utility::math::floor_to_tile;
 
 const QUARTER_ROTATION_DEG: f32 = 90.0;
 
-const ASSET: &str = "asset/sprite/spore.png";
+const ASSET: &str = "asset/sprite/spore.json";
 const HEALTH: u32 = 70;
 const DAMAGE: u32 = 5;
 const CELL_DAMAGE: u32 = 15;
 const DIMENSIONS: Size2 = Size2::new(16, 16);
 
-const CELL_ASSET: &str = "asset/sprite/spore_cell.png";
+const CELL_ASSET: &str = "asset/sprite/spore_cell.json";
 const CELL_DIMENSIONS: Size2 = Size2::new(6, 6);
 const CELL_GRAVITY: Vec2<f32> = Vec2::new(0.0, 256.0);
 const CELL_SPEED: f32 = 180.0;
@@ -48,14 +51,14 @@ const CELL_SPAWN_STEP_DEG: f32 = CELL_SPAWN_SPREAD_DEG / CELL_SPAWN_COUNT as f32
 pub struct Spore {
   spawn_cooldown: Timer,
   direction: Direction,
-  cell_asset: TextureKey,
+  cell_sprite: Sprite,
 }
 
 impl Spore {
   /// Instantiate a new spore
-  pub fn build(direction: Direction, cell_asset: TextureKey) -> Result<Self, String> {
+  pub fn build(direction: Direction, cell_sprite: Sprite) -> Result<Self, String> {
     invariant(direction.is_cardinal(), "Spore direction must be cardinal")?;
-    Ok(Self { direction, cell_asset, spawn_cooldown: Timer::new(Duration::from_millis(CELL_SPAWN_INTERVAL_MS), true) })
+    Ok(Self { direction, cell_sprite, spawn_cooldown: Timer::new(Duration::from_millis(CELL_SPAWN_INTERVAL_MS), true) })
   }
 }
 
@@ -74,7 +77,7 @@ impl Systemize for Spore {
         let cells = ((start_deg as i32)..(end_deg as i32))
           .step_by(CELL_SPAWN_STEP_DEG as usize)
           .map(|angle| {
-            make_spore_cell(spore.cell_asset, cell_position, angle as f32 - QUARTER_ROTATION_DEG)
+            make_spore_cell(spore.cell_sprite, cell_position, angle as f32 - QUARTER_ROTATION_DEG)
           })
           .collect::<Vec<_>>();
         cells
@@ -89,15 +92,39 @@ impl Systemize for Spore {
   }
 }
 
+/// Properties of a Spore object in Tiled
+#[derive(Deserialize, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct SporeObject {
+  pub direction: Direction,
+}
+
+impl Default for SporeObject {
+  fn default() -> Self { Self { direction: Direction::Up } }
+}
+
+impl ObjectClass for SporeObject {
+  const CLASS: &'static str = "Spore";
+  fn manifest() -> AssetManifest { manifest() }
+  fn spawn(&self, object: &ObjectData, SpawnArgs { world, assets, .. }: &mut SpawnArgs) -> Result<Option<Entity>, String> {
+    Ok(Some(world.add(make_spore(assets, object.position, self.direction)?)))
+  }
+}
+
+/// List the assets of a Spore
+pub fn manifest() -> AssetManifest {
+  AssetManifest::default().with_sheet(ASSET).with_sheet(CELL_ASSET)
+}
+
 /// Compose the components of a spore creature
 pub fn make_spore(asset_manager: &mut AssetManager, position: Vec2<f32>, direction: Direction) -> Result<impl DynamicBundle, String> {
-  let spore = asset_manager.texture.load(Path::new(ASSET))?;
-  let cell = asset_manager.texture.load(Path::new(CELL_ASSET))?;
+  let spore = asset_manager.sheet.load(Path::new(ASSET), &mut asset_manager.texture)?;
+  let cell = asset_manager.sheet.load(Path::new(CELL_ASSET), &mut asset_manager.texture)?;
   let floored_position = floor_to_tile(position);
   Ok((
     PlayerHostile,
-    Spore::build(direction, cell)?,
-    Sprite::new(spore, Rec2::new(Vec2::default(), DIMENSIONS)),
+    Spore::build(direction, Sprite::from_frame(asset_manager.sheet.get(cell)?, IDLE_FRAME)?)?,
+    Sprite::from_frame(asset_manager.sheet.get(spore)?, IDLE_FRAME)?,
     Position(floored_position),
     Collider::new(CollisionBox::new(Vec2::default(), DIMENSIONS)),
     CreatureLayer::default(),
@@ -110,11 +137,11 @@ pub fn make_spore(asset_manager: &mut AssetManager, position: Vec2<f32>, directi
 pub struct SporeCell;
 
 /// Compose the components of a spore cell
-pub fn make_spore_cell(texture: TextureKey, position: Vec2<f32>, angle: f32) -> impl DynamicBundle {
+pub fn make_spore_cell(sprite: Sprite, position: Vec2<f32>, angle: f32) -> impl DynamicBundle {
   (
     PlayerHostile,
     SporeCell,
-    Sprite::new(texture, Rec2::new(Vec2::default(), CELL_DIMENSIONS)),
+    sprite,
     Position::from(position),
     Collider::new(CollisionBox::new(Vec2::default(), CELL_DIMENSIONS)),
     Velocity::from(Vec2::from_degrees(angle) * CELL_SPEED),
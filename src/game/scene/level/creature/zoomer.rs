@@ -1,7 +1,10 @@
 use std::path::Path;
 
-use hecs::DynamicBundle;
+use hecs::{DynamicBundle, Entity};
+use serde::Deserialize;
+
 use crate::engine::asset::asset::AssetManager;
+use crate::engine::asset::manifest::AssetManifest;
 use crate::engine::component::position::Position;
 use crate::engine::component::sprite::Sprite;
 use crate::engine::ecs::system::{SysArgs, Systemize};
@@ -10,10 +13,9 @@ use crate::engine::utility::alias::{Coordinate, Size2};
 use crate::engine::utility::color::{OPAQUE, RGBA};
 use crate::engine::utility::direction::{Direction, QUARTER_DIRECTION_ROTATION, Rotation};
 use crate::game::constant::TILE_SIZE;
-use crate::game::preferences::use_preferences;
 use crate::game::scene::level::combat::damage::Damage;
 use crate::game::scene::level::combat::health::Health;
-use crate::game::scene::level::creature::CreatureLayer;
+use crate::game::scene::level::creature::{CreatureLayer, IDLE_FRAME};
 use crate::game::scene::level::physics::collision::Collider;
 use crate::game::scene::level::physics::frozen::FreezeResistant;
 use crate::game::scene::level::physics::gravity::Gravity;
@@ -22,7 +24,9 @@ use crate::game::scene::level::player::combat::PlayerHostile;
 use crate::game::scene::level::room::collision::CollisionBox;
 use crate::game::scene::level::room::meta::TileLayerType;
 use crate::game::scene::level::room::room::{Room, use_room};
+use crate::game::scene::level::room::object::{ObjectClass, ObjectData, SpawnArgs};
 use crate::game::scene::level::tile::query::{TileQuery, TileQueryResult};
+use crate::game::utility::debug::DEBUG_CREATURE;
 use crate::game::utility::math::{floor_to_tile, round_to_tile};
 
 /**
@@ -32,7 +36,7 @@ use crate::game::utility::math::{floor_to_tile, round_to_tile};
 const ZOOMER_SPEED: f32 = 48.0;
 const ZOOMER_HEALTH: u32 = 30;
 const ZOOMER_DAMAGE: u32 = 10;
-const ZOOMER_ASSET: &str = "asset/sprite/zoomer.png";
+const ZOOMER_ASSET: &str = "asset/sprite/zoomer.json";
 const DIMENSIONS: Size2 = Size2::new(16, 16);
 const SIZE: Vec2<f32> = Vec2::new(DIMENSIONS.x as f32, DIMENSIONS.y as f32);
 
@@ -46,8 +50,7 @@ pub struct Zoomer {
 
 impl Systemize for Zoomer {
   /// Process Zoomer logic each frame
-  fn system(SysArgs { world, render, state, camera, .. }: &mut SysArgs) -> Result<(), String> {
-    let debug = use_preferences(state).debug;
+  fn system(SysArgs { world, state, debug, .. }: &mut SysArgs) -> Result<(), String> {
     let room = use_room(state);
 
     for (_, (zoomer, velocity, position)) in world
@@ -56,26 +59,50 @@ impl Systemize for Zoomer {
       let direction = Direction::try_from(velocity.0).expect("Zoomer must have velocity!");
 
       let (leading_coordinate, leading_position) = compute_leading(zoomer, room, direction, position, velocity);
-      if debug { render.draw_rect(Rec2::new(camera.translate(leading_position), TILE_SIZE), RGBA::new(255, 128, 0, OPAQUE)); }
+      debug.rect(DEBUG_CREATURE, Rec2::new(leading_position, TILE_SIZE), RGBA::new(255, 128, 0, OPAQUE));
 
       let cling_position = compute_cling(zoomer, room, direction, leading_coordinate, position, velocity);
-      if debug { render.draw_rect(Rec2::new(camera.translate(cling_position), TILE_SIZE), RGBA::new(128, 255, 0, OPAQUE)); }
+      debug.rect(DEBUG_CREATURE, Rec2::new(cling_position, TILE_SIZE), RGBA::new(128, 255, 0, OPAQUE));
     }
 
     Ok(())
   }
 }
 
+/// Properties of a Zoomer object in Tiled
+#[derive(Deserialize, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct ZoomerObject {
+  pub direction: Direction,
+}
+
+impl Default for ZoomerObject {
+  fn default() -> Self { Self { direction: Direction::Right } }
+}
+
+impl ObjectClass for ZoomerObject {
+  const CLASS: &'static str = "Zoomer";
+  fn manifest() -> AssetManifest { manifest() }
+  fn spawn(&self, object: &ObjectData, SpawnArgs { world, assets, .. }: &mut SpawnArgs) -> Result<Option<Entity>, String> {
+    Ok(Some(world.add(make_zoomer(assets, object.position, self.direction)?)))
+  }
+}
+
+/// List the assets of a Zoomer
+pub fn manifest() -> AssetManifest {
+  AssetManifest::default().with_sheet(ZOOMER_ASSET)
+}
+
 /// Add a Zoomer to the world
 pub fn make_zoomer(asset_manager: &mut AssetManager, position: Vec2<f32>, initial_direction: Direction) -> Result<impl DynamicBundle, String> {
   if initial_direction.is_ordinal() { return Err(String::from("Zoomer must be initialized with an ordinal direction")); }
-  let zoomer = asset_manager.texture.load(Path::new(ZOOMER_ASSET))?;
+  let zoomer = asset_manager.sheet.load(Path::new(ZOOMER_ASSET), &mut asset_manager.texture)?;
   let floored_position = floor_to_tile(position);
 
   Ok((
     PlayerHostile,
     Zoomer { rotation: Rotation::Right, last_cling: None, last_lead: None, turning: false },
-    Sprite::new(zoomer, Rec2::new(Vec2::default(), DIMENSIONS)),
+    Sprite::from_frame(asset_manager.sheet.get(zoomer)?, IDLE_FRAME)?,
     Position(floored_position),
     Gravity::new(Vec2::new(0.0, 0.0)),
     Velocity::from(Vec2::<f32>::from(initial_direction.to_coordinate()) * ZOOMER_SPEED),
@@ -2,6 +2,8 @@ mod collectable;
 mod combat;
 mod creature;
 mod ui;
+mod animator;
+mod reload;
 
 pub mod physics;
 pub mod player;
@@ -4,11 +4,9 @@
 
 use crate::engine::component::position::Position;
 use crate::engine::ecs::system::SysArgs;
-use crate::engine::geometry::shape::{Rec2, Vec2};
-use crate::engine::utility::alias::Size;
 use crate::engine::utility::color::{OPAQUE, RGBA};
-use crate::game::preferences::use_preferences;
 use crate::game::scene::level::room::collision::CollisionBox;
+use crate::game::utility::debug::DEBUG_COLLIDER;
 
 /**
  * Collider component
@@ -30,15 +28,9 @@ impl Collider {
 }
 
 /// Render colliders in the world while debugging
-pub fn sys_render_colliders(SysArgs { world, camera, render, state, .. }: &mut SysArgs) -> Result<(), String> {
-  if !use_preferences(state).debug { return Ok(()); }
-
+pub fn sys_render_colliders(SysArgs { world, debug, .. }: &mut SysArgs) -> Result<(), String> {
   for (_, (position, collider)) in world.query::<(&Position, &Collider)>() {
-    let new_position = camera.translate(Vec2::from(position.0));
-    render.draw_rect(
-      Rec2::<i32, Size>::new(new_position, collider.0.size),
-      RGBA::new(0, 255, 0, OPAQUE),
-    );
+    debug.rect(DEBUG_COLLIDER, make_collision_box(position, collider), RGBA::new(0, 255, 0, OPAQUE));
   }
 
   Ok(())
@@ -51,7 +51,7 @@ pub fn freeze_entity(entity: Entity, collision_box: CollisionBox, world: &mut Wo
 
   world.add_components(entity, (
     Frozen::new(thaw_ms),
-    TileCollider::new(collision_box, CollisionMask::full()),
+    TileCollider::new(vec![collision_box], CollisionMask::full()),
     RoomCollision::All,
   ))?;
 
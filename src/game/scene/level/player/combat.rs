@@ -88,7 +88,7 @@ impl Systemize for PlayerCombat {
 }
 
 /// Available weapon types for the player
-#[derive(PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum Weapon { Bullet, Rocket, IceBeam }
 
 pub type ProjectileLayer = layer::Layer7;
@@ -105,8 +105,8 @@ pub struct IceBeam;
 /// Mark an entity as damaging to creatures but the player
 pub struct CreatureHostile;
 
-/// Fire a plasma projectile in the direction the player is aiming
-pub fn fire_weapon(world: &mut World, aim: Direction, weapon: Weapon) {
+/// Fire a plasma projectile in the direction the player is aiming, returning false if the weapon is cooling down
+pub fn fire_weapon(world: &mut World, aim: Direction, weapon: Weapon) -> bool {
   let PlayerQuery { combat, position, .. } = use_player(world);
   let (position, velocity, rotation) = compute_projectile_spawn(aim, position.0, PLAYER_SIZE);
 
@@ -120,14 +120,14 @@ pub fn fire_weapon(world: &mut World, aim: Direction, weapon: Weapon) {
   let mut sprite = Sprite::new(texture, SrcRect::new(Vec2::new(0, 0), sprite_dimensions));
   sprite.rotate(rotation.into(), Some(Vec2::from(PROJECTILE_DIMENSIONS / 2)));
 
-  if !combat.bullet_cooldown.done() { return; }
+  if !combat.bullet_cooldown.done() { return false; }
   combat.bullet_cooldown.reset();
 
   if weapon == Weapon::Rocket {
-    if !combat.rocket_cooldown.done() { return; }
+    if !combat.rocket_cooldown.done() { return false; }
     combat.rocket_cooldown.reset();
   } else if weapon == Weapon::IceBeam {
-    if !combat.ice_cooldown.done() { return; }
+    if !combat.ice_cooldown.done() { return false; }
     combat.ice_cooldown.reset();
   }
 
@@ -148,7 +148,8 @@ pub fn fire_weapon(world: &mut World, aim: Direction, weapon: Weapon) {
     Weapon::Bullet => world.add_components(projectile, (Bullet, )),
     Weapon::Rocket => world.add_components(projectile, (Rocket, )),
     Weapon::IceBeam => world.add_components(projectile, (IceBeam, )),
-  }.expect("Failed to add weapon components to projectile")
+  }.expect("Failed to add weapon components to projectile");
+  true
 }
 
 /// Compute the starting position and velocity and rotation of the player projectile
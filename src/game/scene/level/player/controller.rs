@@ -9,6 +9,7 @@ use crate::game::scene::level::player::combat::{fire_weapon, HEALTH_PICKUP_INCRE
 use crate::game::scene::level::player::physics::{calculate_gravity, calculate_jump_velocity, HIGH_JUMP_BOOTS_JUMP_HEIGHT, INITIAL_JUMP_HEIGHT, INITIAL_JUMP_WIDTH, INITIAL_WALK_SPEED};
 use crate::game::scene::level::player::world::{PlayerQuery, use_player};
 use crate::game::scene::level::room::meta::Collectable;
+use crate::game::sound::{SoundEvent, use_sounds};
 use crate::game::utility::controls::{Behaviour, Control, get_controls_direction, is_control};
 
 const INITIAL_DIRECTION: Direction = Direction::Right;
@@ -44,11 +45,13 @@ impl PlayerController {
   fn set_walked(&mut self, direction: Direction) { self.last_walk = direction; }
   /// Set the last direction the player aimed
   fn set_aimed(&mut self, direction: Direction) { self.last_aim = direction; }
+  /// Get the last direction the player aimed
+  pub fn get_aim(&self) -> Direction { self.last_aim }
 }
 
 impl Systemize for PlayerController {
   /// Process user controls each frame
-  fn system(SysArgs { delta, event, world, .. }: &mut SysArgs) -> Result<(), String> {
+  fn system(SysArgs { delta, event, world, state, .. }: &mut SysArgs) -> Result<(), String> {
     let PlayerQuery { health, velocity, inventory, controller, gravity, .. } = use_player(world);
     let aim = get_controls_direction(event, Behaviour::Held).unwrap_or(controller.last_aim);
 
@@ -117,9 +120,14 @@ impl Systemize for PlayerController {
     let secondary_trigger = is_control(Control::SecondaryTrigger, Behaviour::Pressed, event);
     let tertiary_trigger = is_control(Control::TertiaryTrigger, Behaviour::Pressed, event);
 
-    if primary_trigger { fire_weapon(world, aim, Weapon::Bullet); }
-    if secondary_trigger && has_rocket { fire_weapon(world, aim, Weapon::Rocket); }
-    if tertiary_trigger && has_ice_beam { fire_weapon(world, aim, Weapon::IceBeam); }
+    let fired = [
+      (primary_trigger, Weapon::Bullet),
+      (secondary_trigger && has_rocket, Weapon::Rocket),
+      (tertiary_trigger && has_ice_beam, Weapon::IceBeam),
+    ];
+    for (_, weapon) in fired.into_iter().filter(|(trigger, ..)| *trigger) {
+      if fire_weapon(world, aim, weapon) { use_sounds(state).play(SoundEvent::Fire(weapon)); }
+    }
 
     Ok(())
   }
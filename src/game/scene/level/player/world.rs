@@ -6,7 +6,9 @@ use std::collections::HashSet;
 use std::path::Path;
 
 use crate::engine::asset::asset::AssetManager;
-use crate::engine::asset::texture::SrcRect;
+use crate::engine::asset::manifest::AssetManifest;
+use crate::engine::asset::sheet::SpriteSheet;
+use crate::engine::component::animator::{Animator, Condition, Transition};
 use crate::engine::component::position::Position;
 use crate::engine::component::sprite::Sprite;
 use crate::engine::ecs::world::World;
@@ -14,6 +16,7 @@ use crate::engine::geometry::shape::{Rec2, Vec2};
 use crate::engine::render::camera::CameraTether;
 use crate::engine::render::renderer::layer;
 use crate::engine::utility::alias::Size2;
+use crate::game::scene::level::animator::{PARAM_AIM, PARAM_GROUNDED, PARAM_RISE, PARAM_SPEED, TRIGGER_HIT};
 use crate::game::scene::level::collectable::collectable::Collection;
 use crate::game::scene::level::combat::health::Health;
 use crate::game::scene::level::physics::collision::Collider;
@@ -22,15 +25,25 @@ use crate::game::scene::level::physics::velocity::Velocity;
 use crate::game::scene::level::player::combat::{PLAYER_BASE_HEALTH, PlayerCombat};
 use crate::game::scene::level::player::controller::PlayerController;
 use crate::game::scene::level::player::physics::{calculate_gravity, INITIAL_JUMP_HEIGHT, INITIAL_JUMP_WIDTH, INITIAL_WALK_SPEED};
-use crate::game::scene::level::room::collision::{CollisionBox, RoomCollision};
+use crate::game::scene::level::room::collision::{CollisionBox, Grounded, RoomCollision};
 use crate::game::scene::level::room::meta::Item;
 use crate::game::scene::level::story::data::StoryKey;
 use crate::game::scene::level::story::world::{StoryAdvancements, StoryAdvancer};
 
 pub const PLAYER_SIZE: Size2 = Size2::new(12, 28);
 
-const PLAYER_ASSET: &str = "asset/sprite/player.png";
-const PLAYER_SPRITE: SrcRect = SrcRect::new(Vec2::new(0, 0), PLAYER_SIZE);
+const PLAYER_ASSET: &str = "asset/sprite/player.json";
+const BULLET_ASSET: &str = "asset/sprite/plasma_burst.png";
+const ROCKET_ASSET: &str = "asset/sprite/desolation_pulse.png";
+const ICE_BEAM_ASSET: &str = "asset/sprite/temporal_flare.png";
+const PLAYER_FRAME: &str = "idle";
+
+const STATE_IDLE: &str = "idle";
+const STATE_RUN: &str = "run";
+const STATE_JUMP: &str = "jump";
+const STATE_FALL: &str = "fall";
+const STATE_AIM_UP: &str = "aim_up";
+const STATE_HURT: &str = "hurt";
 const PLAYER_COLLIDER: CollisionBox = Rec2::new(Vec2::new(0.0, 0.0), PLAYER_SIZE);
 
 /// Alias for the player layer
@@ -75,28 +88,41 @@ pub fn use_player(world: &mut World) -> PlayerQuery {
   }
 }
 
+/// List the assets of the player
+pub fn manifest() -> AssetManifest {
+  AssetManifest::default()
+    .with_sheet(PLAYER_ASSET)
+    .with_texture(BULLET_ASSET)
+    .with_texture(ROCKET_ASSET)
+    .with_texture(ICE_BEAM_ASSET)
+}
+
 /// Set up the world for the player
 pub fn make_player(world: &mut World, asset: &mut AssetManager, inventory: impl Iterator<Item=Item>, story: HashSet<StoryKey>, position: Vec2<f32>) {
-  let player_texture = asset.texture
-    .load(Path::new(PLAYER_ASSET))
-    .expect("Failed to load player texture");
+  let player_sheet = asset.sheet
+    .load(Path::new(PLAYER_ASSET), &mut asset.texture)
+    .expect("Failed to load player sheet");
+  let player_sheet = asset.sheet.get(player_sheet).expect("Failed to get player sheet");
+  let player_sprite = Sprite::from_frame(player_sheet, PLAYER_FRAME).expect("Failed to make player sprite");
+  let player_animator = make_player_animator(player_sheet).expect("Failed to make player animator");
   let bullet = asset.texture
-    .load(Path::new("asset/sprite/plasma_burst.png"))
+    .load(Path::new(BULLET_ASSET))
     .expect("Failed to load bullet texture");
   let rocket = asset.texture
-    .load(Path::new("asset/sprite/desolation_pulse.png"))
+    .load(Path::new(ROCKET_ASSET))
     .expect("Failed to load rocket texture");
   let ice_beam = asset.texture
-    .load(Path::new("asset/sprite/temporal_flare.png"))
+    .load(Path::new(ICE_BEAM_ASSET))
     .expect("Failed to load ice beam texture");
 
-  world.add((
+  let player = world.add((
     PlayerCombat::new(bullet, rocket, ice_beam),
     PlayerController::default(),
-    Sprite::new(player_texture, PLAYER_SPRITE.into()),
+    player_sprite,
+    player_animator,
     Position::from(position),
     LayerPlayer::default(),
-    CameraTether::new(Vec2::<i32>::from(PLAYER_SPRITE.size / 2)), // player center
+    CameraTether::new(Vec2::<i32>::from(player_sprite.src.size / 2)), // player center
     Gravity::new(calculate_gravity(INITIAL_JUMP_HEIGHT, INITIAL_WALK_SPEED, INITIAL_JUMP_WIDTH)),
     Velocity::default(),
     Collection::new(inventory),
@@ -106,5 +132,16 @@ pub fn make_player(world: &mut World, asset: &mut AssetManager, inventory: impl
     StoryAdvancer,
     StoryAdvancements::new(story),
   ));
+  world.add_components(player, (Grounded::default(), )).expect("Failed to add player ground contact");
 }
 
+/// Build the animation state machine of the player
+fn make_player_animator(sheet: &SpriteSheet) -> Result<Animator, String> {
+  Animator::build(sheet, STATE_IDLE)?
+    .with_transition(Transition::to(STATE_HURT).when(Condition::Triggered(String::from(TRIGGER_HIT))))?
+    .with_transition(Transition::to(STATE_JUMP).when(Condition::IsNot(String::from(PARAM_GROUNDED))).when(Condition::Below(String::from(PARAM_RISE), 0.0)))?
+    .with_transition(Transition::to(STATE_FALL).when(Condition::IsNot(String::from(PARAM_GROUNDED))))?
+    .with_transition(Transition::to(STATE_AIM_UP).when(Condition::Is(String::from(PARAM_GROUNDED))).when(Condition::Below(String::from(PARAM_AIM), 0.0)))?
+    .with_transition(Transition::to(STATE_RUN).when(Condition::Is(String::from(PARAM_GROUNDED))).when(Condition::Above(String::from(PARAM_SPEED), 0.0)))?
+    .with_transition(Transition::to(STATE_IDLE).when(Condition::Is(String::from(PARAM_GROUNDED))))
+}
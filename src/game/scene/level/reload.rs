@@ -0,0 +1,113 @@
+/**
+ * Reload assets, data files and Tiled maps while the level runs in debug builds
+ */
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::engine::asset::asset::AssetManager;
+use crate::engine::asset::watch::FileWatcher;
+use crate::engine::ecs::system::{SysArgs, Systemize};
+use crate::game::constant::WORLD_PATH;
+use crate::game::scene::level::collectable::data::{COLLECTABLE_DATA_PATH, deserialize_weapon_data};
+use crate::game::scene::level::player::world::use_player;
+use crate::game::scene::level::scene::LevelState;
+use crate::game::scene::level::story::data::{deserialize_story_data, STORY_DATA_PATH, StoryKey};
+use crate::game::scene::level::tile::tiled::TiledParser;
+
+/// Time between checks for changed files
+pub const RELOAD_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Extensions of the Tiled files the rooms are built from
+const TILED_EXTENSIONS: [&str; 3] = ["world", "tmx", "tsx"];
+
+/// Watches the files the level is built from
+pub struct HotReload {
+  watcher: FileWatcher,
+}
+
+impl HotReload {
+  /// Instantiate a hot reloader watching the data files and the Tiled files of `parser`
+  pub fn new(parser: &TiledParser) -> Self {
+    let mut reload = Self { watcher: FileWatcher::new(RELOAD_INTERVAL) };
+    reload.watcher.watch_many([WORLD_PATH, STORY_DATA_PATH, COLLECTABLE_DATA_PATH]);
+    reload.watch_tiled(parser);
+    reload
+  }
+  /// Watch the tilemaps and tilesets of a Tiled world
+  fn watch_tiled(&mut self, parser: &TiledParser) {
+    self.watcher.watch_many(parser.tilemaps.keys());
+    self.watcher.watch_many(parser.tilesets.keys());
+  }
+  /// Watch the textures and sheets loaded since the last poll, and get the files that changed
+  fn poll(&mut self, asset: &AssetManager) -> Vec<PathBuf> {
+    self.watcher.watch_many(asset.texture.get_paths());
+    self.watcher.watch_many(asset.sheet.get_paths());
+    self.watcher.poll()
+  }
+}
+
+/// What a changed file requires to be reloaded
+enum Reload {
+  /// the file was reloaded in place
+  Done,
+  /// the rooms must be rebuilt
+  Rooms,
+  /// the file is not used by the level
+  Unused,
+}
+
+/// Reload a changed file in place, or report that the rooms must be rebuilt
+fn reload_file(filepath: &Path, asset: &mut AssetManager, level: &mut LevelState) -> Result<Reload, String> {
+  if asset.texture.reload(filepath)? { return Ok(Reload::Done); }
+  // sheets are copied into the components of creatures, so they are spawned again
+  if asset.sheet.reload(filepath, &mut asset.texture)? { return Ok(Reload::Rooms); }
+
+  if filepath == Path::new(COLLECTABLE_DATA_PATH) {
+    level.weapon_data = deserialize_weapon_data()?;
+    return Ok(Reload::Done);
+  }
+  let tiled = filepath.extension().is_some_and(|extension| TILED_EXTENSIONS.iter().any(|tiled| extension == *tiled));
+  if tiled || filepath == Path::new(STORY_DATA_PATH) { return Ok(Reload::Rooms); }
+  Ok(Reload::Unused)
+}
+
+impl Systemize for HotReload {
+  /// Reload changed files, rebuilding the current room in place if its Tiled files or data changed
+  fn system(SysArgs { world, state, asset, render, camera, .. }: &mut SysArgs) -> Result<(), String> {
+    let level = state.get_mut::<LevelState>()?;
+    if level.room_registry.is_transitioning() { return Ok(()); } // changes are picked up once the transition ends
+    let Some(reload) = level.reload.as_mut() else { return Ok(()); };
+    let changed = reload.poll(asset);
+
+    let mut rebuild = false;
+    for filepath in changed {
+      match reload_file(&filepath, asset, level) {
+        Ok(Reload::Done) => println!("Reloaded {}", filepath.display()),
+        Ok(Reload::Rooms) => rebuild = true,
+        Ok(Reload::Unused) => {}
+        Err(error) => eprintln!("Failed to reload {}: {}", filepath.display(), error),
+      }
+    }
+    if !rebuild { return Ok(()); }
+
+    // a malformed file is reported rather than ending the session, so it can be fixed and saved again
+    let advancements: HashSet<StoryKey> = use_player(world).advancement.clone().into();
+    let rebuilt = deserialize_story_data()
+      .and_then(|story| Ok((story.omit(&advancements), TiledParser::parse(Path::new(WORLD_PATH))?)))
+      .and_then(|(story, parser)| {
+        if let Some(reload) = level.reload.as_mut() { reload.watch_tiled(&parser); }
+        level.room_registry.rebuild(parser, story, world, render, asset)
+      });
+    match rebuilt {
+      Ok(()) => {
+        camera.set_bounds(level.room_registry.get_current().ok_or("Failed to get rebuilt room")?.get_bounds());
+        println!("Rebuilt rooms");
+      }
+      Err(error) => eprintln!("Failed to rebuild rooms: {}", error),
+    }
+
+    Ok(())
+  }
+}
@@ -13,16 +13,17 @@ use crate::engine::geometry::shape::{Rec2, Vec2};
 use crate::engine::utility::alias::Size;
 use crate::engine::utility::color::{OPAQUE, RGBA};
 use crate::engine::utility::direction::Direction;
-use crate::game::preferences::use_preferences;
 use crate::game::scene::level::physics::collision::{Collider, Fragile, make_collision_box};
 use crate::game::scene::level::physics::frozen::Frozen;
 use crate::game::scene::level::physics::velocity::Velocity;
 use crate::game::scene::level::player::combat::{Bullet, Rocket};
-use crate::game::scene::level::room::meta::{Soft, Strong, TileLayerType};
+use crate::game::scene::level::room::meta::{Soft, Strong, TileBreakability, TileLayerType};
 use crate::game::scene::level::room::room::use_room;
 use crate::game::scene::level::tile::query::{TileHandle, TileQuery};
 use crate::game::scene::level::tile::tile::TileCollider;
 use crate::game::scene::level::tile::tilemap::TilemapMutation;
+use crate::game::sound::{SoundEvent, use_sounds};
+use crate::game::utility::debug::DEBUG_TILE;
 
 /// The maximum value of a resolvable collision
 ///
@@ -167,6 +168,10 @@ pub enum RoomCollision {
   Player,
 }
 
+/// Whether an entity stood on a tile when room collisions were last resolved
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Grounded(pub bool);
+
 /// Resolve tile collisions for entities collideable with rooms tiles
 impl Systemize for RoomCollision {
   fn system(SysArgs { world, state, .. }: &mut SysArgs) -> Result<(), String> {
@@ -180,10 +185,12 @@ impl Systemize for RoomCollision {
       .collect::<HashMap<_, _>>();
 
     let room = use_room(state);
+    let mut broken = Vec::new();
 
     for (entity, (position, collider, layer)) in &colliders {
       let mut collision_box = make_collision_box(position, collider);
       let mut phase = 0;
+      let mut grounded = false;
       'resolving: loop {
         phase += 1;
         let collisions = get_tile_collisions(world, &collision_box, layer);
@@ -203,6 +210,7 @@ impl Systemize for RoomCollision {
             let result = room.query_tile(TileLayerType::Collision, TileQuery::Position(position.0));
             if let Ok(handle) = TileHandle::try_from(result) {
               room.remove_tile(world, handle, TilemapMutation::Session);
+              broken.push(if brittle { TileBreakability::Brittle } else if strong { TileBreakability::Strong } else { TileBreakability::Soft });
             } else {
               return Err(String::from("Failed to remove tile"));
             }
@@ -218,6 +226,8 @@ impl Systemize for RoomCollision {
           let mut velocity = world.get_component_mut::<Velocity>(*entity)?;
           let resolution = collision.get_resolution();
           position.0 = position.0 - resolution;
+          // resolving up puts the entity on top of the tile
+          grounded |= resolution.y > 0.0;
           if resolution.y > 0.0 && velocity.0.y > 0.0 {
             // cut vertical acceleration if resolving up while falling
             // eg: landing on a platform
@@ -240,8 +250,12 @@ impl Systemize for RoomCollision {
           break 'resolving;
         }
       };
+      if let Ok(mut contact) = world.get_component_mut::<Grounded>(*entity) { contact.0 = grounded; }
     }
 
+    let sounds = use_sounds(state);
+    for breakability in broken { sounds.play(SoundEvent::Break(breakability)); }
+
     Ok(())
   }
 }
@@ -253,17 +267,13 @@ pub type TileCollisionBundle = (Entity, Collision, CollisionBox, Position);
 fn get_tile_collisions<'a>(world: &'a mut World, collider_box: &'a CollisionBox, layer: &'a RoomCollision) -> impl Iterator<Item=TileCollisionBundle> + 'a {
   world.query::<(&Position, &TileCollider, &RoomCollision)>()
     .into_iter()
-    .filter_map(|(entity, (tile_position, tile_collider, tile_collision_layer))| {
-      let tile_box = &CollisionBox::new(tile_position.0 + tile_collider.collision_box.origin, tile_collider.collision_box.size);
-      let collision = rec2_collision(collider_box, tile_box, tile_collider.mask);
-
-      if let Some(collision) = collision {
-        if *tile_collision_layer == RoomCollision::All || *layer == *tile_collision_layer {
-          return Some((entity, collision, *tile_box, *tile_position));
-        }
-      }
-
-      None
+    .filter(|(.., (.., tile_collision_layer))| **tile_collision_layer == RoomCollision::All || *layer == **tile_collision_layer)
+    .flat_map(move |(entity, (tile_position, tile_collider, _))| {
+      tile_collider.collision_boxes.iter().filter_map(move |collision_box| {
+        let tile_box = CollisionBox::new(tile_position.0 + collision_box.origin, collision_box.size);
+        let collision = rec2_collision(collider_box, &tile_box, tile_collider.mask)?;
+        Some((entity, collision, tile_box, *tile_position))
+      })
     })
 }
 
@@ -285,18 +295,21 @@ fn get_closest_collision<'a>(collisions: impl Iterator<Item=TileCollisionBundle>
 }
 
 /// Render the tile colliders to the screen when debug mode is active
-pub fn sys_render_tile_colliders(SysArgs { world, camera, render, state, .. }: &mut SysArgs) -> Result<(), String> {
-  if !use_preferences(state).debug { return Ok(()); }
+pub fn sys_render_tile_colliders(SysArgs { world, debug, .. }: &mut SysArgs) -> Result<(), String> {
+  if !debug.is_visible(DEBUG_TILE) { return Ok(()); }
 
   for (_, (position, collider)) in world.query::<(&Position, &TileCollider)>() {
     let color = RGBA::new(255, 0, 0, OPAQUE);
-    let (width, height) = collider.collision_box.size.destructure();
-    let p = camera.translate(Vec2::from(position.0 + collider.collision_box.origin));
+    for collision_box in &collider.collision_boxes {
+      let (width, height) = collision_box.size.destructure();
+      let (right, bottom) = (width as f32 - 1.0, height as f32 - 1.0);
+      let p = position.0 + collision_box.origin;
 
-    if collider.mask.top { render.draw_line(p, p + Vec2::new(width as i32 - 1, 0), color); }
-    if collider.mask.right { render.draw_line(p + Vec2::new(width as i32 - 1, 0), p + Vec2::new(width as i32 - 1, height as i32 - 1), color); }
-    if collider.mask.bottom { render.draw_line(p + Vec2::new(0, height as i32 - 1), p + Vec2::new(width as i32 - 1, height as i32 - 1), color); }
-    if collider.mask.left { render.draw_line(p, p + Vec2::new(0, height as i32 - 1), color); }
+      if collider.mask.top { debug.line(DEBUG_TILE, p, p + Vec2::new(right, 0.0), color); }
+      if collider.mask.right { debug.line(DEBUG_TILE, p + Vec2::new(right, 0.0), p + Vec2::new(right, bottom), color); }
+      if collider.mask.bottom { debug.line(DEBUG_TILE, p + Vec2::new(0.0, bottom), p + Vec2::new(right, bottom), color); }
+      if collider.mask.left { debug.line(DEBUG_TILE, p, p + Vec2::new(0.0, bottom), color); }
+    }
   }
 
   Ok(())
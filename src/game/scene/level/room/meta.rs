@@ -8,11 +8,13 @@ use serde::{Deserialize, Serialize};
 
 use crate::engine::asset::texture::SrcRect;
 use crate::engine::component::animation::{Animation, AnimationFrame};
-use crate::engine::geometry::shape::{Rec2, Vec2};
+use crate::engine::geometry::shape::Vec2;
 use crate::engine::utility::alias::{Size, Size2};
-use crate::engine::utility::direction::{CompassDirectionType, Direction};
+use crate::engine::utility::direction::Direction;
 use crate::game::scene::level::room::collision::{CollisionBox, RoomCollision};
-use crate::game::scene::level::tile::tiled::{TiledAnimation, TiledObject, TiledProperties, TiledProperty, TiledTileset};
+use crate::game::scene::level::tile::tile::TileData;
+use crate::game::scene::level::tile::tiled::{TiledAnimation, TiledObjectGroup, TiledProperties, TiledProperty, TiledTileLayer, TiledTileset};
+use crate::game::scene::level::tile::tilelayer::LayerPresentation;
 use crate::game::scene::level::tile::tilemap::MapIndex;
 
 /// An item collectable by Collections
@@ -37,10 +39,13 @@ pub struct Item {
   pub collectable: Collectable,
   pub map_index: MapIndex,
   pub room_name: String,
+  /// name of the tile layer the item was collected from, saves from before layers were named have none
+  #[serde(default)]
+  pub layer_name: Option<String>,
 }
 
 /// Defines the level of breakability for a tile
-#[derive(Default, Clone, Copy, Debug, PartialEq)]
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum TileBreakability {
   #[default]
   Solid,
@@ -68,22 +73,8 @@ pub struct TileMeta {
   pub collision_layer: RoomCollision,
   pub animation: Option<Animation>,
   pub damage: u32,
-}
-
-/// Metadata for a tilemap object
-#[derive(Clone, Debug)]
-pub enum ObjMeta {
-  AngryBuzzConcept { position: Vec2<f32> },
-  BubblyConcept { position: Vec2<f32>, direction: Direction },
-  BuzzConcept { position: Vec2<f32> },
-  StoryConcept { position: Vec2<f32>, collision_box: CollisionBox, key: String },
-  GruntConcept { position: Vec2<f32> },
-  SaveAreaConcept { position: Vec2<f32>, collision_box: CollisionBox },
-  SpikyConcept { direction: Direction, position: Vec2<f32> },
-  SporeConcept { direction: Direction, position: Vec2<f32> },
-  RipperConcept { direction: Direction, position: Vec2<f32> },
-  RotundConcept { direction: Direction, position: Vec2<f32>, spit_axis: CompassDirectionType },
-  ZoomerConcept { direction: Direction, position: Vec2<f32> },
+  /// rectangles relative to the tile from the Tiled collision editor, the whole tile collides if there are none
+  pub collision: Vec<CollisionBox>,
 }
 
 /// The behaviour and rendering order of a tile layer
@@ -95,6 +86,23 @@ pub enum TileLayerType {
   Background,
 }
 
+impl TileLayerType {
+  /// Get the render depth of layers of the type without a `depth` property
+  pub fn get_default_depth(&self) -> u8 {
+    match self {
+      TileLayerType::Foreground => 7,
+      TileLayerType::Collision => 6,
+      TileLayerType::Background => 4,
+    }
+  }
+}
+
+/// Deepest render layer tiles may be drawn on, the layers above are drawn relative to the camera
+pub const MAX_TILE_DEPTH: u8 = 7;
+
+/// Member of the Room class of the Tiled project naming the music track of a room, unset when empty
+pub const MUSIC_PROPERTY: &str = "Music";
+
 /// Extract a specific property from a collection of properties
 pub fn get_property(name: impl Into<String>, properties: &Option<TiledProperties>) -> Option<&str> {
   let name = name.into();
@@ -131,61 +139,6 @@ pub fn parse_animation(tileset: &TiledTileset, animation: &Option<TiledAnimation
   Ok(animation)
 }
 
-/// Parse a TiledObject into an ObjMeta
-pub fn parse_object(TiledObject { name, object_type, properties, x, y, width, height, .. }: &TiledObject) -> Result<ObjMeta, String> {
-  let position = Vec2::new(*x, *y);
-  let object_type = object_type.trim().to_lowercase();
-  let meta = match object_type.as_str() {
-    "angrybuzz" => ObjMeta::AngryBuzzConcept { position },
-    "buzz" => ObjMeta::BuzzConcept { position },
-    "bubbly" => {
-      let direction = parse_direction(properties).unwrap_or(Direction::UpRight);
-      ObjMeta::BubblyConcept { direction, position }
-    }
-    "grunt" => ObjMeta::GruntConcept { position },
-    "save" => {
-      if let (Some(width), Some(height)) = (*width, *height) {
-        let bounds = Rec2::new(position, Size2::new(width as Size, height as Size));
-        return Ok(ObjMeta::SaveAreaConcept { position, collision_box: CollisionBox::from(bounds) });
-      }
-      return Err(String::from("Save area must have a width and height"));
-    }
-    "spore" => {
-      let direction = parse_direction(properties).unwrap_or(Direction::Up);
-      ObjMeta::SporeConcept { direction, position }
-    }
-    "spiky" => {
-      let direction = parse_direction(properties).unwrap_or(Direction::Right);
-      ObjMeta::SpikyConcept { direction, position }
-    }
-    "ripper" => {
-      let direction = parse_direction(properties).unwrap_or(Direction::Right);
-      ObjMeta::RipperConcept { direction, position }
-    }
-    "rotund" => {
-      let direction = parse_direction(properties).unwrap_or(Direction::UpRight);
-      let spit_axis = parse_compass_direction_type("spit_axis", properties).unwrap_or(CompassDirectionType::Ordinal);
-      ObjMeta::RotundConcept { direction, position, spit_axis }
-    }
-    "zoomer" => {
-      let direction = parse_direction(properties).unwrap_or(Direction::Right);
-      ObjMeta::ZoomerConcept { direction, position }
-    }
-    "story" => {
-      if let Some(key) = name {
-        if let (Some(width), Some(height)) = (*width, *height) {
-          let bounds = Rec2::new(position, Size2::new(width as Size, height as Size));
-          return Ok(ObjMeta::StoryConcept { position, collision_box: CollisionBox::from(bounds), key: key.clone() });
-        }
-        return Err(String::from("Save area must have a width and height"));
-      }
-      return Err(String::from("Story object must have a name"));
-    }
-    _ => return Err(String::from(format!("Unknown object type: {}", object_type))),
-  };
-  Ok(meta)
-}
-
 /// Parse a tiled property into a Collectable
 ///
 /// todo: implement From<String> for Collectable
@@ -255,22 +208,56 @@ pub fn parse_tilelayer(properties: &Option<TiledProperties>) -> Result<TileLayer
   Ok(TileLayerType::default())
 }
 
-/// Parse a compass direction property from a collection of properties
-pub fn parse_compass_direction_type(property: &str, properties: &Option<TiledProperties>) -> Result<CompassDirectionType, String> {
-  if let Some(prop) = get_property(property, properties) {
-    let direction = CompassDirectionType::try_from(String::from(prop)).map_err(|err| err.to_string())?;
-    return Ok(direction);
-  }
-  Err(String::from("Direction property not found"))
+/// Parse the presentation of a Tiled tilelayer of a type
+pub fn parse_layer_presentation(layer: &TiledTileLayer, layer_type: TileLayerType) -> Result<LayerPresentation, String> {
+  let depth = match get_property("depth", &layer.properties) {
+    Some(prop) => prop.trim().parse::<u8>().map_err(|err| format!("Invalid depth: {}", err))?,
+    None => layer_type.get_default_depth(),
+  };
+  if !(1..=MAX_TILE_DEPTH).contains(&depth) { return Err(format!("Depth {} is not between 1 and {}", depth, MAX_TILE_DEPTH)); }
+  let opacity = layer.opacity.unwrap_or(1.0);
+  let parallax = Vec2::new(layer.parallax_x.unwrap_or(1.0), layer.parallax_y.unwrap_or(1.0));
+  Ok(LayerPresentation { depth, opacity, parallax })
 }
 
-/// Parse a direction property from a collection of properties
-pub fn parse_direction(properties: &Option<TiledProperties>) -> Result<Direction, String> {
-  if let Some(prop) = get_property("direction", properties) {
-    let direction = Direction::try_from(String::from(prop)).map_err(|err| err.to_string())?;
-    return Ok(direction);
-  }
-  Err(String::from("Direction property not found"))
+/// Parse the collision rectangles of a tile drawn in the Tiled collision editor
+pub fn parse_tile_collision(group: &Option<TiledObjectGroup>) -> Result<Vec<CollisionBox>, String> {
+  let Some(TiledObjectGroup { objects: Some(objects) }) = group else { return Ok(Vec::new()); };
+  objects
+    .iter()
+    .map(|object| {
+      let shaped = object.point.is_some() || object.ellipse.is_some() || object.polygon.is_some() || object.polyline.is_some();
+      match (object.width, object.height) {
+        (Some(width), Some(height)) if !shaped && width > 0.0 && height > 0.0 => {
+          Ok(CollisionBox::new(Vec2::new(object.x, object.y), Size2::new(width.round() as Size, height.round() as Size)))
+        }
+        _ => Err(format!("Collision shape {} is not a rectangle", object.id)),
+      }
+    })
+    .collect()
+}
+
+/// Get the collision rectangles of a placed tile, mirrored with the tile
+pub fn get_tile_collision(data: &TileData<TileMeta>) -> Vec<CollisionBox> {
+  if data.meta.collision.is_empty() { return vec![CollisionBox::new(Vec2::default(), data.src.size)]; }
+  data.meta.collision.iter().map(|rect| data.flip.apply_rect(rect, data.src.size)).collect()
+}
+
+/// Check if a single collision rectangle of a tile of `size` spans the whole edge of a side
+pub fn covers_side(collision: &[CollisionBox], size: Size2, side: Direction) -> bool {
+  let (width, height) = (size.x as f32, size.y as f32);
+  collision.iter().any(|rect| {
+    let ((x, y), (w, h)) = rect.destructure();
+    let (right, bottom) = (x + w as f32, y + h as f32);
+    let (spans_x, spans_y) = (x <= 0.0 && right >= width, y <= 0.0 && bottom >= height);
+    match side {
+      Direction::Up => y <= 0.0 && spans_x,
+      Direction::Right => right >= width && spans_y,
+      Direction::Down => bottom >= height && spans_x,
+      Direction::Left => x <= 0.0 && spans_y,
+      _ => false,
+    }
+  })
 }
 
 /// Parse a damage property from a collection of properties
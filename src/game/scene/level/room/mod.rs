@@ -1,6 +1,7 @@
 mod transition;
 
 pub mod meta;
+pub mod object;
 pub mod collision;
 pub mod registry;
 pub mod room;
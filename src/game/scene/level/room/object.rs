@@ -0,0 +1,362 @@
+/**
+ * Registry of the classes of Tiled objects, parsing their properties and spawning them into the world
+ */
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::{Arc, OnceLock};
+
+use hecs::Entity;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Number, Value};
+
+use crate::engine::asset::asset::AssetManager;
+use crate::engine::asset::manifest::AssetManifest;
+use crate::engine::ecs::world::World;
+use crate::engine::geometry::shape::{Ellipse, Polygon, Polyline, Rec2, Vec2};
+use crate::engine::utility::alias::{Size, Size2};
+use crate::game::persistence::world::SaveObject;
+use crate::game::scene::level::creature::angry_buzz::AngryBuzzObject;
+use crate::game::scene::level::creature::bubbly::BubblyObject;
+use crate::game::scene::level::creature::buzz::BuzzObject;
+use crate::game::scene::level::creature::grunt::GruntObject;
+use crate::game::scene::level::creature::ripper::RipperObject;
+use crate::game::scene::level::creature::rotund::RotundObject;
+use crate::game::scene::level::creature::spiky::SpikyObject;
+use crate::game::scene::level::creature::spore::SporeObject;
+use crate::game::scene::level::creature::zoomer::ZoomerObject;
+use crate::game::scene::level::story::data::Story;
+use crate::game::scene::level::story::world::StoryObject;
+use crate::game::scene::level::tile::tiled::{TiledObject, TiledProperties};
+
+/// The shape of a Tiled object, in the same space as its position
+#[derive(Clone, Debug, PartialEq)]
+pub enum ObjectShape {
+  /// a marker such as a spawn or teleport destination
+  Point(Vec2<f32>),
+  Rectangle(Rec2<f32, Size>),
+  Ellipse(Ellipse),
+  Polygon(Polygon),
+  /// an open path such as a patrol route
+  Polyline(Polyline),
+}
+
+impl ObjectShape {
+  /// Does the shape contain a point? Points and polylines enclose nothing
+  pub fn contains(&self, point: Vec2<f32>) -> bool {
+    match self {
+      ObjectShape::Point(..) | ObjectShape::Polyline(..) => false,
+      ObjectShape::Rectangle(rectangle) => rectangle.contains_point(point),
+      ObjectShape::Ellipse(ellipse) => ellipse.contains(point),
+      ObjectShape::Polygon(polygon) => polygon.contains(point),
+    }
+  }
+  /// Get the smallest rectangle containing the shape
+  pub fn get_bounds(&self) -> Rec2<f32, Size> {
+    match self {
+      ObjectShape::Point(point) => Rec2::new(*point, Size2::default()),
+      ObjectShape::Rectangle(rectangle) => *rectangle,
+      ObjectShape::Ellipse(ellipse) => ellipse.get_bounds(),
+      ObjectShape::Polygon(polygon) => polygon.get_bounds(),
+      ObjectShape::Polyline(polyline) => polyline.get_bounds(),
+    }
+  }
+  /// Move the shape by an offset
+  pub fn translate(&self, offset: Vec2<f32>) -> Self {
+    match self {
+      ObjectShape::Point(point) => ObjectShape::Point(*point + offset),
+      ObjectShape::Rectangle(rectangle) => ObjectShape::Rectangle(Rec2::new(rectangle.origin + offset, rectangle.size)),
+      ObjectShape::Ellipse(ellipse) => ObjectShape::Ellipse(ellipse.translate(offset)),
+      ObjectShape::Polygon(polygon) => ObjectShape::Polygon(polygon.translate(offset)),
+      ObjectShape::Polyline(polyline) => ObjectShape::Polyline(polyline.translate(offset)),
+    }
+  }
+}
+
+/// Attributes shared by every Tiled object
+#[derive(Clone, Debug, PartialEq)]
+pub struct ObjectData {
+  pub name: Option<String>,
+  /// position relative to the room while parsed, and in worldspace while spawned
+  pub position: Vec2<f32>,
+  pub shape: ObjectShape,
+}
+
+impl ObjectData {
+  /// Get the bounds of a rectangle object, if it has a width and height
+  pub fn get_rectangle(&self) -> Option<Rec2<f32, Size>> {
+    match self.shape {
+      ObjectShape::Rectangle(rectangle) if rectangle.size.x > 0 && rectangle.size.y > 0 => Some(rectangle),
+      _ => None,
+    }
+  }
+}
+
+/// Everything an object needs to add itself to the world
+pub struct SpawnArgs<'a, 'ttf> {
+  pub world: &'a mut World,
+  pub assets: &'a mut AssetManager<'ttf>,
+  pub story: &'a Story,
+  /// name of the room the object belongs to
+  pub room: &'a str,
+}
+
+/// A class of Tiled object, deserialized from the properties of an object
+///
+/// Fields are the members of the class in `asset/meta/propertytypes.json`, and default to the values there.
+/// Classes deny unknown fields, so a property the class does not declare fails to parse.
+pub trait ObjectClass: DeserializeOwned + Debug + Send + Sync + 'static {
+  /// name of the class in Tiled
+  const CLASS: &'static str;
+  /// List the assets of the class
+  fn manifest() -> AssetManifest { AssetManifest::default() }
+  /// Check the attributes of an object of the class
+  fn check(_object: &ObjectData) -> Result<(), String> { Ok(()) }
+  /// Add an object of the class to the world, or nothing if it should not appear
+  fn spawn(&self, object: &ObjectData, args: &mut SpawnArgs) -> Result<Option<Entity>, String>;
+}
+
+/// The properties of an object of any class
+trait ObjectProperties: Debug + Send + Sync {
+  fn manifest(&self) -> AssetManifest;
+  fn spawn(&self, object: &ObjectData, args: &mut SpawnArgs) -> Result<Option<Entity>, String>;
+}
+
+impl<T> ObjectProperties for T where T: ObjectClass {
+  fn manifest(&self) -> AssetManifest { T::manifest() }
+  fn spawn(&self, object: &ObjectData, args: &mut SpawnArgs) -> Result<Option<Entity>, String> { ObjectClass::spawn(self, object, args) }
+}
+
+/// Metadata for a tilemap object
+#[derive(Clone, Debug)]
+pub struct ObjMeta {
+  /// name of the class of the object in Tiled
+  pub class: &'static str,
+  pub data: ObjectData,
+  properties: Arc<dyn ObjectProperties>,
+}
+
+impl ObjMeta {
+  /// List the assets of the object
+  pub fn manifest(&self) -> AssetManifest { self.properties.manifest() }
+  /// Add the object to the world, relative to the room at `origin`
+  pub fn spawn(&self, origin: Vec2<f32>, args: &mut SpawnArgs) -> Result<Option<Entity>, String> {
+    let object = ObjectData {
+      name: self.data.name.clone(),
+      position: origin + self.data.position,
+      shape: self.data.shape.translate(origin),
+    };
+    self.properties.spawn(&object, args)
+  }
+}
+
+/// Convert Tiled properties to a JSON object, typed by their Tiled type
+fn properties_to_json(properties: &Option<TiledProperties>) -> Result<Map<String, Value>, String> {
+  let Some(properties) = properties else { return Ok(Map::new()); };
+  properties.properties
+    .iter()
+    .map(|property| {
+      let invalid = || format!("Property '{}' has an invalid value: {}", property.name, property.value);
+      let value = match property.primitive_type.as_deref() {
+        Some("bool") => Value::Bool(property.value.parse().map_err(|_| invalid())?),
+        Some("int") => Value::Number(property.value.parse::<i64>().map_err(|_| invalid())?.into()),
+        Some("float") => property.value
+          .parse::<f64>()
+          .ok()
+          .and_then(Number::from_f64)
+          .map(Value::Number)
+          .ok_or_else(invalid)?,
+        _ => Value::String(property.value.clone()),
+      };
+      Ok((property.name.clone(), value))
+    })
+    .collect()
+}
+
+/// Parse the shape of a Tiled object
+///
+/// Objects without a shape element are rectangles, of no size if they have no width or height.
+fn parse_shape(object: &TiledObject) -> Result<ObjectShape, String> {
+  let position = Vec2::new(object.x, object.y);
+  let size = Size2::new(object.width.unwrap_or_default() as Size, object.height.unwrap_or_default() as Size);
+  let shape = match (&object.point, &object.ellipse, &object.polygon, &object.polyline) {
+    (Some(..), ..) => ObjectShape::Point(position),
+    (_, Some(..), ..) => ObjectShape::Ellipse(Ellipse::from_bounds(&Rec2::new(position, size))),
+    (.., Some(polygon), _) => ObjectShape::Polygon(Polygon::new(polygon.get_points()?).translate(position)),
+    (.., Some(polyline)) => ObjectShape::Polyline(Polyline::new(polyline.get_points()?).translate(position)),
+    _ => ObjectShape::Rectangle(Rec2::new(position, size)),
+  };
+  Ok(shape)
+}
+
+/// Parse the properties of an object of a class
+fn parse_class<T>(properties: &Option<TiledProperties>) -> Result<Arc<dyn ObjectProperties>, String> where T: ObjectClass {
+  let properties = serde_json::from_value::<T>(Value::Object(properties_to_json(properties)?)).map_err(|e| e.to_string())?;
+  Ok(Arc::new(properties))
+}
+
+/// How to parse an object of a class
+struct ObjectFactory {
+  class: &'static str,
+  check: fn(&ObjectData) -> Result<(), String>,
+  parse: fn(&Option<TiledProperties>) -> Result<Arc<dyn ObjectProperties>, String>,
+}
+
+/// The classes of object a tilemap may contain, keyed by their name in Tiled
+#[derive(Default)]
+pub struct ObjectRegistry {
+  factories: HashMap<String, ObjectFactory>,
+}
+
+impl ObjectRegistry {
+  /// Register a class of object, matched by its name in Tiled regardless of case
+  pub fn with<T>(mut self) -> Self where T: ObjectClass {
+    let factory = ObjectFactory { class: T::CLASS, check: T::check, parse: parse_class::<T> };
+    self.factories.insert(T::CLASS.to_lowercase(), factory);
+    self
+  }
+
+  /// Get the names of the registered classes in alphabetical order
+  pub fn get_classes(&self) -> Vec<&'static str> {
+    let mut classes = self.factories.values().map(|factory| factory.class).collect::<Vec<_>>();
+    classes.sort_unstable();
+    classes
+  }
+
+  /// Parse a Tiled object into the properties of its class
+  pub fn parse(&self, object: &TiledObject) -> Result<ObjMeta, String> {
+    let TiledObject { id, name, object_type, properties, x, y, .. } = object;
+    let factory = self.factories
+      .get(&object_type.trim().to_lowercase())
+      .ok_or_else(|| format!("Unknown object class '{}' of object {}, expected one of: {}", object_type, id, self.get_classes().join(", ")))?;
+
+    let parsed = parse_shape(object)
+      .map(|shape| ObjectData { name: name.clone(), position: Vec2::new(*x, *y), shape })
+      .and_then(|data| (factory.check)(&data).and_then(|_| (factory.parse)(properties)).map(|properties| (data, properties)));
+    let (data, properties) = parsed.map_err(|e| format!("Failed to parse {} object {}: {}", factory.class, id, e))?;
+    Ok(ObjMeta { class: factory.class, data, properties })
+  }
+}
+
+static OBJECT_CLASSES: OnceLock<ObjectRegistry> = OnceLock::new();
+
+/// Get the classes of object placed in the rooms of the game
+pub fn use_object_classes() -> &'static ObjectRegistry {
+  OBJECT_CLASSES.get_or_init(|| {
+    ObjectRegistry::default()
+      .with::<AngryBuzzObject>()
+      .with::<BubblyObject>()
+      .with::<BuzzObject>()
+      .with::<GruntObject>()
+      .with::<RipperObject>()
+      .with::<RotundObject>()
+      .with::<SaveObject>()
+      .with::<SpikyObject>()
+      .with::<SporeObject>()
+      .with::<StoryObject>()
+      .with::<ZoomerObject>()
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::game::scene::level::tile::tiled::TiledProperty;
+
+  fn object(object_type: &str, properties: Vec<TiledProperty>) -> TiledObject {
+    TiledObject {
+      id: 1,
+      gid: None,
+      name: Some(String::from("intro")),
+      object_type: String::from(object_type),
+      x: 16.0,
+      y: 32.0,
+      width: Some(48.0),
+      height: Some(32.0),
+      properties: Some(TiledProperties { properties }),
+      point: None,
+      ellipse: None,
+      polygon: None,
+      polyline: None,
+      text: None,
+    }
+  }
+
+  fn property(name: &str, primitive_type: Option<&str>, value: &str) -> TiledProperty {
+    TiledProperty { name: String::from(name), property_type: None, primitive_type: primitive_type.map(String::from), value: String::from(value) }
+  }
+
+  #[test]
+  fn test_parse_object() {
+    let classes = use_object_classes();
+    let meta = classes.parse(&object("rotund", vec![property("spit_axis", None, "cardinal")])).unwrap();
+    assert_eq!((meta.class, meta.data.position), ("Rotund", Vec2::new(16.0, 32.0)), "class is matched regardless of case");
+    assert_eq!(meta.data.get_rectangle().map(|bounds| bounds.size), Some(Size2::new(48, 32)));
+    assert!(format!("{:?}", meta).contains("Cardinal"));
+    let unsized_save = TiledObject { width: None, ..object("Save", Vec::new()) };
+    assert!(classes.parse(&unsized_save).is_err(), "save area has no width");
+    assert!(classes.parse(&TiledObject { height: Some(0.0), ..object("Story", Vec::new()) }).is_err(), "story area has no height");
+    assert!(classes.parse(&object("Zoomer", vec![property("direction", None, "sideways")])).is_err(), "property has an invalid value");
+    assert!(classes.parse(&object("Buzz", vec![property("direction", None, "left")])).is_err(), "class does not declare the property");
+
+    let error = classes.parse(&object("Blob", Vec::new())).unwrap_err();
+    assert!(error.contains("'Blob'") && error.contains("AngryBuzz, Bubbly, Buzz"), "error lists the known classes: {}", error);
+  }
+
+  #[test]
+  fn test_parse_shape() {
+    use crate::game::scene::level::tile::tiled::{TiledEllipse, TiledPoint, TiledPolygon};
+    let shape = |object: TiledObject| parse_shape(&object).unwrap();
+    let bounds = Rec2::new(Vec2::new(16.0, 32.0), Size2::new(48, 32));
+    assert_eq!(shape(object("Save", Vec::new())), ObjectShape::Rectangle(bounds));
+    assert_eq!(shape(TiledObject { point: Some(TiledPoint), ..object("Zoomer", Vec::new()) }), ObjectShape::Point(Vec2::new(16.0, 32.0)));
+    assert_eq!(shape(TiledObject { ellipse: Some(TiledEllipse), ..object("Story", Vec::new()) }), ObjectShape::Ellipse(Ellipse::from_bounds(&bounds)));
+
+    let path = TiledPolygon { points: String::from("0,0 32,0 32,16") };
+    let polyline = shape(TiledObject { polyline: Some(path), ..object("Buzz", Vec::new()) });
+    assert_eq!(polyline.get_bounds(), Rec2::new(Vec2::new(16.0, 32.0), Size2::new(32, 16)), "points are relative to the object");
+    assert!(!polyline.contains(Vec2::new(40.0, 36.0)), "paths enclose nothing");
+    let triangle = TiledPolygon { points: String::from("0,0 32,0 32,16") };
+    let polygon = shape(TiledObject { polygon: Some(triangle), ..object("Story", Vec::new()) });
+    assert!(polygon.contains(Vec2::new(40.0, 36.0)) && !polygon.contains(Vec2::new(20.0, 44.0)));
+    assert!(polygon.translate(Vec2::new(100.0, 0.0)).contains(Vec2::new(140.0, 36.0)));
+  }
+
+  #[test]
+  fn test_property_types() {
+    let properties = Some(TiledProperties { properties: vec![
+      property("hidden", Some("bool"), "true"),
+      property("count", Some("int"), "3"),
+      property("speed", Some("float"), "1.5"),
+      property("direction", None, "up"),
+    ] });
+    let json = properties_to_json(&properties).unwrap();
+    assert_eq!(Value::Object(json), serde_json::json!({ "hidden": true, "count": 3, "speed": 1.5, "direction": "up" }));
+    let properties = Some(TiledProperties { properties: vec![property("count", Some("int"), "many")] });
+    assert!(properties_to_json(&properties).is_err());
+  }
+
+  /// Every class is an object class of the Tiled project, declares its members, and takes its defaults when a property is not set
+  #[test]
+  fn test_aligned_with_property_types() {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("asset/meta/propertytypes.json");
+    let types = serde_json::from_str::<Vec<Value>>(&std::fs::read_to_string(path).unwrap()).unwrap();
+    for class in use_object_classes().get_classes() {
+      let tiled = types
+        .iter()
+        .find(|tiled| tiled["type"] == "class" && tiled["name"] == class)
+        .unwrap_or_else(|| panic!("{} is not a class of the Tiled project", class));
+      assert!(tiled["useAs"].as_array().unwrap().contains(&Value::from("object")), "{} is not an object class", class);
+
+      let defaults = tiled["members"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|member| property(member["name"].as_str().unwrap(), member["type"].as_str(), &member["value"].to_string().trim_matches('"')))
+        .collect();
+      let unset = use_object_classes().parse(&object(class, Vec::new())).unwrap();
+      let set = use_object_classes().parse(&object(class, defaults)).unwrap();
+      assert_eq!(format!("{:?}", unset), format!("{:?}", set), "{} defaults differ from the Tiled project", class);
+    }
+  }
+}
@@ -12,18 +12,21 @@ use crate::engine::ecs::world::World;
 use crate::engine::geometry::shape::{Rec2, Vec2};
 use crate::engine::math::interpolation::lerp;
 use crate::engine::render::camera::CameraBounds;
+use crate::engine::render::renderer::Renderer;
 use crate::engine::utility::alias::Size;
 use crate::game::constant::ease_in_out;
 use crate::game::scene::level::player::world::{PlayerQuery, use_player};
 use crate::game::scene::level::room::collision::{CollisionBox, CollisionMask, rec2_collision};
-use crate::game::scene::level::room::meta::TileMeta;
+use crate::game::scene::level::room::meta::{MUSIC_PROPERTY, TileMeta};
+use crate::game::scene::level::room::object::use_object_classes;
 use crate::game::scene::level::room::room::{ActiveRoom, Room, ROOM_ENTER_MARGIN, RoomCollider, RoomKey, RoomTileException};
 use crate::game::scene::level::room::transition::{RoomTransition, RoomTransitionData, RoomTransitionState};
 use crate::game::scene::level::scene::LevelState;
 use crate::game::scene::level::story::data::Story;
 use crate::game::scene::level::tile::parse::{tilemap_from_tiled, tileset_from_tiled};
 use crate::game::scene::level::tile::tiled::{TiledParser, TiledTilemapChildren};
-use crate::game::scene::level::tile::tileset::Tileset;
+use crate::game::scene::level::tile::tile::{TileKey, TilesetKey};
+use crate::game::scene::level::tile::tileset::{TilemapTilesets, Tileset};
 use crate::game::utility::path::{get_basename, get_filename};
 
 type RoomRegistryTileExceptions = HashMap<RoomKey, Vec<RoomTileException>>;
@@ -33,7 +36,7 @@ pub struct RoomRegistry {
   current: Option<RoomKey>,
   transition: RoomTransition,
 
-  tilesets: HashMap<RoomKey, Tileset<TileMeta>>,
+  tilesets: HashMap<TilesetKey, Tileset<TileMeta>>,
   story_data: Story,
   rooms: HashMap<RoomKey, Room>,
   colliders: HashMap<RoomKey, Entity>,
@@ -49,28 +52,29 @@ impl RoomRegistry {
     let mut tilesets = HashMap::new();
     for (path, tiled_tileset) in parser.tilesets {
       let name = get_filename(&path)?;
-      let tileset = tileset_from_tiled(assets, path, &tiled_tileset)?;
+      if tilesets.contains_key(&name) { return Err(format!("Multiple tilesets are named {}", name)); }
+      let tileset = tileset_from_tiled(assets, &name, path, &tiled_tileset)?;
       tilesets.insert(name, tileset);
     }
 
     let mut rooms = HashMap::new();
     let mut colliders = HashMap::new();
     for (path, tiled_tilemap) in parser.tilemaps {
-      let tileset_path = &tiled_tilemap
+      let references = tiled_tilemap
         .children
         .iter()
         .filter_map(|child| match child {
           TiledTilemapChildren::TilesetReference(child) => Some(child),
           _ => None,
         })
-        .next() // we don't support multiple tilesets per tilemap
-        .ok_or("No tileset found")?
-        .source;
-      let tileset_name = get_filename(tileset_path)?;
-      let tileset = tilesets
-        .get(&tileset_name)
-        .ok_or("Tileset not found")?;
-      let tilemap = tilemap_from_tiled(&tiled_tilemap, &tileset)?;
+        .map(|reference| {
+          let name = get_filename(&reference.source)?;
+          let tileset = tilesets.get(&name).ok_or(format!("Tileset {} not found", name))?;
+          Ok((reference.first_gid as TileKey, tileset))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+      let tilemap = tilemap_from_tiled(&tiled_tilemap, &TilemapTilesets::build(references)?, use_object_classes())
+        .map_err(|e| format!("Failed to build tilemap {}: {}", path.display(), e))?;
 
       let tilemap_file = get_basename(&path)?;
       let world_map_reference = parser.world.maps
@@ -86,14 +90,11 @@ impl RoomRegistry {
       let collider_entity = world.add((collider, ));
       colliders.insert(tilemap_name.clone(), collider_entity);
 
-      let room = Room::build(tilemap_name.clone(), tilemap, position, exceptions.remove(&tilemap_name).unwrap_or(Vec::new()));
+      let room = Room::build(tilemap_name.clone(), tilemap, position, exceptions.remove(&tilemap_name).unwrap_or(Vec::new()))
+        .with_music(tiled_tilemap.get_property(MUSIC_PROPERTY).filter(|music| !music.is_empty()).map(String::from));
       rooms.insert(tilemap_name, room);
     }
 
-    if tilesets.len() > 1 {
-      return Err(String::from("Multiple tilesets found; can't store a single tileset in the register"));
-    }
-
     Ok(Self {
       current: None,
       transition: RoomTransition::default(),
@@ -104,10 +105,27 @@ impl RoomRegistry {
       colliders,
     })
   }
+  /// Rebuild the rooms from a `TiledParser` and add the current room to the world again
+  ///
+  /// Rooms keep their tile exceptions, and entities that do not belong to a room such as the player are untouched.
+  pub fn rebuild(&mut self, parser: TiledParser, story_data: Story, world: &mut World, render: &mut Renderer, assets: &mut AssetManager) -> Result<(), String> {
+    if !self.transition.is_idle() { return Err(String::from("Rooms can't be rebuilt during a transition")); }
+    let current = self.current.clone().ok_or("No current room to rebuild")?;
+
+    self.remove_room_from_world(&current, world, assets)?;
+    for (_, entity) in self.colliders.drain() { world.free_now(entity)?; }
+    let exceptions = self.rooms
+      .drain()
+      .map(|(name, room)| (name, room.into_exceptions()))
+      .collect();
+
+    *self = Self::build(parser, exceptions, story_data, assets, world)?;
+    self.load_room(current, world, render, assets)
+  }
   /// Load a starting room and bypass room transitions
-  pub fn load_room(&mut self, room: impl Into<RoomKey>, world: &mut World, assets: &mut AssetManager) -> Result<(), String> {
+  pub fn load_room(&mut self, room: impl Into<RoomKey>, world: &mut World, render: &mut Renderer, assets: &mut AssetManager) -> Result<(), String> {
     let next = room.into();
-    self.add_room_to_world(&next, world, assets).expect("Failed to load starting room");
+    self.add_room_to_world(&next, world, render, assets).expect("Failed to load starting room");
     self.activate_room(&next, world).expect("Failed to activate starting room");
     self.current = Some(next);
 
@@ -133,23 +151,20 @@ impl RoomRegistry {
   }
 
   /// Remove a room and it's entities from the world
-  fn remove_room_from_world(&mut self, name: &String, world: &mut World) -> Result<(), String> {
-    let room = self
+  fn remove_room_from_world(&mut self, name: &String, world: &mut World, assets: &mut AssetManager) -> Result<(), String> {
+    self
       .rooms
       .get_mut(name)
-      .ok_or("Room not found")?;
-
-    room.remove_from_world(world);
-
-    Ok(())
+      .ok_or("Room not found")?
+      .remove_from_world(world, assets)
   }
   /// Add the entities associated with a room to the world
-  fn add_room_to_world(&mut self, name: impl Into<String>, world: &mut World, assets: &mut AssetManager) -> Result<(), String> {
+  fn add_room_to_world(&mut self, name: impl Into<String>, world: &mut World, render: &mut Renderer, assets: &mut AssetManager) -> Result<(), String> {
     self
       .rooms
       .get_mut(&name.into())
       .ok_or("Room not found")?
-      .add_to_world(world, assets, &self.story_data)
+      .add_to_world(world, render, assets, &self.story_data)
   }
 
   pub fn queue_transition(&mut self, name: impl Into<String>) -> Result<(), String> {
@@ -168,13 +183,16 @@ impl RoomRegistry {
       .and_then(|name| self.rooms.get_mut(name))
   }
 
+  /// Check if a room transition is queued or in progress
+  pub fn is_transitioning(&self) -> bool { !self.transition.is_idle() }
+
   /// Get a tileset by name
-  pub fn get_tileset(&self, name: String) -> Option<&Tileset<TileMeta>> { self.tilesets.get(&name) }
+  pub fn get_tileset(&self, name: &str) -> Option<&Tileset<TileMeta>> { self.tilesets.get(name) }
 }
 
 /// Check for room transitions and manage them
 impl Systemize for RoomRegistry {
-  fn system(SysArgs { world, asset, camera, event, state, .. }: &mut SysArgs) -> Result<(), String> {
+  fn system(SysArgs { world, asset, camera, event, state, render, .. }: &mut SysArgs) -> Result<(), String> {
     let room_registry = &mut state.get_mut::<LevelState>()?.room_registry;
 
     let PlayerQuery { position, collider: player_collider, .. } = use_player(world);
@@ -202,7 +220,7 @@ impl Systemize for RoomRegistry {
       // Transition is queued: create the next room and start the transition
       RoomTransitionState::Queued(next) => {
         room_registry.deactivate_room(&room_registry.current.clone().unwrap(), world)?;
-        room_registry.add_room_to_world(&next, world, asset)?;
+        room_registry.add_room_to_world(&next, world, render, asset)?;
 
         let new_bounds = room_registry.rooms.get(&next).expect("Failed to get new bounds").get_bounds();
         let entry_bounds = CameraBounds::new(
@@ -243,7 +261,7 @@ impl Systemize for RoomRegistry {
       }
       // the transition is complete: activate the new room, delete the old one and resume the game
       RoomTransitionState::Complete(next) => {
-        room_registry.remove_room_from_world(&room_registry.current.clone().unwrap(), world)?;
+        room_registry.remove_room_from_world(&room_registry.current.clone().unwrap(), world, asset)?;
 
         room_registry.activate_room(&next, world)?;
         room_registry.current = Some(next);
@@ -2,46 +2,40 @@
  * Room creation and management
  */
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use hecs::{DynamicBundle, Entity};
 
 use crate::engine::asset::asset::AssetManager;
+use crate::engine::asset::manifest::AssetManifest;
+use crate::engine::asset::texture::{SrcRect, TextureScope};
 use crate::engine::component::position::Position;
 use crate::engine::component::sprite::Sprite;
 use crate::engine::ecs::system::SysArgs;
 use crate::engine::ecs::world::World;
-use crate::engine::geometry::shape::{Rec2, Vec2};
+use crate::engine::geometry::shape::Vec2;
 use crate::engine::math::conversion::coordinate_to_index;
-use crate::engine::render::camera::CameraBounds;
-use crate::engine::render::renderer::layer;
+use crate::engine::render::camera::{CameraBounds, Parallax};
+use crate::engine::render::renderer::{layer, Renderer};
 use crate::engine::utility::color::{OPAQUE, RGBA};
 use crate::engine::utility::direction::{HALF_DIRECTION_ROTATION, Rotation};
 use crate::engine::utility::state::State;
 use crate::game::constant::TILE_SIZE;
-use crate::game::persistence::world::make_save_area;
-use crate::game::preferences::use_preferences;
 use crate::game::scene::level::combat::damage::Damage;
-use crate::game::scene::level::creature::angry_buzz::make_angry_buzz;
-use crate::game::scene::level::creature::bubbly::make_bubbly;
-use crate::game::scene::level::creature::buzz::make_buzz;
-use crate::game::scene::level::creature::grunt::make_grunt;
-use crate::game::scene::level::creature::ripper::make_ripper;
-use crate::game::scene::level::creature::rotund::make_rotund;
-use crate::game::scene::level::creature::spiky::make_spiky;
-use crate::game::scene::level::creature::spore::make_spore;
-use crate::game::scene::level::creature::zoomer::make_zoomer;
 use crate::game::scene::level::physics::collision::Fragile;
 use crate::game::scene::level::player::combat::PlayerHostile;
 use crate::game::scene::level::room::collision::{CollisionBox, CollisionMask, RoomCollision};
-use crate::game::scene::level::room::meta::{ObjMeta, Soft, Strong, TileBreakability, TileLayerType, TileMeta};
+use crate::game::scene::level::room::meta::{get_tile_collision, Soft, Strong, TileBreakability, TileLayerType, TileMeta};
+use crate::game::scene::level::room::object::{ObjMeta, SpawnArgs};
 use crate::game::scene::level::scene::LevelState;
 use crate::game::scene::level::story::data::Story;
-use crate::game::scene::level::story::world::make_story_area;
+use crate::game::scene::level::tile::bake::{BakedChunk, TileBake};
 use crate::game::scene::level::tile::query::{TileHandle, TileQuery, TileQueryResult};
 use crate::game::scene::level::tile::tile::{Tile, TileCollider, TileKey};
-use crate::game::scene::level::tile::tilemap::{MapIndex, Tilemap, TilemapMutation};
+use crate::game::scene::level::tile::tilelayer::LayerPresentation;
+use crate::game::scene::level::tile::tilemap::{LayerIndex, MapIndex, Tilemap, TilemapMutation};
 use crate::game::scene::level::tile::tileset::Tileset;
+use crate::game::utility::debug::DEBUG_ROOM;
 
 pub const ROOM_ENTER_MARGIN: i32 = TILE_SIZE.x as i32 / 2;
 
@@ -67,21 +61,30 @@ impl RoomCollider {
 #[derive(Debug, Clone, Default)]
 pub struct ActiveRoom;
 
-/// An exception to the tilemap that must be overridden
+/// An exception to a tile of a layer of the tilemap
+///
+/// Exceptions without a layer name were saved before layers were named, and apply to the front-most layer of their
+/// type with a tile at the index, which is the layer the tile was collected from.
 #[derive(Default, Debug, Clone)]
 pub struct RoomTileException {
   layer: TileLayerType,
+  layer_name: Option<String>,
   index: MapIndex,
   key: Option<TileKey>,
 }
 
 impl RoomTileException {
   /// Instantiate a new tile exception
-  pub fn new(index: MapIndex, layer: TileLayerType, key: Option<TileKey>) -> Self {
-    Self { index, key, layer }
+  pub fn new(index: MapIndex, layer: TileLayerType, layer_name: Option<String>, key: Option<TileKey>) -> Self {
+    Self { index, key, layer, layer_name }
   }
 }
 
+/// List the assets of the objects in a room
+pub fn make_room_manifest<'a>(objects: impl IntoIterator<Item=&'a ObjMeta>) -> AssetManifest {
+  objects.into_iter().fold(AssetManifest::default(), |manifest, object| manifest.with_manifest(object.manifest()))
+}
+
 /// A room in the game, with a tilemap and entities that interact with it
 pub struct Room {
   name: String,
@@ -89,30 +92,60 @@ pub struct Room {
   tilemap: Tilemap<TileMeta, TileLayerType, ObjMeta>,
   exceptions: Vec<RoomTileException>,
   entities: HashSet<Entity>,
+  baked: Option<Vec<BakedChunk<LayerIndex>>>,
+  /// textures referenced while the room is in the world
+  textures: Option<TextureScope>,
+  /// track played in the room rather than the track of its area
+  music: Option<String>,
 }
 
 impl Room {
   /// Instantiate a new room
   pub fn build(name: String, tilemap: Tilemap<TileMeta, TileLayerType, ObjMeta>, position: Vec2<f32>, exceptions: Vec<RoomTileException>) -> Self {
-    Self { name, tilemap, position, exceptions, entities: HashSet::new() }
+    Self { name, tilemap, position, exceptions, entities: HashSet::new(), baked: None, textures: None, music: None }
+  }
+  /// Play a track in the room rather than the track of its area
+  pub fn with_music(mut self, music: Option<String>) -> Self {
+    self.music = music;
+    self
   }
 
   // Tilemap //
 
   /// Create and add tiles associated with the tilemap to the world
-  fn add_tilemap_to_world(&mut self, world: &mut World) -> Result<(), String> {
+  ///
+  /// Static tiles are baked into chunk textures when the room is added. Only tiles that animate, can be
+  /// destroyed, or collected keep their own sprite, so breaking a tile never invalidates a baked chunk.
+  fn add_tilemap_to_world(&mut self, world: &mut World, render: &mut Renderer, assets: &mut AssetManager) -> Result<(), String> {
     let tilemap_position = self.position;
     let dimensions = self.tilemap.get_size();
-    self.tilemap.add_tiles(|layer, tile, _, position| {
+    let mut bake = if self.baked.is_none() { Some(TileBake::new(self.tilemap.get_tile_size())) } else { None };
+    let exceptions = self.exceptions
+      .iter()
+      .filter_map(|exception| self.get_exception_layer(exception).map(|layer_index| ((layer_index, exception.index), exception.key)))
+      .collect::<HashMap<_, _>>();
+
+    self.tilemap.add_tiles(|layer_index, layer, presentation, tile, coordinate, position| {
       let index = coordinate_to_index(&tile.coordinate, dimensions);
-      if let Some(exception) = self.exceptions.iter().find(|exception| exception.layer == layer && exception.index == index) {
-        if exception.key.is_none() {
+      if let Some(key) = exceptions.get(&(layer_index, index)) {
+        if key.is_none() {
           return Ok(None);
         } else {
           unimplemented!("Tile exceptions with Some tile keys are not yet implemented");
         }
       }
 
+      let visible = tile.data.meta.collision_layer == RoomCollision::All;
+      let baked = visible && is_static(&tile.data.meta);
+      if baked {
+        if let Some(bake) = bake.as_mut() {
+          bake.add(layer_index, coordinate, position, tile.data.texture_key, tile.data.src, tile.data.flip.get_orientation());
+        }
+      }
+
+      // only collision tiles and tiles drawn individually need an entity
+      if layer != TileLayerType::Collision && (baked || !visible) { return Ok(None); }
+
       let position = position + tilemap_position;
 
       let collision_layer = tile.data.meta.collision_layer;
@@ -123,32 +156,24 @@ impl Room {
       ));
 
       // todo: add sprites if `meta.hidden` is true instead...
-      if collision_layer == RoomCollision::All {
-        world.add_components(entity, (
-          Sprite::new(tile.data.texture_key, tile.data.src),
-        )).expect("Failed to add active room component");
+      if visible && !baked {
+        let mut sprite = Sprite::new(tile.data.texture_key, tile.data.src).with_opacity(presentation.opacity);
+        tile.data.flip.apply(&mut sprite);
+        world.add_components(entity, (sprite, )).expect("Failed to add active room component");
 
         if let Some(animation) = tile.data.meta.animation.clone() {
           world.add_components(entity, (
             animation.start(),
           )).expect("Failed to add animation component");
         }
-      }
 
-      // add render layer
-      match layer {
-        TileLayerType::Foreground => world.add_components(entity, (layer::Layer7, ))?,
-        TileLayerType::Collision => world.add_components(entity, (layer::Layer6, ))?,
-        TileLayerType::Background => world.add_components(entity, (layer::Layer4, ))?,
+        add_presentation(world, entity, presentation, tilemap_position)?;
       }
 
       // add a collider if the tile has a mask
       if layer == TileLayerType::Collision {
         if !tile.mask.is_empty() {
-          let collider = TileCollider::new(
-            Rec2::new(Vec2::default(), tile.data.src.size),
-            tile.mask,
-          );
+          let collider = TileCollider::new(get_tile_collision(&tile.data), tile.mask);
           world.add_components(entity, (collider, ))?;
         }
 
@@ -170,7 +195,37 @@ impl Room {
       }
 
       Ok(Some(entity))
-    })
+    })?;
+
+    if let Some(bake) = bake {
+      self.baked = Some(if bake.is_empty() { Vec::new() } else { bake.bake(render, &mut assets.texture)? });
+    }
+    self.add_baked_to_world(world)
+  }
+  /// Add entities to render the baked chunks of the tilemap
+  fn add_baked_to_world(&mut self, world: &mut World) -> Result<(), String> {
+    let chunks = self.baked.as_ref().ok_or("Tilemap has not been baked")?;
+    for chunk in chunks {
+      let presentation = &self.tilemap.get_layer(chunk.layer).ok_or("Baked chunk layer does not exist")?.presentation;
+      let entity = world.add((
+        Sprite::new(chunk.texture, SrcRect::new(Vec2::default(), chunk.dimensions)).with_opacity(presentation.opacity),
+        Position::from(self.position + chunk.position),
+      ));
+      add_presentation(world, entity, presentation, self.position)?;
+      self.entities.insert(entity);
+    }
+    Ok(())
+  }
+  /// Get the layer a tile exception applies to
+  fn get_exception_layer(&self, exception: &RoomTileException) -> Option<LayerIndex> {
+    match &exception.layer_name {
+      Some(name) => self.tilemap.find_layer(exception.layer, |layer| layer.name == *name),
+      None => self.tilemap.find_layer(exception.layer, |layer| layer.tiles.get(exception.index).is_some_and(Option::is_some)),
+    }
+  }
+  /// Get the name of a tile layer of the room
+  pub fn get_layer_name(&self, layer_index: LayerIndex) -> Option<String> {
+    self.tilemap.get_layer(layer_index).map(|layer| layer.name.clone())
   }
   /// Remove the tiles from the world
   fn remove_tilemap_from_world(&mut self, world: &mut World) {
@@ -190,8 +245,8 @@ impl Room {
       |handle, neighbour| {
         // Repair the neighbor tile by adding a collider or updating the mask
         if !world.has_component::<TileCollider>(handle.entity).expect("Failed to check tile tile collider") {
-          let collision_box = CollisionBox::new(Vec2::default(), handle.concept.data.src.size);
-          world.add_components(handle.entity, (TileCollider::new(collision_box, CollisionMask::default()), )).expect("Failed to add tile collider");
+          let collision_boxes = get_tile_collision(&handle.concept.data);
+          world.add_components(handle.entity, (TileCollider::new(collision_boxes, CollisionMask::default()), )).expect("Failed to add tile collider");
         }
         let mut tile_collider = world.get_component_mut::<TileCollider>(handle.entity).expect("Failed to retrieve tile collider");
         tile_collider.mask.set_side(neighbour.rotate(Rotation::Left, HALF_DIRECTION_ROTATION), true).expect("failed to set side");
@@ -205,31 +260,11 @@ impl Room {
   // Entities //
 
   pub fn add_entities_to_world(&mut self, world: &mut World, assets: &mut AssetManager, state: &Story) -> Result<(), String> {
+    let mut args = SpawnArgs { world, assets, story: state, room: &self.name };
     self.tilemap.add_objects(|object| {
-      let entity = match object {
-        ObjMeta::AngryBuzzConcept { position } => world.add(make_angry_buzz(assets, self.position + *position)?),
-        ObjMeta::BubblyConcept { position, direction, .. } => world.add(make_bubbly(assets, self.position + *position, *direction)?),
-        ObjMeta::BuzzConcept { position } => world.add(make_buzz(assets, self.position + *position)?),
-        ObjMeta::GruntConcept { position } => world.add(make_grunt(assets, self.position + *position)?),
-        ObjMeta::SaveAreaConcept { position, collision_box } => {
-          let story = state.get_entry("save");
-          world.add(make_save_area(self.name.clone(), CollisionBox::new(self.position + *position, collision_box.size), story)?)
-        }
-        ObjMeta::SpikyConcept { direction, position } => world.add(make_spiky(assets, self.position + *position, *direction)?),
-        ObjMeta::SporeConcept { direction, position } => world.add(make_spore(assets, self.position + *position, *direction)?),
-        ObjMeta::RipperConcept { direction, position } => world.add(make_ripper(assets, self.position + *position, *direction)?),
-        ObjMeta::RotundConcept { direction, position, spit_axis } => world.add(make_rotund(assets, self.position + *position, *direction, *spit_axis)?),
-        ObjMeta::ZoomerConcept { direction, position } => world.add(make_zoomer(assets, self.position + *position, *direction)?),
-        ObjMeta::StoryConcept { position, collision_box, key } => {
-          if let Some(entry) = state.get_entry(key) {
-            let collision_box = CollisionBox::new(self.position + *position, collision_box.size);
-            return Ok(Some(world.add(make_story_area(entry.clone(), collision_box))));
-          }
-          return Ok(None);
-        }
-      };
-      self.entities.insert(entity);
-      Ok(Some(entity))
+      let entity = object.spawn(self.position, &mut args)?;
+      self.entities.extend(entity);
+      Ok(entity)
     })
   }
   // Add a new entity associated with the room
@@ -257,14 +292,25 @@ impl Room {
   // Room //
 
   /// Add the entities and tilemap associated with the room to the world
-  pub fn add_to_world(&mut self, world: &mut World, assets: &mut AssetManager, state: &Story) -> Result<(), String> {
-    self.add_tilemap_to_world(world)?;
-    self.add_entities_to_world(world, assets, state)
+  ///
+  /// Textures referenced while adding the room are held until it is removed.
+  pub fn add_to_world(&mut self, world: &mut World, render: &mut Renderer, assets: &mut AssetManager, state: &Story) -> Result<(), String> {
+    let scope = *self.textures.get_or_insert_with(|| assets.texture.open_scope());
+    let previous = assets.texture.set_scope(Some(scope));
+    let added = self
+      .add_tilemap_to_world(world, render, assets)
+      .and_then(|_| self.add_entities_to_world(world, assets, state));
+    assets.texture.set_scope(previous);
+    added
   }
-  // Remove the entities associated with the room from the world
-  pub fn remove_from_world(&mut self, world: &mut World) {
+  /// Consume the room into the tile exceptions it was built with
+  pub fn into_exceptions(self) -> Vec<RoomTileException> { self.exceptions }
+  // Remove the entities associated with the room from the world, and release the textures it referenced
+  pub fn remove_from_world(&mut self, world: &mut World, assets: &mut AssetManager) -> Result<(), String> {
     self.remove_tilemap_from_world(world);
     self.remove_entities(world);
+    self.baked = None;
+    self.textures.take().map_or(Ok(()), |scope| assets.texture.close_scope(scope))
   }
 
   // Query //
@@ -288,14 +334,40 @@ impl Room {
   }
   /// Get the name of the room
   pub fn get_name(&self) -> String { self.name.clone() }
+  /// Get the track played in the room if it overrides the track of its area
+  pub fn get_music(&self) -> Option<&str> { self.music.as_deref() }
+}
+
+/// Check if a tile is drawn the same way for the lifetime of the room
+fn is_static(meta: &TileMeta) -> bool {
+  meta.animation.is_none()
+    && meta.breakability == TileBreakability::Solid
+    && meta.collectable.is_none()
+}
+
+/// Add the render layer of the depth of a tile layer to an entity, and its parallax aligned at the room `origin`
+fn add_presentation(world: &mut World, entity: Entity, presentation: &LayerPresentation, origin: Vec2<f32>) -> Result<(), String> {
+  if presentation.has_parallax() {
+    world.add_components(entity, (Parallax::new(presentation.parallax, origin), ))?;
+  }
+  match presentation.depth {
+    1 => world.add_components(entity, (layer::Layer1, )),
+    2 => world.add_components(entity, (layer::Layer2, )),
+    3 => world.add_components(entity, (layer::Layer3, )),
+    4 => world.add_components(entity, (layer::Layer4, )),
+    5 => world.add_components(entity, (layer::Layer5, )),
+    6 => world.add_components(entity, (layer::Layer6, )),
+    7 => world.add_components(entity, (layer::Layer7, )),
+    depth => Err(format!("Tiles cannot be drawn at depth {}", depth)),
+  }
 }
 
 /// Render rectangles around the colliders that start room transitions
-pub fn sys_render_room_colliders(SysArgs { world, render, camera, state, .. }: &mut SysArgs) -> Result<(), String> {
-  if !use_preferences(state).debug { return Ok(()); }
+pub fn sys_render_room_colliders(SysArgs { world, debug, .. }: &mut SysArgs) -> Result<(), String> {
+  let color = RGBA::new(0, 0, 255, OPAQUE);
   for (_, room_collider) in world.query::<&RoomCollider>() {
-    let pos = Vec2::<i32>::from(camera.translate(room_collider.collision_box.origin));
-    render.draw_rect(Rec2::new(pos, room_collider.collision_box.size), RGBA::new(0, 0, 255, OPAQUE));
+    debug.rect(DEBUG_ROOM, room_collider.collision_box, color);
+    debug.text(DEBUG_ROOM, room_collider.collision_box.origin, format!("{}", room_collider.room), color);
   }
   Ok(())
 }
@@ -311,15 +383,15 @@ pub fn use_room(state: &mut State) -> &mut Room {
     .expect("Failed to get current room")
 }
 
-/// Use the Room registries tileset
+/// Use a tileset of the Room registry by name
 /// ## Panics
-/// if the `RoomRegistry` not in state
-pub fn use_tileset(state: &mut State) -> &Tileset<TileMeta> {
+/// if the `RoomRegistry` not in state or the tileset does not exist
+pub fn use_tileset<'a>(state: &'a mut State, name: &str) -> &'a Tileset<TileMeta> {
   let registry = &mut state
     .get_mut::<LevelState>()
     .expect("Failed to get RoomRegistry")
     .room_registry;
   registry
-    .get_tileset(String::from("tileset"))
+    .get_tileset(name)
     .expect("Failed to get tileset")
 }
\ No newline at end of file
@@ -47,6 +47,8 @@ impl Default for RoomTransition {
 }
 
 impl RoomTransition {
+  /// Check if no transition is queued or in progress
+  pub fn is_idle(&self) -> bool { self.next.is_none() }
   /// Queue a transition to a room
   pub fn queue(&mut self, name: impl Into<RoomKey>) -> Result<(), String> {
     if self.next.is_some() {
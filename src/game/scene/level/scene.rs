@@ -5,15 +5,18 @@
 use std::collections::HashMap;
 use std::path::Path;
 
+use crate::engine::asset::manifest::AssetManifest;
 use crate::engine::component::animation::Animation;
+use crate::engine::component::animator::Animator;
 use crate::engine::core::lifecycle::LifecycleArgs;
 use crate::engine::core::scene::Scene;
 use crate::engine::ecs::system::{Schedule, SysArgs, Systemize, SystemTag};
 use crate::engine::geometry::shape::Vec2;
 use crate::game::constant::{DEV_SAVE_FILE, USER_SAVE_FILE, WORLD_PATH};
+use crate::game::music::MusicDirector;
 use crate::game::persistence::data::SaveData;
 use crate::game::persistence::world::{SaveArea, use_save_area};
-use crate::game::preferences::use_preferences;
+use crate::game::scene::level::animator::sys_animator_params;
 use crate::game::scene::level::collectable::collectable::Collection;
 use crate::game::scene::level::collectable::data::{CollectableData, deserialize_weapon_data};
 use crate::game::scene::level::combat::damage::Damage;
@@ -35,19 +38,25 @@ use crate::game::scene::level::physics::velocity::Velocity;
 use crate::game::scene::level::player::combat::PlayerCombat;
 use crate::game::scene::level::player::controller::PlayerController;
 use crate::game::scene::level::player::world::{make_player, PlayerQuery, use_player};
+use crate::game::scene::level::reload::HotReload;
 use crate::game::scene::level::room::collision::{RoomCollision, sys_render_tile_colliders};
 use crate::game::scene::level::room::meta::TileLayerType;
+use crate::game::scene::level::room::object::use_object_classes;
 use crate::game::scene::level::room::registry::RoomRegistry;
-use crate::game::scene::level::room::room::{RoomTileException, sys_render_room_colliders};
+use crate::game::scene::level::room::room::{make_room_manifest, RoomTileException, sys_render_room_colliders};
 use crate::game::scene::level::story::data::deserialize_story_data;
 use crate::game::scene::level::story::modal::sys_story_modal;
 use crate::game::scene::level::story::world::StoryArea;
+use crate::game::scene::level::tile::parse::{get_tileset_image, tilemap_objects};
 use crate::game::scene::level::tile::tiled::TiledParser;
 use crate::game::scene::level::ui::hud::{make_player_health_text, PlayerHealth};
+use crate::game::scene::level::ui::menu;
 use crate::game::scene::level::ui::menu::{make_menu, MenuPane};
+use crate::game::scene::level::{collectable, player, story};
 use crate::game::ui::cursor::Cursor;
 use crate::game::ui::iterative_text::IterativeText;
 use crate::game::utility::controls::{Behaviour, Control, is_control};
+use crate::game::utility::debug::{draw_texture_stats, use_debug_controls};
 
 pub const PHYSICS_SCHEDULE: Schedule = Schedule::FrameUpdate;
 // pub const PHYSICS_SCHEDULE: Schedule = Schedule::FixedUpdate;
@@ -55,26 +64,48 @@ pub const PHYSICS_SCHEDULE: Schedule = Schedule::FrameUpdate;
 pub struct LevelState {
   pub room_registry: RoomRegistry,
   pub weapon_data: CollectableData,
+  /// watches the files the level is built from in debug builds
+  pub reload: Option<HotReload>,
 }
 
 pub struct LevelScene {
   save_data: SaveData,
+  /// Tiled data parsed while listing the assets of the level
+  parser: Option<TiledParser>,
 }
 
 impl LevelScene {
   /// Build the level scene from the save data
-  pub fn new(save_data: SaveData) -> Self { Self { save_data } }
+  pub fn new(save_data: SaveData) -> Self { Self { save_data, parser: None } }
 }
 
 impl Scene for LevelScene {
+  /// List the assets of the player, the interface, the tilesets, and the creatures of every room
+  fn manifest(&mut self) -> Result<AssetManifest, String> {
+    let parser = TiledParser::parse(Path::new(WORLD_PATH)).map_err(|e| format!("Failed to parse Tiled data: {}", e))?;
+    let mut manifest = AssetManifest::default()
+      .with_manifest(player::world::manifest())
+      .with_manifest(menu::manifest())
+      .with_manifest(story::modal::manifest())
+      .with_manifest(collectable::modal::manifest());
+    for (path, tileset) in &parser.tilesets {
+      manifest = manifest.with_texture(get_tileset_image(path, tileset)?);
+    }
+    for tilemap in parser.tilemaps.values() {
+      manifest.extend(make_room_manifest(&tilemap_objects(tilemap, use_object_classes())?));
+    }
+    self.parser = Some(parser);
+    Ok(manifest)
+  }
+
   /// Set up the level scene
-  fn setup(&mut self, LifecycleArgs { world, camera, system, state, asset, .. }: &mut LifecycleArgs) {
+  fn setup(&mut self, LifecycleArgs { world, camera, system, state, asset, render, .. }: &mut LifecycleArgs) {
     let inventory = self.save_data.get_inventory();
     let exceptions = inventory
       .iter()
       .fold(HashMap::new(), |mut exceptions, item| {
         let name = item.room_name.clone();
-        let exception = RoomTileException::new(item.map_index, TileLayerType::Collision, None);
+        let exception = RoomTileException::new(item.map_index, TileLayerType::Collision, item.layer_name.clone(), None);
         exceptions.entry(name).or_insert_with(Vec::new).push(exception);
         exceptions
       });
@@ -84,15 +115,17 @@ impl Scene for LevelScene {
       .expect("Failed to load story data")
       .omit(&story_advancements);
 
-    let path = Path::new(WORLD_PATH);
-    let parser = TiledParser::parse(path)
+    let parser = self.parser
+      .take()
+      .map_or_else(|| TiledParser::parse(Path::new(WORLD_PATH)), Ok)
       .map_err(|e| eprintln!("Failed to parse Tiled data: {}", e))
       .expect("Failed to parse Tiled data");
+    let reload = if cfg!(debug_assertions) { Some(HotReload::new(&parser)) } else { None };
     let mut room_registry = RoomRegistry::build(parser, exceptions, story_data, asset, world).expect("Failed to build room registry");
 
     // load initial room
     let save_room = self.save_data.get_save_room();
-    room_registry.load_room(save_room, world, asset).expect("Failed to load save room");
+    room_registry.load_room(save_room, world, render, asset).expect("Failed to load save room");
 
     let save_position = use_save_area(world).collider.origin;
     let player_position = save_position + self.save_data.get_offset();
@@ -135,6 +168,8 @@ impl Scene for LevelScene {
 
     system.add_many(Schedule::PostUpdate, SystemTag::Scene, vec![
       Animation::system,
+      sys_animator_params,
+      Animator::system,
       RoomRegistry::system,
       LevelScene::system,
       MenuPane::system,
@@ -144,6 +179,8 @@ impl Scene for LevelScene {
       sys_render_colliders,
       sys_render_room_colliders,
       sys_render_tile_colliders,
+      HotReload::system,
+      MusicDirector::system,
     ].into_iter()).expect("Failed to add level systems");
 
     let weapon_data = deserialize_weapon_data().expect("Failed to load weapon data");
@@ -151,6 +188,7 @@ impl Scene for LevelScene {
     state.add(LevelState {
       room_registry,
       weapon_data,
+      reload,
     }).expect("Failed to add level state");
   }
   /// Clean up the level scene
@@ -162,7 +200,7 @@ impl Scene for LevelScene {
 
 /// Listen and respond to level events
 impl Systemize for LevelScene {
-  fn system(SysArgs { event, scene, asset, world, state, .. }: &mut SysArgs) -> Result<(), String> {
+  fn system(SysArgs { event, scene, asset, world, debug, camera, state, .. }: &mut SysArgs) -> Result<(), String> {
     let PlayerQuery { health, .. } = use_player(world);
 
     let dead = health.get_state() == LiveState::Dead;
@@ -178,13 +216,11 @@ impl Systemize for LevelScene {
     }
 
     if exit && !event.is_paused() {
-      make_menu(world, event, asset);
+      make_menu(world, event, asset, state);
     }
 
-    let preferences = use_preferences(state);
-    if is_control(Control::Debug, Behaviour::Pressed, event) {
-      preferences.debug = !preferences.debug;
-    }
+    use_debug_controls(event, debug);
+    draw_texture_stats(debug, camera, &asset.texture);
 
     Ok(())
   }
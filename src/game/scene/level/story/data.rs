@@ -6,12 +6,13 @@ use std::collections::HashSet;
 
 use serde::Deserialize;
 
-use crate::engine::utility::io::{deserialize_json, read_file};
+use crate::engine::asset::vfs::read_to_string;
+use crate::engine::utility::io::deserialize_json;
 
 pub const STORY_DATA_PATH: &str = "data/story.json";
 
 pub fn deserialize_story_data() -> Result<Story, String> {
-  let serialized = read_file(STORY_DATA_PATH)?;
+  let serialized = read_to_string(STORY_DATA_PATH)?;
   deserialize_json(&serialized)
 }
 
@@ -54,4 +55,24 @@ impl Story {
       .find(|entry| entry.key == key.clone().into())
       .cloned()
   }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::engine::utility::layout::parse_markup;
+
+  use super::*;
+
+  /// Story entries are shown as markup, so every entry must parse and keep its bracketed text
+  #[test]
+  fn test_story_markup() {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(STORY_DATA_PATH);
+    let story = deserialize_json::<Story>(&std::fs::read_to_string(path).unwrap()).unwrap();
+    for StoryItem { key, data, .. } in story.entries {
+      let paragraph = data.join("\n");
+      let spans = parse_markup(&paragraph).unwrap_or_else(|e| panic!("Story entry '{}' is not valid markup: {}", key, e));
+      let text = spans.iter().map(|span| span.text.as_str()).collect::<String>();
+      assert_eq!(text, paragraph.replace("[[", "["), "Story entry '{}' lost text to markup", key);
+    }
+  }
 }
\ No newline at end of file
@@ -1,12 +1,14 @@
 use std::collections::HashSet;
 
 use hecs::Entity;
+use serde::Deserialize;
 
 use crate::engine::component::position::Position;
 use crate::engine::ecs::system::{SysArgs, Systemize};
 use crate::engine::geometry::shape::Vec2;
 use crate::game::scene::level::physics::collision::{Collider, make_collision_box};
 use crate::game::scene::level::room::collision::{CollisionBox, CollisionMask, rec2_collision};
+use crate::game::scene::level::room::object::{ObjectClass, ObjectData, SpawnArgs};
 use crate::game::scene::level::story::data::{StoryItem, StoryKey};
 use crate::game::scene::level::story::modal::make_story_modal;
 
@@ -90,6 +92,24 @@ impl Systemize for StoryArea {
   }
 }
 
+/// Properties of a Story object in Tiled, an area that tells the story entry of the same name
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct StoryObject {}
+
+impl ObjectClass for StoryObject {
+  const CLASS: &'static str = "Story";
+  fn check(object: &ObjectData) -> Result<(), String> {
+    if object.name.is_none() { return Err(String::from("Story object must have a name")); }
+    object.get_rectangle().map(|_| ()).ok_or(String::from("Story area must be a rectangle with a width and height"))
+  }
+  /// Story areas whose entry has already been told are not added
+  fn spawn(&self, object: &ObjectData, SpawnArgs { world, story, .. }: &mut SpawnArgs) -> Result<Option<Entity>, String> {
+    let (Some(key), Some(bounds)) = (&object.name, object.get_rectangle()) else { return Err(String::from("Story area must be a named rectangle with a width and height")); };
+    Ok(story.get_entry(key.as_str()).map(|entry| world.add(make_story_area(entry, CollisionBox::new(bounds.origin, bounds.size)))))
+  }
+}
+
 /// Compose story area components from a save room and collision box
 pub fn make_story_area(entry: StoryItem, area: CollisionBox) -> StoryAreaBundle {
   (
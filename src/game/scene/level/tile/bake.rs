@@ -0,0 +1,98 @@
+/**
+ * Bake static tiles into chunk textures
+ */
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::engine::asset::texture::{SrcRect, TextureKey, TextureLoader};
+use crate::engine::component::sprite::Flip;
+use crate::engine::geometry::shape::Vec2;
+use crate::engine::render::renderer::Renderer;
+use crate::engine::utility::alias::{Coordinate, Size, Size2};
+
+/// Width and height of a baked chunk in tiles
+pub const BAKE_CHUNK_TILES: Size = 32;
+
+/// A tile to draw into a chunk texture
+struct BakeTile {
+  texture: TextureKey,
+  src: SrcRect,
+  position: Vec2<f32>,
+  rotation: f64,
+  flip: Flip,
+}
+
+/// A baked texture of static tiles on a layer
+#[derive(Clone, Debug)]
+pub struct BakedChunk<LayerMeta> {
+  pub layer: LayerMeta,
+  pub texture: TextureKey,
+  pub position: Vec2<f32>,
+  pub dimensions: Size2,
+}
+
+/// Collects static tiles of a tilemap by layer and chunk to bake into textures
+pub struct TileBake<LayerMeta> {
+  tile_size: Size2,
+  chunks: HashMap<(LayerMeta, Coordinate), Vec<BakeTile>>,
+}
+
+impl<LayerMeta> TileBake<LayerMeta> where LayerMeta: Copy + Eq + Hash {
+  /// Instantiate a new bake for tiles of `tile_size`
+  pub fn new(tile_size: Size2) -> Self {
+    Self { tile_size, chunks: HashMap::new() }
+  }
+
+  /// Queue a tile at `coordinate` to be baked into its layers chunk, mirrored by `flip` then rotated by `rotation`
+  pub fn add(&mut self, layer: LayerMeta, coordinate: Coordinate, position: Vec2<f32>, texture: TextureKey, src: SrcRect, (rotation, flip): (f64, Flip)) {
+    let chunk = Coordinate::new(
+      coordinate.x.div_euclid(BAKE_CHUNK_TILES as i32),
+      coordinate.y.div_euclid(BAKE_CHUNK_TILES as i32),
+    );
+    self.chunks
+      .entry((layer, chunk))
+      .or_default()
+      .push(BakeTile { texture, src, position, rotation, flip });
+  }
+
+  /// Check if no tiles have been queued
+  pub fn is_empty(&self) -> bool { self.chunks.is_empty() }
+
+  /// Draw the queued tiles into a texture for each chunk
+  ///
+  /// Chunk positions are relative to the tilemap origin
+  pub fn bake(self, render: &mut Renderer, textures: &mut TextureLoader) -> Result<Vec<BakedChunk<LayerMeta>>, String> {
+    let chunk_size = self.tile_size * BAKE_CHUNK_TILES;
+    let mut baked = Vec::with_capacity(self.chunks.len());
+
+    for ((layer, chunk), tiles) in self.chunks {
+      let position = Vec2::<f32>::from(chunk) * Vec2::<f32>::from(chunk_size);
+
+      // shrink the chunk to the extent of its tiles to avoid allocating empty space
+      let extent = tiles
+        .iter()
+        .fold(Vec2::<f32>::default(), |extent, tile| {
+          let tile_extent = tile.position - position + Vec2::from(tile.src.size);
+          Vec2::new(extent.x.max(tile_extent.x), extent.y.max(tile_extent.y))
+        });
+      let dimensions = Size2::from(extent);
+
+      let mut target = textures.build_target(dimensions)?;
+      let store = textures.use_store();
+      let copies = tiles
+        .iter()
+        .map(|tile| {
+          let texture = store.get(tile.texture)?;
+          Ok((texture, tile.src, Vec2::<i32>::from(tile.position - position), tile.rotation, tile.flip))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+      render.draw_to_texture(&mut target, copies.into_iter())?;
+
+      let texture = textures.add(target);
+      baked.push(BakedChunk { layer, texture, position, dimensions });
+    }
+
+    Ok(baked)
+  }
+}
@@ -5,3 +5,4 @@ pub mod tiled;
 pub mod parse;
 pub mod tilelayer;
 pub mod query;
+pub mod bake;
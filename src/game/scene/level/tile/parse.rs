@@ -3,24 +3,22 @@
  */
 
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use crate::engine::asset::asset::AssetManager;
 use crate::engine::utility::alias::Size2;
-use crate::engine::utility::text::{COMMA, strip_newlines};
-use crate::game::scene::level::room::meta::{ObjMeta, parse_animation, parse_breakability, parse_collectable, parse_collision_layer, parse_damage, parse_object, parse_tilelayer, TILED_TILE_CLASS, TileLayerType, TileMeta};
-use crate::game::scene::level::tile::tile::TileKey;
+use crate::engine::math::conversion::coordinate_to_index;
+use crate::engine::utility::direction::{Direction, HALF_DIRECTION_ROTATION, Rotation};
+use crate::game::scene::level::room::meta::{covers_side, get_tile_collision, parse_animation, parse_breakability, parse_collectable, parse_collision_layer, parse_damage, parse_layer_presentation, parse_tile_collision, parse_tilelayer, TILED_TILE_CLASS, TileLayerType, TileMeta};
+use crate::game::scene::level::room::object::{ObjMeta, ObjectRegistry};
+use crate::game::scene::level::tile::tile::{TileConcept, TileFlip, TileKey};
 use crate::game::scene::level::tile::tiled::{TiledObjectGroup, TiledTileLayer, TiledTilemap, TiledTilemapChildren, TiledTileset};
 use crate::game::scene::level::tile::tilelayer::TileLayer;
 use crate::game::scene::level::tile::tilemap::Tilemap;
-use crate::game::scene::level::tile::tileset::Tileset;
+use crate::game::scene::level::tile::tileset::{TilemapTilesets, Tileset};
 
-/// Delimiter for tile data in .Tiled tmx files
-const DELIMITER: char = COMMA;
-/// integer key for 'no tile' in Tiled .tmx files
+/// global tile id for 'no tile' in Tiled .tmx files
 const NULL_TILE: TileKey = 0;
-/// integer offset for tiles keys read from Tiled .tmx files
-const NULL_TILE_OFFSET: TileKey = 1;
 /// Indicates an infinite tilemap.
 const INFINITE_TILEMAP: u8 = 1;
 
@@ -35,52 +33,101 @@ pub fn tileset_meta_from_tiled(tiled_tileset: &TiledTileset) -> Result<HashMap<T
     let animation = parse_animation(tiled_tileset, &tile.animation)?;
     let collision_layer = parse_collision_layer(&tile.properties)?;
     let damage = parse_damage("damage", &tile.properties)?;
-    meta.insert(tile_key, TileMeta { breakability, animation, collectable, damage, collision_layer });
+    let collision = parse_tile_collision(&tile.object_group).map_err(|e| format!("Failed to parse tile {}: {}", tile_key, e))?;
+    meta.insert(tile_key, TileMeta { breakability, animation, collectable, damage, collision_layer, collision });
   }
   Ok(meta)
 }
 
-/// Build objects from a Tiled object group.
-pub fn tilemap_objects_from_tiled(group: &TiledObjectGroup) -> Result<Vec<ObjMeta>, String> {
+/// Build objects of the registered classes from a Tiled object group.
+pub fn tilemap_objects_from_tiled(group: &TiledObjectGroup, classes: &ObjectRegistry) -> Result<Vec<ObjMeta>, String> {
   if let Some(objects) = &group.objects {
     return objects
       .iter()
-      .map(parse_object)
+      .map(|object| classes.parse(object))
       .collect::<Result<Vec<_>, String>>();
   }
   Ok(vec![])
 }
 
+/// Build the objects of every object group in a Tiled tilemap.
+pub fn tilemap_objects(tiled_tilemap: &TiledTilemap, classes: &ObjectRegistry) -> Result<Vec<ObjMeta>, String> {
+  Ok(tiled_tilemap
+    .children
+    .iter()
+    .filter_map(|child| match child {
+      TiledTilemapChildren::ObjectLayer(child) => Some(tilemap_objects_from_tiled(child, classes)),
+      _ => None,
+    })
+    .collect::<Result<Vec<_>, _>>()?
+    .into_iter()
+    .flatten()
+    .collect())
+}
+
 /// Build a tilemap layer from a Tiled tilelayer.
-pub fn tilemap_layer_from_tiled(tileset: &Tileset<TileMeta>, tiled_tilelayer: &TiledTileLayer) -> Result<TileLayer<TileLayerType, TileMeta>, String> {
+pub fn tilemap_layer_from_tiled(tilesets: &TilemapTilesets<TileMeta>, tiled_tilelayer: &TiledTileLayer) -> Result<TileLayer<TileLayerType, TileMeta>, String> {
   let meta = parse_tilelayer(&tiled_tilelayer.properties)?;
-  let keys = make_tile_keys(&tiled_tilelayer.data.tiles, &DELIMITER);
+  let presentation = parse_layer_presentation(tiled_tilelayer, meta)
+    .map_err(|e| format!("Failed to parse layer '{}': {}", tiled_tilelayer.name, e))?;
+  let gids = tiled_tilelayer.data
+    .decode()
+    .map_err(|e| format!("Failed to decode layer '{}': {}", tiled_tilelayer.name, e))?
+    .into_iter()
+    .map(parse_tile_key)
+    .collect::<Vec<_>>();
   let dimensions = Size2::new(tiled_tilelayer.width_tiles, tiled_tilelayer.height_tiles);
-  let tiles: Vec<_> = tileset.tiledata_from(&keys, dimensions)?.collect();
-  Ok(TileLayer { meta, entities: HashMap::with_capacity(tiles.len()), tiles })
+  let mut tiles = tilesets.tiledata_from(&gids, dimensions)?;
+  open_partial_sides(&mut tiles, dimensions)?;
+  Ok(TileLayer { name: tiled_tilelayer.name.clone(), meta, presentation, entities: HashMap::with_capacity(tiles.len()), tiles })
 }
 
-/// Build an tileset from a Tiled tileset.
-pub fn tileset_from_tiled(assets: &mut AssetManager, path: impl AsRef<Path>, tiled_tileset: &TiledTileset) -> Result<Tileset<TileMeta>, String> {
+/// Open the sides of tiles that meet their neighbour along only part of the edge between them
+///
+/// A side is only closed when both tiles span the shared edge, otherwise the gap beside a partial tile could not be
+/// resolved against.
+fn open_partial_sides(tiles: &mut [Option<TileConcept<TileMeta>>], dimensions: Size2) -> Result<(), String> {
+  let collision = tiles
+    .iter()
+    .map(|tile| tile.as_ref().map(|tile| get_tile_collision(&tile.data)))
+    .collect::<Vec<_>>();
+  for (tile, boxes) in tiles.iter_mut().zip(&collision) {
+    let (Some(tile), Some(boxes)) = (tile, boxes) else { continue; };
+    for side in [Direction::Up, Direction::Right, Direction::Down, Direction::Left] {
+      let neighbour = tile.coordinate + side.to_coordinate();
+      if neighbour.x < 0 || neighbour.y < 0 || neighbour.x as u32 >= dimensions.x || neighbour.y as u32 >= dimensions.y { continue; }
+      let Some(neighbour_boxes) = &collision[coordinate_to_index(&neighbour, dimensions)] else { continue; };
+      let opposite = side.rotate(Rotation::Left, HALF_DIRECTION_ROTATION);
+      let size = tile.data.src.size;
+      if !covers_side(boxes, size, side) || !covers_side(neighbour_boxes, size, opposite) {
+        tile.mask.set_side(side, true)?;
+      }
+    }
+  }
+  Ok(())
+}
+
+/// Get the path of the image of a Tiled tileset
+pub fn get_tileset_image(path: impl AsRef<Path>, tiled_tileset: &TiledTileset) -> Result<PathBuf, String> {
+  let directory = path.as_ref().parent().ok_or("Failed to get tileset directory")?;
+  Ok(directory.join(&tiled_tileset.image.source))
+}
+
+/// Build a tileset named `name` from a Tiled tileset.
+pub fn tileset_from_tiled(assets: &mut AssetManager, name: &str, path: impl AsRef<Path>, tiled_tileset: &TiledTileset) -> Result<Tileset<TileMeta>, String> {
   let tile_size: Size2 = Size2::new(tiled_tileset.tile_width, tiled_tileset.tile_height);
   let dimensions = Size2::new(tiled_tileset.image.width, tiled_tileset.image.height);
 
   // load the tilesets texture
-  let file_path = path
-    .as_ref()
-    .parent()
-    .ok_or("Failed to get tileset directory")?
-    .to_path_buf()
-    .join(&tiled_tileset.image.source);
-  let texture_key = assets.texture.load(&file_path.into_boxed_path())?;
+  let texture_key = assets.texture.load(get_tileset_image(path, tiled_tileset)?)?;
 
   let meta = tileset_meta_from_tiled(tiled_tileset)?;
 
-  Tileset::build(texture_key, dimensions, tile_size, meta)
+  Tileset::build(name, texture_key, dimensions, tile_size, meta)
 }
 
-/// Build an tilemap from a Tiled tilemap.
-pub fn tilemap_from_tiled(tiled_tilemap: &TiledTilemap, tiled_tileset: &Tileset<TileMeta>) -> Result<Tilemap<TileMeta, TileLayerType, ObjMeta>, String> {
+/// Build an tilemap from a Tiled tilemap, the tilesets it references, and the classes of its objects.
+pub fn tilemap_from_tiled(tiled_tilemap: &TiledTilemap, tilesets: &TilemapTilesets<TileMeta>, classes: &ObjectRegistry) -> Result<Tilemap<TileMeta, TileLayerType, ObjMeta>, String> {
   if tiled_tilemap.infinite == INFINITE_TILEMAP {
     return Err(String::from("Infinite maps are not supported."));
   }
@@ -91,47 +138,24 @@ pub fn tilemap_from_tiled(tiled_tilemap: &TiledTilemap, tiled_tileset: &Tileset<
     .children
     .iter()
     .filter_map(|child| match child {
-      TiledTilemapChildren::TileLayer(layer) => Some(tilemap_layer_from_tiled(&tiled_tileset, layer)),
+      TiledTilemapChildren::TileLayer(layer) => Some(tilemap_layer_from_tiled(tilesets, layer)),
       _ => None
     })
     .collect::<Result<Vec<_>, _>>()?;
 
-  let objects = tiled_tilemap
-    .children
-    .iter()
-    .filter_map(|child| match child {
-      TiledTilemapChildren::ObjectLayer(child) => Some(tilemap_objects_from_tiled(child)),
-      _ => None,
-    })
-    .collect::<Result<Vec<_>, _>>()?
-    .into_iter()
-    .flatten()
-    .collect();
+  let objects = tilemap_objects(tiled_tilemap, classes)?;
 
-  let tilemap = Tilemap::build(tiled_tileset, dimensions, layers, objects)?;
+  let tilemap = Tilemap::build(tilesets.get_tile_size(), dimensions, layers, objects)?;
 
   return Ok(tilemap);
 }
 
-/// Convert csv tile data into a vector of tile keys.
-fn make_tile_keys(raw_data: &String, delimiter: &char) -> Vec<Option<TileKey>> {
-  raw_data
-    .split(*delimiter)
-    .map(parse_tile_key)
-    .collect::<Vec<_>>()
-}
-
-/// Parse a tile key from a string.
-fn parse_tile_key(key: &str) -> Option<TileKey> {
-  let key = strip_newlines(key)
-    .trim()
-    .parse::<TileKey>()
-    .unwrap_or(NULL_TILE);
-
-  return if key == NULL_TILE {
-    None
-  } else {
-    // 0 is reserved for 'no tile' in .tmx files, so we offset the key by 1
-    Some(key - NULL_TILE_OFFSET)
-  };
+/// Convert a decoded global tile id into a tile key and the flip encoded in its high bits.
+///
+/// Global ids are resolved to a tile of a tileset through the first global id of each tileset the tilemap references.
+fn parse_tile_key(gid: u32) -> Option<(TileKey, TileFlip)> {
+  let (flip, gid) = TileFlip::from_gid(gid);
+  let key = gid as TileKey;
+  // 0 is reserved for 'no tile' in .tmx files
+  if key == NULL_TILE { None } else { Some((key, flip)) }
 }
@@ -7,9 +7,10 @@ use crate::engine::geometry::shape::Vec2;
 
 use crate::engine::utility::alias::Coordinate;
 use crate::game::scene::level::tile::tile::TileConcept;
-use crate::game::scene::level::tile::tilemap::MapIndex;
+use crate::game::scene::level::tile::tilemap::{LayerIndex, MapIndex};
 
 /// Defines a generic query for a tile in a tilemap
+#[derive(Clone, Copy)]
 pub enum TileQuery {
   Position(Vec2<f32>),
   Coordinate(Coordinate),
@@ -24,6 +25,8 @@ pub struct TileQueryResult<'r, TileMeta, LayerMeta>
 {
   pub concept: Option<&'r TileConcept<TileMeta>>,
   pub layer: LayerMeta,
+  /// position of the layer in the tilemap
+  pub layer_index: LayerIndex,
   pub position: Vec2<f32>,
   pub coordinate: Coordinate,
   pub index: MapIndex,
@@ -35,6 +38,8 @@ pub struct TileHandle<TileMeta, LayerMeta> where TileMeta: Clone, LayerMeta: Cop
   /// Convert a tile query result into a non-owning handle of the tile queried
   pub concept: TileConcept<TileMeta>,
   pub layer: LayerMeta,
+  /// position of the layer in the tilemap
+  pub layer_index: LayerIndex,
   pub position: Vec2<f32>,
   pub coordinate: Coordinate,
   pub index: MapIndex,
@@ -49,6 +54,7 @@ impl<TileMeta, LayerMeta> TryFrom<TileQueryResult<'_, TileMeta, LayerMeta>> for
     Ok(TileHandle::<TileMeta, LayerMeta> {
       concept: result.concept.cloned().ok_or(String::from("Tile has no concept"))?,
       layer: result.layer,
+      layer_index: result.layer_index,
       position: result.position,
       coordinate: result.coordinate,
       index: result.index,
@@ -3,29 +3,101 @@
   */
 
 use crate::engine::asset::texture::{SrcRect, TextureKey};
-use crate::engine::utility::alias::Coordinate;
+use crate::engine::component::sprite::{Flip, Sprite};
+use crate::engine::geometry::shape::Vec2;
+use crate::engine::utility::alias::{Coordinate, Size2};
 use crate::engine::utility::store::Key;
 use crate::game::scene::level::room::collision::{CollisionBox, CollisionMask};
 
-/// A unique identifier for a tile
+/// A unique identifier for a tile within its tileset
 pub type TileKey = Key;
 
+/// Name of a tileset, the filename it was loaded from
+pub type TilesetKey = String;
+
+/// Bit of a Tiled global tile id set if the tile is flipped horizontally
+const FLIPPED_HORIZONTALLY: u32 = 0x80000000;
+/// Bit of a Tiled global tile id set if the tile is flipped vertically
+const FLIPPED_VERTICALLY: u32 = 0x40000000;
+/// Bit of a Tiled global tile id set if the tile is flipped across its top left to bottom right diagonal
+const FLIPPED_DIAGONALLY: u32 = 0x20000000;
+/// Bit of a Tiled global tile id set if a hexagonal tile is rotated, which does not apply to orthogonal maps
+const ROTATED_HEXAGONAL: u32 = 0x10000000;
+
+/// Mirroring of a placed tile, Tiled flips diagonally first, then horizontally, then vertically
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TileFlip {
+  pub horizontal: bool,
+  pub vertical: bool,
+  pub diagonal: bool,
+}
+
+impl TileFlip {
+  /// Split a Tiled global tile id into its flip flags and the id without them
+  pub fn from_gid(gid: u32) -> (Self, u32) {
+    let flip = Self {
+      horizontal: gid & FLIPPED_HORIZONTALLY != 0,
+      vertical: gid & FLIPPED_VERTICALLY != 0,
+      diagonal: gid & FLIPPED_DIAGONALLY != 0,
+    };
+    (flip, gid & !(FLIPPED_HORIZONTALLY | FLIPPED_VERTICALLY | FLIPPED_DIAGONALLY | ROTATED_HEXAGONAL))
+  }
+
+  /// Get the clockwise rotation in degrees and the sprite flip that draw the tile with the same mirroring
+  ///
+  /// A diagonal flip is a vertical flip followed by a quarter turn, and a quarter turn swaps horizontal and vertical
+  /// flips applied before it.
+  pub fn get_orientation(&self) -> (f64, Flip) {
+    if self.diagonal {
+      (90.0, Flip { horizontal: self.vertical, vertical: !self.horizontal })
+    } else {
+      (0.0, Flip { horizontal: self.horizontal, vertical: self.vertical })
+    }
+  }
+
+  /// Rotate and mirror a sprite of the tile about its center
+  pub fn apply(&self, sprite: &mut Sprite) {
+    let (rotation, flip) = self.get_orientation();
+    sprite.rotate(rotation, None);
+    sprite.flip(flip);
+  }
+
+  /// Mirror a rectangle within a tile of `size` the same way as the tile
+  pub fn apply_rect(&self, rect: &CollisionBox, size: Size2) -> CollisionBox {
+    let (mut origin, mut rect_size, mut size) = (rect.origin, rect.size, size);
+    if self.diagonal {
+      origin = Vec2::new(origin.y, origin.x);
+      rect_size = Vec2::new(rect_size.y, rect_size.x);
+      size = Vec2::new(size.y, size.x);
+    }
+    if self.horizontal { origin.x = size.x as f32 - origin.x - rect_size.x as f32; }
+    if self.vertical { origin.y = size.y as f32 - origin.y - rect_size.y as f32; }
+    CollisionBox::new(origin, rect_size)
+  }
+}
+
 /// Data to create a tile entity (Sprite + Tile)
 #[derive(Clone, Debug)]
 pub struct TileData<Meta> {
+  /// tileset the tile belongs to
+  pub tileset: TilesetKey,
   pub texture_key: TextureKey,
   pub src: SrcRect,
   pub tile_key: TileKey,
+  /// mirroring of the tile where it is placed
+  pub flip: TileFlip,
   pub meta: Meta,
 }
 
 impl<Meta> TileData<Meta> {
-  /// Instantiate a new tile data with `texture_key`, `src`, and `tile_key`
-  pub fn new(texture_key: TextureKey, src: SrcRect, tile_key: TileKey, meta: Meta) -> Self {
+  /// Instantiate a new tile data of `tileset` with `texture_key`, `src`, and `tile_key`
+  pub fn new(tileset: TilesetKey, texture_key: TextureKey, src: SrcRect, tile_key: TileKey, meta: Meta) -> Self {
     Self {
+      tileset,
       texture_key,
       src,
       tile_key,
+      flip: TileFlip::default(),
       meta,
     }
   }
@@ -63,16 +135,51 @@ impl Tile {
 
 /// A tile that can be collided with
 pub struct TileCollider {
-  pub collision_box: CollisionBox,
+  /// rectangles relative to the tile, each resolved with the same mask
+  pub collision_boxes: Vec<CollisionBox>,
   pub mask: CollisionMask,
 }
 
 impl TileCollider {
-  /// Instantiate a new tile collider with `collision_box` and `mask`
-  pub fn new(collision_box: CollisionBox, mask: CollisionMask) -> Self {
+  /// Instantiate a new tile collider with `collision_boxes` and `mask`
+  pub fn new(collision_boxes: Vec<CollisionBox>, mask: CollisionMask) -> Self {
     Self {
-      collision_box,
+      collision_boxes,
       mask,
     }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_flip_flags() {
+    let (flip, gid) = TileFlip::from_gid(0xA0000007);
+    assert_eq!(gid, 7, "flags are cleared from the id");
+    assert_eq!(flip, TileFlip { horizontal: true, vertical: false, diagonal: true });
+    assert_eq!(TileFlip::from_gid(7), (TileFlip::default(), 7));
+  }
+
+  #[test]
+  fn test_orientation() {
+    let flip = |horizontal, vertical, diagonal| TileFlip { horizontal, vertical, diagonal }.get_orientation();
+    assert_eq!(flip(true, true, false), (0.0, Flip { horizontal: true, vertical: true }));
+    assert_eq!(flip(false, false, true), (90.0, Flip { horizontal: false, vertical: true }), "transpose");
+    assert_eq!(flip(true, false, true), (90.0, Flip { horizontal: false, vertical: false }), "quarter turn clockwise");
+    assert_eq!(flip(false, true, true), (90.0, Flip { horizontal: true, vertical: true }), "quarter turn anticlockwise");
+  }
+
+  #[test]
+  fn test_flip_rect() {
+    let size = Size2::new(16, 16);
+    let ledge = CollisionBox::new(Vec2::new(0.0, 12.0), Size2::new(10, 4));
+    let flip = |horizontal, vertical, diagonal| TileFlip { horizontal, vertical, diagonal }.apply_rect(&ledge, size);
+    assert_eq!(flip(false, false, false), ledge);
+    assert_eq!(flip(true, false, false), CollisionBox::new(Vec2::new(6.0, 12.0), Size2::new(10, 4)));
+    assert_eq!(flip(false, true, false), CollisionBox::new(Vec2::new(0.0, 0.0), Size2::new(10, 4)));
+    assert_eq!(flip(false, false, true), CollisionBox::new(Vec2::new(12.0, 0.0), Size2::new(4, 10)), "transpose");
+    assert_eq!(flip(true, false, true), CollisionBox::new(Vec2::new(0.0, 0.0), Size2::new(4, 10)), "quarter turn clockwise");
+  }
+}
@@ -4,10 +4,17 @@
 
 use std::collections::HashMap;
 use std::fmt::Debug;
+use std::io::Read;
 use std::path::Path;
 
+use base64::prelude::{BASE64_STANDARD, Engine};
+use flate2::read::{GzDecoder, ZlibDecoder};
+use ruzstd::StreamingDecoder;
 use serde::Deserialize;
 
+use crate::engine::asset::vfs;
+use crate::engine::geometry::shape::Vec2;
+
 // Custom Properties //
 
 /// A single property child of a `TiledProperties`
@@ -38,12 +45,27 @@ pub struct TiledPoint;
 #[derive(Deserialize, Debug, PartialEq)]
 pub struct TiledEllipse;
 
+/// Points of a polygon or polyline object, relative to the position of the object
 #[derive(Deserialize, Debug, PartialEq)]
 pub struct TiledPolygon {
   #[serde(rename = "@points")]
   pub points: String,
 }
 
+impl TiledPolygon {
+  /// Parse the space separated `x,y` pairs of the points
+  pub fn get_points(&self) -> Result<Vec<Vec2<f32>>, String> {
+    self.points
+      .split_whitespace()
+      .map(|pair| {
+        let (x, y) = pair.split_once(',').ok_or(format!("Invalid point: {}", pair))?;
+        let parse = |value: &str| value.parse::<f32>().map_err(|_| format!("Invalid point: {}", pair));
+        Ok(Vec2::new(parse(x)?, parse(y)?))
+      })
+      .collect()
+  }
+}
+
 #[derive(Deserialize, Debug, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct TiledText {
@@ -61,7 +83,8 @@ pub struct TiledObject {
   pub gid: Option<u32>,
   #[serde(rename = "@name")]
   pub name: Option<String>,
-  #[serde(rename = "@type")]
+  /// class of the object, collision shapes of tiles have none
+  #[serde(rename = "@type", default)]
   pub object_type: String,
   #[serde(rename = "@x")]
   pub x: f32,
@@ -77,6 +100,7 @@ pub struct TiledObject {
   pub point: Option<TiledPoint>,
   pub ellipse: Option<TiledEllipse>,
   pub polygon: Option<TiledPolygon>,
+  pub polyline: Option<TiledPolygon>,
   pub text: Option<TiledText>,
 }
 
@@ -170,6 +194,10 @@ pub struct TiledTilesetTile {
   pub properties: Option<TiledProperties>,
 
   pub animation: Option<TiledAnimation>,
+
+  /// collision shapes drawn in the tile collision editor, relative to the tile
+  #[serde(rename = "objectgroup")]
+  pub object_group: Option<TiledObjectGroup>,
 }
 
 // Tilemap //
@@ -188,11 +216,53 @@ pub struct TiledTilesetReference {
 pub struct TiledLayerData {
   #[serde(rename = "@encoding")]
   pub encoding: String,
+  #[serde(rename = "@compression")]
+  pub compression: Option<String>,
 
   #[serde(rename = "$value")]
   pub tiles: String,
 }
 
+impl TiledLayerData {
+  /// Decode the global tile ids of the layer from CSV, or base64 with optional zlib, gzip, or zstd compression
+  pub fn decode(&self) -> Result<Vec<u32>, String> {
+    match (self.encoding.as_str(), self.compression.as_deref()) {
+      ("csv", None) => self.tiles
+        .split(',')
+        .map(|gid| gid.trim().parse::<u32>().map_err(|_| format!("Invalid tile id '{}'", gid.trim())))
+        .collect(),
+      ("base64", compression) => {
+        let encoded = self.tiles.split_whitespace().collect::<String>();
+        let bytes = BASE64_STANDARD.decode(encoded).map_err(|e| format!("Invalid base64 tile data: {}", e))?;
+        let bytes = decompress(&bytes, compression)?;
+        if bytes.len() % 4 != 0 { return Err(String::from("Tile data is not a whole number of tile ids")); }
+        Ok(bytes
+          .chunks_exact(4)
+          .map(|gid| u32::from_le_bytes([gid[0], gid[1], gid[2], gid[3]]))
+          .collect())
+      }
+      (encoding, Some(compression)) => Err(format!("Unsupported tile data encoding '{}' with '{}' compression", encoding, compression)),
+      (encoding, None) => Err(format!("Unsupported tile data encoding '{}'", encoding)),
+    }
+  }
+}
+
+/// Decompress base64 decoded tile data
+fn decompress(bytes: &[u8], compression: Option<&str>) -> Result<Vec<u8>, String> {
+  let mut decompressed = Vec::new();
+  let result = match compression {
+    None => return Ok(bytes.to_vec()),
+    Some("zlib") => ZlibDecoder::new(bytes).read_to_end(&mut decompressed),
+    Some("gzip") => GzDecoder::new(bytes).read_to_end(&mut decompressed),
+    Some("zstd") => StreamingDecoder::new(bytes)
+      .map_err(|e| format!("Invalid zstd tile data: {}", e))?
+      .read_to_end(&mut decompressed),
+    Some(compression) => return Err(format!("Unsupported tile data compression '{}'", compression)),
+  };
+  result.map_err(|e| format!("Failed to decompress {} tile data: {}", compression.unwrap_or_default(), e))?;
+  Ok(decompressed)
+}
+
 // A tile layer within a Tiled tilemap
 #[derive(Deserialize, Debug, PartialEq)]
 pub struct TiledTileLayer {
@@ -206,6 +276,12 @@ pub struct TiledTileLayer {
   pub width_tiles: u32,
   #[serde(rename = "@height")]
   pub height_tiles: u32,
+  #[serde(rename = "@opacity")]
+  pub opacity: Option<f32>,
+  #[serde(rename = "@parallaxx")]
+  pub parallax_x: Option<f32>,
+  #[serde(rename = "@parallaxy")]
+  pub parallax_y: Option<f32>,
 
   pub data: TiledLayerData,
   pub properties: Option<TiledProperties>,
@@ -220,6 +296,8 @@ pub enum TiledTilemapChildren {
   TileLayer(TiledTileLayer),
   #[serde(rename = "objectgroup")]
   ObjectLayer(TiledObjectGroup),
+  #[serde(rename = "properties")]
+  Properties(TiledProperties),
 }
 
 /// A Tiled tilemap .tmx file
@@ -248,6 +326,21 @@ pub struct TiledTilemap {
   pub children: Vec<TiledTilemapChildren>,
 }
 
+impl TiledTilemap {
+  /// Get a custom property of the map
+  pub fn get_property(&self, name: &str) -> Option<&str> {
+    self.children
+      .iter()
+      .filter_map(|child| match child {
+        TiledTilemapChildren::Properties(properties) => Some(properties),
+        _ => None,
+      })
+      .flat_map(|properties| properties.properties.iter())
+      .find(|property| property.name == name)
+      .map(|property| property.value.as_str())
+  }
+}
+
 // Parser //
 
 /// A parsed Tiled tilemap and a vector of tileset paths it references.
@@ -301,7 +394,7 @@ impl TiledParser {
 
   /// Parse a Tiled world file
   fn parse_world(world_file: &Path) -> Result<TiledWorld, String> {
-    let world_str = std::fs::read_to_string(world_file).map_err(|e| e.to_string())?;
+    let world_str = vfs::read_to_string(world_file)?;
     let world: TiledWorld = serde_json::from_str(&world_str).map_err(|e| e.to_string())?;
 
     Ok(world)
@@ -313,7 +406,7 @@ impl TiledParser {
     let tilemap_path = path.as_ref();
 
     // parse tilemap
-    let tilemap_str = std::fs::read_to_string(tilemap_path).map_err(|e| e.to_string())?;
+    let tilemap_str = vfs::read_to_string(tilemap_path)?;
     let tilemap: TiledTilemap = quick_xml::de::from_str(&tilemap_str).map_err(|e| e.to_string())?;
 
     // get tileset paths
@@ -348,9 +441,64 @@ impl TiledParser {
 
   /// Parse a Tiled tileset file
   fn parse_tileset(path: impl AsRef<Path>) -> Result<TiledTileset, String> {
-    let tileset_str = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let tileset_str = vfs::read_to_string(path)?;
     let tileset = quick_xml::de::from_str(&tileset_str).map_err(|e| e.to_string())?;
 
     Ok(tileset)
   }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// Global tile ids encoded in each fixture
+  const GIDS: [u32; 4] = [1, 0, 3, 2];
+
+  fn data(encoding: &str, compression: Option<&str>, tiles: &str) -> TiledLayerData {
+    TiledLayerData { encoding: String::from(encoding), compression: compression.map(String::from), tiles: String::from(tiles) }
+  }
+
+  #[test]
+  fn test_decode_encodings() {
+    assert_eq!(data("csv", None, "1,0,\n3,2").decode(), Ok(GIDS.to_vec()));
+    assert_eq!(data("base64", None, "\n   AQAAAAAAAAADAAAAAgAAAA==\n  ").decode(), Ok(GIDS.to_vec()));
+    assert_eq!(data("base64", Some("zlib"), "eJxjZIAAZiBmAmIAAEAABw==").decode(), Ok(GIDS.to_vec()));
+    assert_eq!(data("base64", Some("gzip"), "H4sIAAAAAAACA2NkgABmIGYCYgCsFVVmEAAAAA==").decode(), Ok(GIDS.to_vec()));
+    assert_eq!(data("base64", Some("zstd"), "KLUv/SQQgQAAAQAAAAAAAAADAAAAAgAAAIlttVA=").decode(), Ok(GIDS.to_vec()));
+  }
+
+  #[test]
+  fn test_decode_unsupported() {
+    assert!(data("csv", Some("zlib"), "1,0,3,2").decode().is_err(), "csv is never compressed");
+    assert!(data("base64", Some("lz4"), "AQAAAA==").decode().is_err(), "compression is unknown");
+    assert!(data("xml", None, "").decode().is_err(), "encoding is unknown");
+    assert!(data("base64", None, "AQAA").decode().is_err(), "data is not a whole number of ids");
+  }
+
+  #[test]
+  fn test_object_shapes() {
+    let group = quick_xml::de::from_str::<TiledObjectGroup>(r#"<objectgroup id="1" name="objects">
+      <object id="1" type="Zoomer" x="8" y="16"><point/></object>
+      <object id="2" type="Patrol" x="0" y="0"><polyline points="0,0 32,0 32,-16.5"/></object>
+      <object id="3" type="Trigger" x="0" y="0" width="32" height="16"><ellipse/></object>
+    </objectgroup>"#).unwrap();
+    let objects = group.objects.unwrap();
+    assert!(objects[0].point.is_some());
+    let points = objects[1].polyline.as_ref().unwrap().get_points().unwrap();
+    assert_eq!(points, vec![Vec2::new(0.0, 0.0), Vec2::new(32.0, 0.0), Vec2::new(32.0, -16.5)]);
+    assert!(objects[2].ellipse.is_some() && objects[2].polygon.is_none());
+    assert!(TiledPolygon { points: String::from("0,0 4") }.get_points().is_err(), "point has no y");
+  }
+
+  #[test]
+  fn test_tile_collision() {
+    let tile = quick_xml::de::from_str::<TiledTilesetTile>(r#"<tile id="4" type="Tile">
+      <objectgroup draworder="index" id="2">
+        <object id="1" x="0" y="8" width="16" height="8"/>
+      </objectgroup>
+    </tile>"#).unwrap();
+    let objects = tile.object_group.unwrap().objects.unwrap();
+    assert_eq!((objects[0].object_type.as_str(), objects[0].y, objects[0].height), ("", 8.0, Some(8.0)), "collision shapes have no class");
+  }
+}
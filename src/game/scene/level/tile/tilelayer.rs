@@ -5,15 +5,34 @@
 use std::collections::HashMap;
 
 use hecs::Entity;
+use crate::engine::geometry::shape::Vec2;
 use crate::game::scene::level::tile::tile::TileConcept;
 use crate::game::scene::level::tile::tilemap::MapIndex;
 
 /// A collection of tiles concepts used to build a tilemap
 pub type TileLayerData<TileMeta> = Vec<Option<TileConcept<TileMeta>>>;
 
-/// A layer of tiles in a tilemap
+/// How the tiles of a layer are drawn
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LayerPresentation {
+  /// render layer the tiles are drawn on, from 1 at the back to 7 at the front
+  pub depth: u8,
+  /// opacity in the unit interval
+  pub opacity: f32,
+  /// fraction of the camera movement the layer scrolls by on each axis
+  pub parallax: Vec2<f32>,
+}
+
+impl LayerPresentation {
+  /// Check if the layer scrolls at a different rate to the world
+  pub fn has_parallax(&self) -> bool { self.parallax != Vec2::new(1.0, 1.0) }
+}
+
+/// A named layer of tiles in a tilemap
 pub struct TileLayer<LayerMeta, TileMeta> where LayerMeta: Copy + Clone + Eq, TileMeta: Clone {
+  pub name: String,
   pub meta: LayerMeta,
+  pub presentation: LayerPresentation,
   pub tiles: TileLayerData<TileMeta>,
   pub entities: HashMap<MapIndex, Entity>,
-}
\ No newline at end of file
+}
@@ -13,12 +13,14 @@ use crate::engine::utility::alias::{Coordinate, Size2};
 use crate::engine::utility::direction::{Direction, DIRECTIONS, QUARTER_DIRECTION_ROTATION, Rotation};
 use crate::game::scene::level::tile::query::{TileHandle, TileQuery, TileQueryResult};
 use crate::game::scene::level::tile::tile::TileConcept;
-use crate::game::scene::level::tile::tilelayer::TileLayer;
-use crate::game::scene::level::tile::tileset::Tileset;
+use crate::game::scene::level::tile::tilelayer::{LayerPresentation, TileLayer};
 
 /// Index of a tile in a tilemap
 pub type MapIndex = usize;
 
+/// Index of a layer in a tilemap, layers are ordered back to front
+pub type LayerIndex = usize;
+
 /// Index of an object in a tilemap
 pub type ObjectIndex = usize;
 
@@ -34,7 +36,7 @@ pub enum TilemapMutation {
 /// Manages a grid of entities
 pub struct Tilemap<TileMeta, LayerMeta, ObjMeta> where TileMeta: Clone, LayerMeta: Copy + Clone + Hash + Eq + Default, ObjMeta: Clone {
   // store the data to build the tilemap
-  layers: HashMap<LayerMeta, TileLayer<LayerMeta, TileMeta>>,
+  layers: Vec<TileLayer<LayerMeta, TileMeta>>,
   tile_size: Size2,
   objects: Vec<Option<ObjMeta>>,
   object_entities: HashMap<ObjectIndex, Entity>,
@@ -42,21 +44,16 @@ pub struct Tilemap<TileMeta, LayerMeta, ObjMeta> where TileMeta: Clone, LayerMet
 }
 
 impl<TileMeta, LayerMeta, ObjMeta> Tilemap<TileMeta, LayerMeta, ObjMeta> where TileMeta: Clone + Default, LayerMeta: Copy + Clone + Hash + Eq + Default, ObjMeta: Clone + std::fmt::Debug {
-  /// Instantiate a new tilemap from with `dimensions`
-  pub fn build(tileset: &Tileset<TileMeta>, dimensions: Size2, layers: Vec<TileLayer<LayerMeta, TileMeta>>, objects: Vec<ObjMeta>) -> Result<Self, String> {
+  /// Instantiate a new tilemap of tiles of `tile_size` with `dimensions`
+  pub fn build(tile_size: Size2, dimensions: Size2, layers: Vec<TileLayer<LayerMeta, TileMeta>>, objects: Vec<ObjMeta>) -> Result<Self, String> {
     let object_count = objects.len();
     let tile_count = dimensions.square() as usize;
     for layer in &layers {
-      if layer.tiles.len() != tile_count { return Err(String::from("Layer tiles do not match dimensions")); }
+      if layer.tiles.len() != tile_count { return Err(format!("Layer '{}' tiles do not match dimensions", layer.name)); }
     }
 
-    let layers = layers
-      .into_iter()
-      .map(|layer| (layer.meta, layer))
-      .collect();
-
     Ok(Self {
-      tile_size: tileset.tile_size,
+      tile_size,
       objects: objects.into_iter().map(Some).collect(),
       dimensions,
       layers,
@@ -64,15 +61,15 @@ impl<TileMeta, LayerMeta, ObjMeta> Tilemap<TileMeta, LayerMeta, ObjMeta> where T
     })
   }
 
-  /// Add tiles to the world by invoking an injected add function on each concept
-  pub fn add_tiles(&mut self, mut add: impl FnMut(LayerMeta, &TileConcept<TileMeta>, Coordinate, Vec2<f32>) -> Result<Option<Entity>, String>) -> Result<(), String> {
+  /// Add tiles to the world by invoking an injected add function on each concept, layer by layer from back to front
+  pub fn add_tiles(&mut self, mut add: impl FnMut(LayerIndex, LayerMeta, &LayerPresentation, &TileConcept<TileMeta>, Coordinate, Vec2<f32>) -> Result<Option<Entity>, String>) -> Result<(), String> {
     let dimensions = self.dimensions;
-    for (meta, layer, ) in &mut self.layers {
+    for (layer_index, layer) in self.layers.iter_mut().enumerate() {
       for (index, tile) in layer.tiles.iter().enumerate() {
         if let Some(tile) = tile {
           let coordinate = index_to_coordinate(index, dimensions);
           let position = Vec2::<f32>::from(coordinate) * Vec2::from(tile.data.src.size);
-          if let Some(entity) = add(*meta, tile, coordinate, position)? {
+          if let Some(entity) = add(layer_index, layer.meta, &layer.presentation, tile, coordinate, position)? {
             layer.entities.insert(index, entity);
           }
         }
@@ -84,7 +81,7 @@ impl<TileMeta, LayerMeta, ObjMeta> Tilemap<TileMeta, LayerMeta, ObjMeta> where T
   /// Remove a tile concept from the session
   fn remove_tile_concept(&mut self, handle: &TileHandle<TileMeta, LayerMeta>) {
     self.layers
-      .get_mut(&handle.layer)
+      .get_mut(handle.layer_index)
       .expect("Invalid handle layer!")
       .tiles
       .get_mut(handle.index)
@@ -94,7 +91,7 @@ impl<TileMeta, LayerMeta, ObjMeta> Tilemap<TileMeta, LayerMeta, ObjMeta> where T
   /// Mutate a tile concept in the session
   fn mutate_tile_concept(&mut self, handle: &TileHandle<TileMeta, LayerMeta>, concept: TileConcept<TileMeta>) {
     self.layers
-      .get_mut(&handle.layer)
+      .get_mut(handle.layer_index)
       .expect("Invalid handle layer!")
       .tiles
       .get_mut(handle.index)
@@ -105,7 +102,7 @@ impl<TileMeta, LayerMeta, ObjMeta> Tilemap<TileMeta, LayerMeta, ObjMeta> where T
   pub fn remove_tile(&mut self, handle: &TileHandle<TileMeta, LayerMeta>, mut remove: impl FnMut(Entity), mutation: TilemapMutation) {
     if let Some(entity) = self
       .layers
-      .get_mut(&handle.layer)
+      .get_mut(handle.layer_index)
       .expect("layer does not exist")
       .entities
       .remove(&handle.index)
@@ -118,7 +115,7 @@ impl<TileMeta, LayerMeta, ObjMeta> Tilemap<TileMeta, LayerMeta, ObjMeta> where T
   pub fn for_neighbour(&mut self, handle: &TileHandle<TileMeta, LayerMeta>, mut repair: impl FnMut(&mut TileHandle<TileMeta, LayerMeta>, Direction), mutation: TilemapMutation) {
     let mut check = Direction::Up;
     for _ in 0..DIRECTIONS / 2 {
-      let check_result = self.query_tile(handle.layer, TileQuery::Coordinate(handle.coordinate + check.to_coordinate()));
+      let check_result = self.query_layer(handle.layer_index, TileQuery::Coordinate(handle.coordinate + check.to_coordinate()));
       if let Ok(mut handle) = TileHandle::try_from(check_result) {
         repair(&mut handle, check);
         if mutation == TilemapMutation::Session {
@@ -130,19 +127,32 @@ impl<TileMeta, LayerMeta, ObjMeta> Tilemap<TileMeta, LayerMeta, ObjMeta> where T
   }
   /// Remove tiles from the world by invoking an injected remove function on each entity
   pub fn remove_tiles(&mut self, mut remove: impl FnMut(Entity)) {
-    for layer in self.layers.values_mut() {
+    for layer in &mut self.layers {
       for (.., entity) in layer.entities.drain() { remove(entity); }
     }
   }
   /// get the dimensions of the tilemap in worldspace
   pub fn get_dimensions(&self) -> Size2 { self.dimensions * self.tile_size }
+  /// get the size of a tile in worldspace
+  pub fn get_tile_size(&self) -> Size2 { self.tile_size }
   /// get the size of the tilemap in tiles
   pub fn get_size(&self) -> Size2 { self.dimensions }
+  /// Get a layer of the tilemap
+  pub fn get_layer(&self, layer_index: LayerIndex) -> Option<&TileLayer<LayerMeta, TileMeta>> { self.layers.get(layer_index) }
+  /// Find the front-most layer of a type that matches a predicate
+  pub fn find_layer(&self, layer: LayerMeta, predicate: impl Fn(&TileLayer<LayerMeta, TileMeta>) -> bool) -> Option<LayerIndex> {
+    self.layers
+      .iter()
+      .enumerate()
+      .rev()
+      .find(|(.., tile_layer)| tile_layer.meta == layer && predicate(tile_layer))
+      .map(|(layer_index, ..)| layer_index)
+  }
   /// Get a tile at a coordinate
-  fn get_concept(&self, layer: LayerMeta, index: MapIndex) -> Option<&TileConcept<TileMeta>> {
+  fn get_concept(&self, layer_index: LayerIndex, index: MapIndex) -> Option<&TileConcept<TileMeta>> {
     self
       .layers
-      .get(&layer)
+      .get(layer_index)
       .and_then(|layer| {
         layer
           .tiles
@@ -184,46 +194,105 @@ impl<TileMeta, LayerMeta, ObjMeta> Tilemap<TileMeta, LayerMeta, ObjMeta> where T
     for (.., entity) in self.object_entities.drain() { remove(entity); }
   }
 
-  /// Query for a tile concept
+  /// Query for a tile concept on the layers of a type
   ///
-  /// Returns a mutable reference to the tile concept
-  #[inline]
+  /// Layers are searched from front to back, returning the first tile found, or the result of the front layer if
+  /// no layer of the type has a tile
   pub fn query_tile(&self, layer: LayerMeta, get: TileQuery) -> TileQueryResult<TileMeta, LayerMeta> {
+    let mut results = self.layers
+      .iter()
+      .enumerate()
+      .rev()
+      .filter(|(.., tile_layer)| tile_layer.meta == layer)
+      .map(|(layer_index, ..)| self.query_layer(layer_index, get));
+    let Some(front) = results.next() else { return TileQueryResult { layer, ..Default::default() }; };
+    if front.concept.is_some() { return front; }
+    results.find(|result| result.concept.is_some()).unwrap_or(front)
+  }
+  /// Query for a tile concept on a single layer
+  fn query_layer(&self, layer_index: LayerIndex, get: TileQuery) -> TileQueryResult<'_, TileMeta, LayerMeta> {
     match get {
       TileQuery::Entity(entity) => {
         if let Some((index, ..)) = self
           .layers
-          .get(&layer)
+          .get(layer_index)
           .and_then(|layer| {
             layer.entities.iter().find(|(_, e)| **e == entity)
           })
         {
-          self.query_tile(layer, TileQuery::Index(*index))
+          self.query_layer(layer_index, TileQuery::Index(*index))
         } else {
           TileQueryResult::default()
         }
       }
       TileQuery::Position(position) => {
         let coordinate = position_to_coordinate(position, self.tile_size);
-        self.query_tile(layer, TileQuery::Coordinate(coordinate))
+        self.query_layer(layer_index, TileQuery::Coordinate(coordinate))
       }
       TileQuery::Coordinate(coordinate) => {
         let index = coordinate_to_index(&coordinate, self.dimensions);
-        self.query_tile(layer, TileQuery::Index(index))
+        self.query_layer(layer_index, TileQuery::Index(index))
       }
       TileQuery::Index(index) => {
-        let concept = self.get_concept(layer, index);
-        let entity = self.layers
-          .get(&layer)
-          .and_then(|layer| {
-            layer.entities.get(&index).copied()
-          });
+        let layer = self.layers.get(layer_index);
+        let concept = self.get_concept(layer_index, index);
+        let entity = layer.and_then(|layer| layer.entities.get(&index).copied());
 
         let coordinate = index_to_coordinate(index, self.dimensions);
         let position = Vec2::<f32>::from(coordinate) * Vec2::<f32>::from(self.tile_size);
-        TileQueryResult { layer, concept, entity, coordinate, position, index }
+        let layer = layer.map(|layer| layer.meta).unwrap_or_default();
+        TileQueryResult { layer, layer_index, concept, entity, coordinate, position, index }
       }
     }
   }
 }
 
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::engine::geometry::shape::Rec2;
+  use crate::game::scene::level::room::collision::CollisionMask;
+  use crate::game::scene::level::tile::tile::{TileData, TileKey};
+
+  #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+  enum Kind {
+    #[default]
+    Back,
+    Front,
+  }
+
+  /// Build a layer two tiles wide with a tile of `key` at each index of `tiles`
+  fn layer(name: &str, meta: Kind, tiles: [Option<TileKey>; 2]) -> TileLayer<Kind, ()> {
+    let tiles = tiles
+      .iter()
+      .enumerate()
+      .map(|(x, key)| key.map(|key| {
+        let data = TileData::new(String::from("tileset"), 0, Rec2::new(Vec2::default(), Size2::new(16, 16)), key, ());
+        TileConcept::new(data, Coordinate::new(x as i32, 0), CollisionMask::default())
+      }))
+      .collect();
+    let presentation = LayerPresentation { depth: 4, opacity: 1.0, parallax: Vec2::new(1.0, 1.0) };
+    TileLayer { name: String::from(name), meta, presentation, tiles, entities: HashMap::new() }
+  }
+
+  #[test]
+  fn test_layers_of_a_type() {
+    let layers = vec![
+      layer("far", Kind::Back, [Some(1), Some(2)]),
+      layer("near", Kind::Back, [Some(3), None]),
+      layer("front", Kind::Front, [None, None]),
+    ];
+    let tilemap = Tilemap::<(), Kind, ()>::build(Size2::new(16, 16), Size2::new(2, 1), layers, Vec::new()).unwrap();
+
+    let result = tilemap.query_tile(Kind::Back, TileQuery::Index(0));
+    assert_eq!((result.layer_index, result.concept.map(|tile| tile.data.tile_key)), (1, Some(3)), "front layer of the type wins");
+    let result = tilemap.query_tile(Kind::Back, TileQuery::Index(1));
+    assert_eq!((result.layer_index, result.concept.map(|tile| tile.data.tile_key)), (0, Some(2)), "layers of the type do not overwrite each other");
+    let result = tilemap.query_tile(Kind::Front, TileQuery::Index(1));
+    assert_eq!((result.layer_index, result.layer, result.concept.is_some()), (2, Kind::Front, false));
+    assert_eq!(tilemap.get_layer(1).map(|layer| layer.name.as_str()), Some("near"));
+    assert_eq!(tilemap.find_layer(Kind::Back, |layer| layer.name == "far"), Some(0));
+    assert_eq!(tilemap.find_layer(Kind::Back, |layer| layer.tiles[1].is_some()), Some(0), "front-most match of the type");
+    assert_eq!(tilemap.find_layer(Kind::Front, |layer| layer.name == "far"), None, "layer is of another type");
+  }
+}
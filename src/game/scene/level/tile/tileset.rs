@@ -10,10 +10,10 @@ use crate::engine::math::conversion::{coordinate_to_index, index_to_coordinate};
 use crate::engine::utility::alias::{Coordinate, Size2};
 use crate::engine::utility::direction::Direction;
 use crate::game::scene::level::room::collision::CollisionMask;
-use crate::game::scene::level::tile::tile::{TileConcept, TileData, TileKey};
+use crate::game::scene::level::tile::tile::{TileConcept, TileData, TileFlip, TileKey, TilesetKey};
 
 /// builds up the tile data for `dimensions` using `tile_size`
-fn make_tiles<Meta>(texture_key: TextureKey, dimensions: Size2, tile_size: Size2, meta: HashMap<TileKey, Meta>) -> Result<Vec<TileData<Meta>>, &'static str> where Meta: Clone {
+fn make_tiles<Meta>(name: &TilesetKey, texture_key: TextureKey, dimensions: Size2, tile_size: Size2, meta: HashMap<TileKey, Meta>) -> Result<Vec<TileData<Meta>>, &'static str> where Meta: Clone {
   let (width, height) = dimensions.destructure();
   if width % tile_size.x != 0 {
     return Err("Tileset width must be divisible by tile size");
@@ -23,7 +23,7 @@ fn make_tiles<Meta>(texture_key: TextureKey, dimensions: Size2, tile_size: Size2
   }
 
   let mut tiles = Vec::new();
-  for y in 0..width / tile_size.y {
+  for y in 0..height / tile_size.y {
     for x in 0..width / tile_size.x {
       let tile_key: TileKey = (y * (width / tile_size.x) + x) as TileKey;
       let tile_position = Vec2::new(x, y) * tile_size;
@@ -32,31 +32,39 @@ fn make_tiles<Meta>(texture_key: TextureKey, dimensions: Size2, tile_size: Size2
         .get(&tile_key)
         .cloned()
         .ok_or("Failed to get tile meta")?;
-      tiles.push(TileData::new(texture_key, src, tile_key, meta));
+      tiles.push(TileData::new(name.clone(), texture_key, src, tile_key, meta));
     }
   }
 
   Ok(tiles)
 }
 
+/// Name of the tileset barrier tiles belong to, and collectable icons are drawn from by default
+pub const DEFAULT_TILESET: &str = "tileset";
+
 const PLAYER_BARRIER_TILE: TileKey = 26;
 const CREATURE_BARRIER_TILE: TileKey = 27;
 
 /// Check if a tile is a barrier tile
-fn is_barrier_tile(tile: &TileKey) -> bool { *tile == PLAYER_BARRIER_TILE || *tile == CREATURE_BARRIER_TILE }
+fn is_barrier_tile<Meta>(tile: &TileData<Meta>) -> bool {
+  tile.tileset == DEFAULT_TILESET && (tile.tile_key == PLAYER_BARRIER_TILE || tile.tile_key == CREATURE_BARRIER_TILE)
+}
 
 /// Wrapper for a texture that contains tiles
 pub struct Tileset<Meta> {
+  pub name: TilesetKey,
   pub texture: TextureKey,
   pub tile_size: Size2,
   pub tiles: Vec<TileData<Meta>>,
 }
 
 impl<Meta> Tileset<Meta> where Meta: Clone {
-  /// Instantiate a new tileset from a `texture` with `tile_size`
-  pub fn build(texture: TextureKey, dimensions: Size2, tile_size: Size2, meta: HashMap<TileKey, Meta>) -> Result<Self, String> {
-    let tiles = make_tiles(texture, dimensions, tile_size, meta)?;
+  /// Instantiate a new tileset named `name` from a `texture` with `tile_size`
+  pub fn build(name: impl Into<TilesetKey>, texture: TextureKey, dimensions: Size2, tile_size: Size2, meta: HashMap<TileKey, Meta>) -> Result<Self, String> {
+    let name = name.into();
+    let tiles = make_tiles(&name, texture, dimensions, tile_size, meta)?;
     Ok(Self {
+      name,
       texture,
       tile_size,
       tiles,
@@ -70,33 +78,65 @@ impl<Meta> Tileset<Meta> where Meta: Clone {
       .ok_or("Failed to get tile data")?;
     Ok(data.clone())
   }
+}
+
+/// The tilesets a tilemap references, each covering the global tile ids from its first global id
+pub struct TilemapTilesets<'a, Meta> {
+  /// tilesets ordered by first global id
+  tilesets: Vec<(TileKey, &'a Tileset<Meta>)>,
+}
+
+impl<'a, Meta> TilemapTilesets<'a, Meta> where Meta: Clone {
+  /// Instantiate the tilesets of a tilemap from their first global ids, which must share a tile size
+  pub fn build(mut tilesets: Vec<(TileKey, &'a Tileset<Meta>)>) -> Result<Self, String> {
+    tilesets.sort_by_key(|(first_gid, ..)| *first_gid);
+    let (.., first) = tilesets.first().ok_or("No tileset found")?;
+    if let Some((.., tileset)) = tilesets.iter().find(|(.., tileset)| tileset.tile_size != first.tile_size) {
+      return Err(format!("Tileset {} does not share the tile size of tileset {}", tileset.name, first.name));
+    }
+    Ok(Self { tilesets })
+  }
+
+  /// Get the size of the tiles of every tileset
+  pub fn get_tile_size(&self) -> Size2 { self.tilesets[0].1.tile_size }
+
+  /// Get the tile data of a global tile id
+  pub fn get_tile(&self, gid: TileKey) -> Result<TileData<Meta>, String> {
+    let (first_gid, tileset) = self.tilesets
+      .iter()
+      .rev()
+      .find(|(first_gid, ..)| *first_gid <= gid)
+      .ok_or(format!("No tileset contains tile {}", gid))?;
+    tileset
+      .get_tile(gid - first_gid)
+      .map_err(|_| format!("Tileset {} does not contain tile {}", tileset.name, gid - first_gid))
+  }
 
-  /// Convert a collection of `tile_keys` to `TileData`
-  pub fn tiledata_from<'a>(&'a self, tile_data: &Vec<Option<TileKey>>, dimensions: Size2) -> Result<impl Iterator<Item=Option<TileConcept<Meta>>> + 'a, String> {
-    let result = tile_data
+  /// Convert a collection of global tile ids and their flips to `TileConcept`s
+  pub fn tiledata_from(&self, gids: &[Option<(TileKey, TileFlip)>], dimensions: Size2) -> Result<Vec<Option<TileConcept<Meta>>>, String> {
+    let tiles = gids
+      .iter()
+      .map(|gid| gid.map(|(gid, flip)| self.get_tile(gid).map(|data| TileData { flip, ..data })).transpose())
+      .collect::<Result<Vec<_>, _>>()?;
+
+    let concepts = tiles
       .iter()
       .enumerate()
-      .map(|(index, tile_key)| {
-        if let Some(tile_key) = tile_key {
-          let data = self.get_tile(*tile_key as usize)?;
-          let coordinate = index_to_coordinate(index, dimensions);
-          let borders_barrier = is_barrier_tile(tile_key);
-          let mask = CollisionMask::new(
-            empty_neighbour(tile_data, borders_barrier, coordinate, Direction::Up, &dimensions),
-            empty_neighbour(tile_data, borders_barrier, coordinate, Direction::Right, &dimensions),
-            empty_neighbour(tile_data, borders_barrier, coordinate, Direction::Down, &dimensions),
-            empty_neighbour(tile_data, borders_barrier, coordinate, Direction::Left, &dimensions),
-          );
-          let concept = TileConcept::new(data.clone(), coordinate, mask.clone());
-          Ok::<Option<TileConcept<Meta>>, String>(Some(concept))
-        } else {
-          Ok(None)
-        }
+      .map(|(index, data)| {
+        let data = data.as_ref()?;
+        let coordinate = index_to_coordinate(index, dimensions);
+        let borders_barrier = is_barrier_tile(data);
+        let mask = CollisionMask::new(
+          empty_neighbour(&tiles, borders_barrier, coordinate, Direction::Up, &dimensions),
+          empty_neighbour(&tiles, borders_barrier, coordinate, Direction::Right, &dimensions),
+          empty_neighbour(&tiles, borders_barrier, coordinate, Direction::Down, &dimensions),
+          empty_neighbour(&tiles, borders_barrier, coordinate, Direction::Left, &dimensions),
+        );
+        Some(TileConcept::new(data.clone(), coordinate, mask))
       })
-      .collect::<Result<Vec<_>, _>>()?
-      .into_iter();
+      .collect();
 
-    Ok(result)
+    Ok(concepts)
   }
 }
 
@@ -104,11 +144,41 @@ impl<Meta> Tileset<Meta> where Meta: Clone {
 /// Check if a neighboring tile at `coordinate` is valid and contains a tile
 ///
 /// Non-barrier tiles are considered empty to barrier tiles, and vice versa.
-fn empty_neighbour(data: &Vec<Option<TileKey>>, barrier_border: bool, coordinate: Coordinate, direction: Direction, dimensions: &Size2) -> bool {
+fn empty_neighbour<Meta>(data: &[Option<TileData<Meta>>], barrier_border: bool, coordinate: Coordinate, direction: Direction, dimensions: &Size2) -> bool {
   let neighbour = coordinate + direction.to_coordinate();
   let index = coordinate_to_index(&neighbour, *dimensions);
   if let Some(Some(tile)) = data.get(index) {
     return barrier_border != is_barrier_tile(tile);
   }
   true
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn tileset(name: &str, texture: TextureKey, tiles: usize) -> Tileset<()> {
+    let meta = (0..tiles).map(|tile| (tile as TileKey, ())).collect();
+    Tileset::build(name, texture, Size2::new(16 * tiles as u32, 16), Size2::new(16, 16), meta).unwrap()
+  }
+
+  #[test]
+  fn test_resolve_gids() {
+    let (terrain, props) = (tileset("terrain", 1, 4), tileset("props", 2, 2));
+    let tilesets = TilemapTilesets::build(vec![(5, &props), (1, &terrain)]).unwrap();
+
+    let tile = tilesets.get_tile(4).unwrap();
+    assert_eq!((tile.tileset.as_str(), tile.tile_key), ("terrain", 3));
+    let tile = tilesets.get_tile(6).unwrap();
+    assert_eq!((tile.tileset.as_str(), tile.tile_key, tile.texture_key), ("props", 1, 2), "gid is offset by the first gid of its tileset");
+    assert!(tilesets.get_tile(7).is_err(), "gid is past the last tileset");
+  }
+
+  #[test]
+  fn test_tile_size() {
+    let terrain = tileset("terrain", 1, 4);
+    let large = Tileset::build("large", 2, Size2::new(32, 32), Size2::new(32, 32), HashMap::from([(0, ())])).unwrap();
+    assert!(TilemapTilesets::build(vec![(1, &terrain), (5, &large)]).is_err(), "tilesets must share a tile size");
+    assert!(TilemapTilesets::<()>::build(Vec::new()).is_err(), "tilemap has no tileset");
+  }
+}
@@ -1,8 +1,10 @@
 use crate::engine::asset::asset::AssetManager;
+use crate::engine::asset::manifest::AssetManifest;
 use crate::engine::core::event::EventStore;
 use crate::engine::ecs::world::World;
 use crate::engine::utility::alias::Size2;
 use crate::engine::utility::alignment::{Align, Alignment};
+use crate::engine::utility::state::State;
 use crate::game::scene::level::ui::help::parse::load_help_data;
 use crate::game::ui::modal::{make_modal, Modal};
 
@@ -13,9 +15,14 @@ const MODAL_MARGIN: f32 = 8.0;
 const MODAL_CONTENT_START_Y: f32 = 32.0;
 const MODAL_HELP_LINE_HEIGHT: f32 = 16.0;
 
-pub fn make_help_modal(world: &mut World, events: &mut EventStore, asset: &mut AssetManager) {
+/// List the assets of the help modal
+pub fn manifest() -> AssetManifest {
+  AssetManifest::default().with_texture(MODAL_BACKGROUND)
+}
+
+pub fn make_help_modal(world: &mut World, events: &mut EventStore, asset: &mut AssetManager, state: &mut State) {
   let background = asset.texture.load(MODAL_BACKGROUND).expect("Failed to load collectable modal background");
-  let (.., mut builder) = make_modal(world, events, asset, String::from(MODAL_TITLE), MODAL_SIZE, background);
+  let (.., mut builder) = make_modal(world, events, asset, state, String::from(MODAL_TITLE), MODAL_SIZE, background);
 
   let data = load_help_data().expect("Failed to load help data");
 
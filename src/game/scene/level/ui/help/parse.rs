@@ -1,12 +1,12 @@
 use std::path::Path;
 
-use crate::engine::utility::io::read_file;
+use crate::engine::asset::vfs::read_to_string;
 
 const HELP_FILE: &str = "data/help.txt";
 
 /// Load the help data from the help file
 pub fn load_help_data() -> Result<Vec<String>, String> {
-  let contents = read_file(Path::new(HELP_FILE))?;
+  let contents = read_to_string(Path::new(HELP_FILE))?;
 
   Ok(contents
     .lines()
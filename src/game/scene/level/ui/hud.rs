@@ -13,6 +13,7 @@ use crate::engine::utility::color::color;
 use crate::game::constant::WINDOW;
 use crate::game::scene::level::player::world::{PlayerQuery, use_player};
 use crate::game::ui::text_builder::make_text;
+use crate::game::ui::typography::numeral_font;
 
 #[derive(Default)]
 pub struct PlayerHealth;
@@ -20,12 +21,9 @@ pub struct PlayerHealth;
 pub fn make_player_health_text(world: &mut World, asset: &mut AssetManager) {
   let PlayerQuery { health, .. } = use_player(world);
   let text = format!("{}", health);
-  let font = asset.typeface
-    .use_store()
-    .get("typeface")
-    .expect("Failed to get typeface");
+  let alignment = Alignment::new(Align::End(8.0), Align::Start(8.0));
   world.add(
-    make_text::<PlayerHealth, Sticky2>(text, Alignment::new(Align::End(8.0), Align::Start(8.0)), &WINDOW, color::TEXT, font, &mut asset.texture)
+    make_text::<PlayerHealth, Sticky2>(text, alignment, &WINDOW, color::TEXT, Some(numeral_font()), &asset.typeface, &mut asset.texture)
   );
 }
 
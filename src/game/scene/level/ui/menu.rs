@@ -3,14 +3,19 @@
  */
 
 use crate::engine::asset::asset::AssetManager;
+use crate::engine::asset::manifest::AssetManifest;
 use crate::engine::component::animation::Animation;
 use crate::engine::core::event::EventStore;
 use crate::engine::ecs::system::{SysArgs, Systemize};
 use crate::engine::ecs::world::World;
 use crate::engine::utility::alias::Size2;
 use crate::engine::utility::alignment::{Align, Alignment};
+use crate::engine::utility::state::State;
+use crate::game::scene::level::ui::help::modal;
 use crate::game::scene::level::ui::help::modal::make_help_modal;
 use crate::game::scene::main_menu::scene::MenuScene;
+use crate::game::sound::{SoundEvent, use_sounds};
+use crate::game::ui::cursor;
 use crate::game::ui::cursor::make_cursor;
 use crate::game::ui::modal::{close_modal, make_modal, Modal, use_escape_modal};
 use crate::game::ui::selection::Selection;
@@ -23,16 +28,24 @@ pub const PANE_DIMENSIONS: Size2 = Size2::new(128, 93);
 pub const PANE_LEFT_MARGIN: f32 = 24.0;
 pub const BUTTONS_START_Y: f32 = 24.0;
 pub const BUTTONS_GAP_Y: f32 = 16.0;
+pub const PANE_BACKGROUND: &str = "asset/hud/menu_pane.png";
+
+/// List the assets of the in-game menu and the modals it opens
+pub fn manifest() -> AssetManifest {
+  AssetManifest::default()
+    .with_texture(PANE_BACKGROUND)
+    .with_manifest(cursor::manifest())
+    .with_manifest(modal::manifest())
+}
 
 /// Add the in-game menu UI to the world
-pub fn make_menu(world: &mut World, event: &mut EventStore, asset: &mut AssetManager) {
+pub fn make_menu(world: &mut World, event: &mut EventStore, asset: &mut AssetManager, state: &mut State) {
   let textures = &mut asset.texture;
   let background = textures
-    .load("asset/hud/menu_pane.png")
+    .load(PANE_BACKGROUND)
     .expect("Failed to load pane texture");
-  let cursor_texture = textures.load("asset/hud/cursor.png").expect("Failed to load cursor texture");
 
-  let (.., mut builder) = make_modal(world, event, asset, String::from("Menu"), PANE_DIMENSIONS, background);
+  let (.., mut builder) = make_modal(world, event, asset, state, String::from("Menu"), PANE_DIMENSIONS, background);
 
   let buttons = [
     world.add(builder.make_text::<Modal>("resume", Alignment::new(Align::Start(PANE_LEFT_MARGIN), Align::Start(BUTTONS_START_Y)))),
@@ -41,7 +54,7 @@ pub fn make_menu(world: &mut World, event: &mut EventStore, asset: &mut AssetMan
     world.add(builder.make_text::<Modal>("quit", Alignment::new(Align::Start(PANE_LEFT_MARGIN), Align::Start(BUTTONS_START_Y + BUTTONS_GAP_Y * 3.0)))),
   ];
 
-  let cursor = make_cursor::<Modal>(world, cursor_texture, asset);
+  let cursor = make_cursor::<Modal>(world, asset);
 
   world.add((
     Selection::build(buttons, cursor).expect("Failed to build selection"),
@@ -51,7 +64,7 @@ pub fn make_menu(world: &mut World, event: &mut EventStore, asset: &mut AssetMan
 }
 
 impl Systemize for MenuPane {
-  fn system(SysArgs { world, scene, event, asset, .. }: &mut SysArgs) -> Result<(), String> {
+  fn system(SysArgs { world, scene, event, asset, state, .. }: &mut SysArgs) -> Result<(), String> {
     if !event.is_paused() { return Ok(()); };
 
     use_escape_modal(world, event);
@@ -63,6 +76,7 @@ impl Systemize for MenuPane {
       let down = is_control(Control::Down, Behaviour::Pressed, event);
       let delta = if up { -1 } else if down { 1 } else { 0 };
       *menu += delta;
+      if delta != 0 { use_sounds(state).play(SoundEvent::MenuMove); }
 
       let select = is_control(Control::Select, Behaviour::Pressed, event);
       if select {
@@ -73,7 +87,7 @@ impl Systemize for MenuPane {
           }
           1 => {
             close_modal(world, event, false).expect("Failed to close modal");
-            make_help_modal(world, event, asset);
+            make_help_modal(world, event, asset, state);
           }
           2 => {
             close_modal(world, event, false).expect("Failed to close modal");
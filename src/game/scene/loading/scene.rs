@@ -0,0 +1,47 @@
+/**
+ * The scene shown while the assets of the next scene are preloaded
+ */
+
+use crate::engine::component::text::Text;
+use crate::engine::core::lifecycle::LifecycleArgs;
+use crate::engine::core::scene::Scene;
+use crate::engine::ecs::system::{Schedule, SysArgs, Systemize, SystemTag};
+use crate::engine::render::camera::Sticky1;
+use crate::engine::utility::alignment::{Align, Alignment};
+use crate::engine::utility::color::color;
+use crate::game::constant::WINDOW;
+use crate::game::ui::text_builder::TextBuilder;
+
+/// Marks the text reporting the preload progress
+#[derive(Default)]
+pub struct LoadingProgress;
+
+/// Get the label of the preload progress in the unit interval
+fn progress_label(progress: f32) -> String {
+  format!("loading {}%", (progress * 100.0).floor() as u32)
+}
+
+/// Reports the progress of preloading the next scene
+pub struct LoadingScene;
+
+impl Scene for LoadingScene {
+  /// Set up the loading scene
+  fn setup(&mut self, LifecycleArgs { world, system, asset, .. }: &mut LifecycleArgs) {
+    let mut builder = TextBuilder::<Sticky1>::new(&asset.typeface, &mut asset.texture, color::TEXT, WINDOW);
+    world.add(builder.make_text::<LoadingProgress>(progress_label(0.0), Alignment::new(Align::Center(0.0), Align::Center(0.0))));
+    system.add(Schedule::PostUpdate, SystemTag::Scene, LoadingScene::system).expect("Failed to add loading system");
+  }
+  /// Destroy the loading scene
+  fn destroy(&mut self, LifecycleArgs { .. }: &mut LifecycleArgs) {}
+}
+
+impl Systemize for LoadingScene {
+  /// Update the progress text
+  fn system(SysArgs { world, scene, .. }: &mut SysArgs) -> Result<(), String> {
+    let label = progress_label(scene.get_progress().unwrap_or(1.0));
+    for (_, (text, ..)) in world.query::<(&mut Text, &LoadingProgress)>() {
+      text.set_content(label.clone());
+    }
+    Ok(())
+  }
+}
@@ -2,6 +2,7 @@
  * The game menu scene
  */
 use crate::engine::asset::asset::AssetManager;
+use crate::engine::asset::manifest::AssetManifest;
 use crate::engine::asset::texture::SrcRect;
 use crate::engine::component::animation::Animation;
 use crate::engine::component::position::Position;
@@ -12,13 +13,19 @@ use crate::engine::ecs::system::{Schedule, SysArgs, Systemize, SystemTag};
 use crate::engine::ecs::world::World;
 use crate::engine::geometry::shape::{Rec2, Vec2};
 use crate::engine::render::camera::{Sticky1, Sticky2};
+use crate::engine::component::text::Text;
+use crate::engine::render::presentation::Presentation;
 use crate::engine::render::renderer::layer::Layer9;
 use crate::engine::utility::alias::{Size, Size2};
 use crate::engine::utility::alignment::{Align, Aligner, Alignment};
 use crate::engine::utility::color::color;
-use crate::game::constant::{DEV_SAVE_FILE, USER_SAVE_FILE, WINDOW};
+use crate::game::constant::{DEV_SAVE_FILE, PREFERENCES_FILE, USER_SAVE_FILE, WINDOW};
+use crate::game::music::use_music;
 use crate::game::persistence::data::SaveData;
+use crate::game::preferences::use_preferences;
 use crate::game::scene::level::scene::LevelScene;
+use crate::game::sound::{SoundEvent, use_sounds};
+use crate::game::ui::cursor;
 use crate::game::ui::cursor::{Cursor, CURSOR_MARGIN, make_cursor};
 use crate::game::ui::selection::Selection;
 use crate::game::ui::text_builder::TextBuilder;
@@ -28,21 +35,26 @@ pub const TITLE_Y: f32 = 70.0;
 pub const COPYRIGHT_MARGIN: f32 = 10.0;
 
 pub const TITLE_SIZE: Size2 = Size2::new(78, 20);
+pub const TITLE_PATH: &str = "asset/typography/title.png";
 
 pub const BUTTON_GAP: f32 = 16.0;
-pub const BUTTON_COUNT: f32 = 3.0;
-pub const OPTIONS_BOUNDS: Size2 = Size2::new(48, (BUTTON_GAP * BUTTON_COUNT) as Size);
+pub const BUTTON_COUNT: f32 = 4.0;
+pub const OPTIONS_BOUNDS: Size2 = Size2::new(64, (BUTTON_GAP * BUTTON_COUNT) as Size);
+
+/// Get the label of the display button for a presentation
+fn display_label(presentation: Presentation) -> String {
+  format!("display {}", presentation)
+}
 
 /// Add the main menu UI to the world
-pub fn add_ui(world: &mut World, asset: &mut AssetManager) {
+pub fn add_ui(world: &mut World, asset: &mut AssetManager, presentation: Presentation) {
   let textures = &mut asset.texture;
-  let typeface = asset.typeface.use_store().get("typeface").expect("Failed to get typeface");
-  let cursor_texture = textures.load("asset/hud/cursor.png").expect("Failed to load cursor texture");
+  let typefaces = &asset.typeface;
 
-  let mut static_builder: TextBuilder::<Sticky2> = TextBuilder::<Sticky2>::new(typeface, textures, color::TEXT, WINDOW);
+  let mut static_builder: TextBuilder::<Sticky2> = TextBuilder::<Sticky2>::new(typefaces, textures, color::TEXT, WINDOW);
   world.add(static_builder.make_text::<()>("Aardhyn Lavender 2024", Alignment::new(Align::Center(0.0), Align::End(COPYRIGHT_MARGIN))));
 
-  let title = textures.load("asset/typography/title.png").expect("Failed to load title texture");
+  let title = textures.load(TITLE_PATH).expect("Failed to load title texture");
   let title_alignment = Alignment::new(Align::Center(0.0), Align::Start(TITLE_Y));
   world.add((
     Sprite::new(title, SrcRect::new(Vec2::default(), TITLE_SIZE)),
@@ -52,14 +64,15 @@ pub fn add_ui(world: &mut World, asset: &mut AssetManager) {
 
   let buttons_position = WINDOW.center(OPTIONS_BOUNDS);
   let buttons_aligner = Aligner::new(Rec2::new(Vec2::<i32>::from(buttons_position), OPTIONS_BOUNDS));
-  let mut button_builder: TextBuilder<'_, '_, Sticky1> = TextBuilder::new(typeface, textures, color::TEXT, buttons_aligner);
+  let mut button_builder: TextBuilder<'_, '_, Sticky1> = TextBuilder::new(typefaces, textures, color::TEXT, buttons_aligner);
   let buttons = [
     world.add(button_builder.make_text::<()>("start", Alignment::new(Align::Start(CURSOR_MARGIN), Align::Start(0.0)))),
     world.add(button_builder.make_text::<()>("new game", Alignment::new(Align::Start(CURSOR_MARGIN), Align::Start(BUTTON_GAP)))),
-    world.add(button_builder.make_text::<()>("quit", Alignment::new(Align::Start(CURSOR_MARGIN), Align::Start(BUTTON_GAP * 2.0)))),
+    world.add(button_builder.make_text::<()>(display_label(presentation), Alignment::new(Align::Start(CURSOR_MARGIN), Align::Start(BUTTON_GAP * 2.0)))),
+    world.add(button_builder.make_text::<()>("quit", Alignment::new(Align::Start(CURSOR_MARGIN), Align::Start(BUTTON_GAP * 3.0)))),
   ];
 
-  let cursor = make_cursor::<()>(world, cursor_texture, asset);
+  let cursor = make_cursor::<()>(world, asset);
 
   world.add((Selection::build(buttons, cursor).expect("Failed to build selection"),));
 }
@@ -68,9 +81,14 @@ pub fn add_ui(world: &mut World, asset: &mut AssetManager) {
 pub struct MenuScene;
 
 impl Scene for MenuScene {
+  /// List the assets of the main menu
+  fn manifest(&mut self) -> Result<AssetManifest, String> {
+    Ok(AssetManifest::default().with_texture(TITLE_PATH).with_manifest(cursor::manifest()))
+  }
   /// Set up the main menu scene
-  fn setup(&mut self, LifecycleArgs { world, system, asset, .. }: &mut LifecycleArgs) {
-    add_ui(world, asset);
+  fn setup(&mut self, LifecycleArgs { world, system, asset, state, .. }: &mut LifecycleArgs) {
+    add_ui(world, asset, use_preferences(state).presentation);
+    use_music(state).leave(&mut asset.audio).expect("Failed to fade out music");
     system.add(Schedule::PostUpdate, SystemTag::Suspendable, MenuScene::system).expect("Failed to add menu system");
     system.add(Schedule::PostUpdate, SystemTag::Suspendable, Cursor::system).expect("Failed to cursor system");
     system.add(Schedule::FrameUpdate, SystemTag::Suspendable, Animation::system).expect("Failed to add animation system");
@@ -81,7 +99,7 @@ impl Scene for MenuScene {
 
 impl Systemize for MenuScene {
   /// Manage the selection of the main menu
-  fn system(SysArgs { scene, event, world, .. }: &mut SysArgs) -> Result<(), String> {
+  fn system(SysArgs { scene, event, world, render, state, .. }: &mut SysArgs) -> Result<(), String> {
     let (.., menu) = world.query_one::<&mut Selection>().ok_or("Failed to get menu selection")?;
     let cursor = menu.get_cursor();
 
@@ -89,9 +107,10 @@ impl Systemize for MenuScene {
     let down = is_control(Control::Down, Behaviour::Pressed, event);
     let delta = if up { -1 } else if down { 1 } else { 0 };
     *menu += delta;
+    if delta != 0 { use_sounds(state).play(SoundEvent::MenuMove); }
 
     if is_control(Control::Select, Behaviour::Pressed, event) {
-      let (index, ..) = menu.get_selection();
+      let (index, button) = menu.get_selection();
       match index {
         0 => {
           let save_data = SaveData::from_file(USER_SAVE_FILE)
@@ -107,7 +126,14 @@ impl Systemize for MenuScene {
             .unwrap_or(SaveData::default());
           scene.queue_next(LevelScene::new(save_data))
         }
-        2 => { event.queue_quit() }
+        2 => {
+          let preferences = use_preferences(state);
+          let presentation = preferences.cycle_presentation();
+          preferences.to_file(PREFERENCES_FILE).map_err(|error| eprintln!("Failed to save preferences: {}", error)).ok();
+          render.set_presentation(presentation)?;
+          world.get_component_mut::<Text>(button)?.set_content(display_label(presentation));
+        }
+        3 => { event.queue_quit() }
         _ => { unreachable!("Invalid menu selection index"); }
       }
     }
@@ -1,3 +1,4 @@
 pub mod main_menu;
 pub mod level;
-pub mod credits;
\ No newline at end of file
+pub mod credits;
+pub mod loading;
\ No newline at end of file
@@ -0,0 +1,228 @@
+/**
+ * Gameplay sound effects mapped from a data file
+ */
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+use crate::engine::asset::audio::{AudioPlayer, Loop, SoundType};
+use crate::engine::ecs::system::{SysArgs, Systemize};
+use crate::engine::asset::vfs::read_to_string;
+use crate::engine::utility::io::deserialize_json;
+use crate::engine::utility::state::State;
+use crate::game::scene::level::player::combat::Weapon;
+use crate::game::scene::level::room::meta::TileBreakability;
+
+pub const SOUND_DATA_PATH: &str = "data/sound.json";
+pub const SYNTH_DATA_PATH: &str = "data/synth.json";
+
+/// Occurrences in the game that play a sound
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SoundEvent {
+  Fire(Weapon),
+  Break(TileBreakability),
+  CreatureHit,
+  CreatureDeath,
+  /// an ice beam froze a creature
+  CreatureFreeze,
+  /// an ice beam hit a creature that cannot be frozen
+  FreezeInvalid,
+  PlayerHurt,
+  Collect,
+  Save,
+  ModalOpen,
+  MenuMove,
+}
+
+/// Every sound event, the events the data file may give a sound and that `test_shipped_sounds` checks
+///
+/// Events the data file leaves out, such as `break_solid`, are silent.
+pub const SOUND_EVENTS: [SoundEvent; 16] = [
+  SoundEvent::Fire(Weapon::Bullet),
+  SoundEvent::Fire(Weapon::Rocket),
+  SoundEvent::Fire(Weapon::IceBeam),
+  SoundEvent::Break(TileBreakability::Solid),
+  SoundEvent::Break(TileBreakability::Strong),
+  SoundEvent::Break(TileBreakability::Soft),
+  SoundEvent::Break(TileBreakability::Brittle),
+  SoundEvent::CreatureHit,
+  SoundEvent::CreatureDeath,
+  SoundEvent::CreatureFreeze,
+  SoundEvent::FreezeInvalid,
+  SoundEvent::PlayerHurt,
+  SoundEvent::Collect,
+  SoundEvent::Save,
+  SoundEvent::ModalOpen,
+  SoundEvent::MenuMove,
+];
+
+impl SoundEvent {
+  /// Get the name of the event in the data file
+  pub fn get_name(&self) -> &'static str {
+    match self {
+      SoundEvent::Fire(Weapon::Bullet) => "fire_bullet",
+      SoundEvent::Fire(Weapon::Rocket) => "fire_rocket",
+      SoundEvent::Fire(Weapon::IceBeam) => "fire_ice_beam",
+      SoundEvent::Break(TileBreakability::Solid) => "break_solid",
+      SoundEvent::Break(TileBreakability::Strong) => "break_strong",
+      SoundEvent::Break(TileBreakability::Soft) => "break_soft",
+      SoundEvent::Break(TileBreakability::Brittle) => "break_brittle",
+      SoundEvent::CreatureHit => "creature_hit",
+      SoundEvent::CreatureDeath => "creature_death",
+      SoundEvent::CreatureFreeze => "creature_freeze",
+      SoundEvent::FreezeInvalid => "freeze_invalid",
+      SoundEvent::PlayerHurt => "player_hurt",
+      SoundEvent::Collect => "collect",
+      SoundEvent::Save => "save",
+      SoundEvent::ModalOpen => "modal_open",
+      SoundEvent::MenuMove => "menu_move",
+    }
+  }
+}
+
+/// Where the sound of an event comes from
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum SoundSource {
+  /// sound file relative to the asset root
+  File { file: PathBuf },
+  /// name of a preset in the synth data file
+  Synth { synth: String },
+}
+
+/// Sound played for an event
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct SoundData {
+  #[serde(flatten)]
+  pub source: SoundSource,
+  /// volume in the unit interval
+  #[serde(default = "default_volume")]
+  pub volume: f32,
+  /// fraction the pitch may vary by either way each time the sound plays
+  #[serde(default)]
+  pub pitch: f32,
+  /// instances of the sound that may play at once
+  pub instances: Option<usize>,
+}
+
+fn default_volume() -> f32 { 1.0 }
+
+/// Parse the sounds of events, keyed by event name
+pub fn parse_sound_data(data: &str) -> Result<HashMap<SoundEvent, SoundData>, String> {
+  let mut named = deserialize_json::<HashMap<String, SoundData>>(data)?;
+  let sounds = SOUND_EVENTS
+    .iter()
+    .filter_map(|event| named.remove(event.get_name()).map(|sound| (*event, sound)))
+    .collect();
+  if let Some(name) = named.keys().next() { return Err(format!("Unknown sound event '{}'", name)); }
+  Ok(sounds)
+}
+
+/// A loaded sound of an event
+#[derive(Debug)]
+struct BankSound {
+  name: String,
+  volume: f32,
+}
+
+/// Plays the sounds of events queued by the game systems
+///
+/// Events without a sound, or whose sound failed to load, are silent.
+#[derive(Debug, Default)]
+pub struct SoundBank {
+  sounds: HashMap<SoundEvent, BankSound>,
+  queue: Vec<SoundEvent>,
+}
+
+impl SoundBank {
+  /// Load the sounds of the events in a data file, reporting the sounds that fail to load
+  ///
+  /// Synthesized sounds must already be in the audio store.
+  pub fn load(filepath: &str, audio: &mut AudioPlayer) -> Result<Self, String> {
+    let data = parse_sound_data(&read_to_string(filepath)?).map_err(|e| format!("Failed to parse sounds {}: {}", filepath, e))?;
+    let mut sounds = HashMap::new();
+    for (event, sound) in data {
+      let name = match &sound.source {
+        SoundSource::File { file } => audio.load(SoundType::Effect, file),
+        SoundSource::Synth { synth } if audio.is_loaded(synth) => Ok(synth.clone()),
+        SoundSource::Synth { synth } => Err(format!("Synth preset {} is missing", synth)),
+      };
+      let name = match name {
+        Ok(name) => name,
+        Err(error) => {
+          eprintln!("Sound for '{}' is silent: {}", event.get_name(), error);
+          continue;
+        }
+      };
+      audio.set_pitch_variation(&name, sound.pitch)?;
+      if let Some(instances) = sound.instances { audio.set_max_instances(&name, instances); }
+      sounds.insert(event, BankSound { name, volume: sound.volume });
+    }
+    Ok(Self { sounds, queue: Vec::new() })
+  }
+
+  /// Queue the sound of an event to play at the end of the frame
+  pub fn play(&mut self, event: SoundEvent) { self.queue.push(event); }
+}
+
+impl Systemize for SoundBank {
+  /// Play the sounds of the events queued this frame
+  fn system(SysArgs { state, asset, .. }: &mut SysArgs) -> Result<(), String> {
+    let bank = use_sounds(state);
+    for event in bank.queue.drain(..) {
+      let Some(sound) = bank.sounds.get(&event) else { continue; };
+      asset.audio
+        .play(&sound.name, sound.volume, Loop::Once)
+        .map_err(|error| eprintln!("Failed to play sound for '{}': {}", event.get_name(), error))
+        .ok();
+    }
+    Ok(())
+  }
+}
+
+/// Mutably borrows the sound bank from the engine
+pub fn use_sounds(state: &mut State) -> &mut SoundBank {
+  state.get_mut::<SoundBank>().expect("Failed to get sound bank")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_parse_sound_data() {
+    let sounds = parse_sound_data(r#"{
+      "fire_rocket": { "synth": "rocket", "volume": 0.5, "pitch": 0.1 },
+      "break_soft": { "file": "asset/sound/crumble.ogg", "instances": 2 }
+    }"#).unwrap();
+    let rocket = &sounds[&SoundEvent::Fire(Weapon::Rocket)];
+    assert_eq!(rocket.source, SoundSource::Synth { synth: String::from("rocket") });
+    assert_eq!((rocket.volume, rocket.pitch, rocket.instances), (0.5, 0.1, None));
+    let crumble = &sounds[&SoundEvent::Break(TileBreakability::Soft)];
+    assert_eq!(crumble.source, SoundSource::File { file: PathBuf::from("asset/sound/crumble.ogg") });
+    assert_eq!((crumble.volume, crumble.pitch, crumble.instances), (1.0, 0.0, Some(2)), "settings are optional");
+    assert!(parse_sound_data(r#"{ "jump": { "file": "jump.ogg" } }"#).is_err(), "event is unknown");
+  }
+
+  /// The sound table only references sound files and synth presets that ship with the game
+  #[test]
+  fn test_shipped_sounds() {
+    let root = std::path::Path::new(env!("CARGO_MANIFEST_DIR"));
+    let read = |path: &str| std::fs::read_to_string(root.join(path)).unwrap();
+    let presets = deserialize_json::<HashMap<String, serde_json::Value>>(&read(SYNTH_DATA_PATH)).unwrap();
+    for (event, sound) in parse_sound_data(&read(SOUND_DATA_PATH)).unwrap() {
+      match sound.source {
+        SoundSource::File { file } => assert!(root.join(&file).is_file(), "Sound for '{}' is missing {}", event.get_name(), file.display()),
+        SoundSource::Synth { synth } => assert!(presets.contains_key(&synth), "Sound for '{}' has no synth preset {}", event.get_name(), synth),
+      }
+    }
+  }
+
+  #[test]
+  fn test_event_names() {
+    let names = SOUND_EVENTS.iter().map(SoundEvent::get_name).collect::<std::collections::HashSet<_>>();
+    assert_eq!(names.len(), SOUND_EVENTS.len(), "every event has a unique name");
+  }
+}
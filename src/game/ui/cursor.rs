@@ -1,5 +1,3 @@
-use std::time::Duration;
-
 /**
  * UI Cursor component and system
  */
@@ -7,36 +5,41 @@ use std::time::Duration;
 use hecs::{Component, Entity};
 
 use crate::engine::asset::asset::AssetManager;
-use crate::engine::asset::texture::{SrcRect, TextureKey};
-use crate::engine::component::animation::make_texture_animation;
+use crate::engine::asset::manifest::AssetManifest;
+use crate::engine::component::animation::Animation;
 use crate::engine::component::position::Position;
 use crate::engine::component::sprite::Sprite;
 use crate::engine::ecs::system::{SysArgs, Systemize};
 use crate::engine::ecs::world::World;
 use crate::engine::geometry::shape::Vec2;
 use crate::engine::render::camera::Sticky2;
-use crate::engine::utility::alias::Size2;
 use crate::game::ui::selection::Selection;
 
-pub const CURSOR_DIMENSIONS: Size2 = Vec2::new(5, 5);
 pub const CURSOR_MARGIN: f32 = 8.0;
-pub const CURSOR_PATH: &str = "asset/hud/cursor.png";
-pub const CURSOR_OFFSET: Vec2<f32> = Vec2::new(-(CURSOR_DIMENSIONS.x as f32) + -CURSOR_MARGIN, 1.0);
-pub const CURSOR_ANIMATE_MS: u64 = 150;
+pub const CURSOR_PATH: &str = "asset/hud/cursor.json";
+pub const CURSOR_CLIP: &str = "blink";
+pub const CURSOR_OFFSET_Y: f32 = 1.0;
+
+/// List the assets of a cursor
+pub fn manifest() -> AssetManifest {
+  AssetManifest::default().with_sheet(CURSOR_PATH)
+}
 
 /// Cursor component
 
 pub struct Cursor;
 
 // Create cursor
-pub fn make_cursor<C>(world: &mut World, texture: TextureKey, assets: &mut AssetManager) -> Entity where C: Default + Component {
-  let animation = make_texture_animation(texture, assets, CURSOR_DIMENSIONS, Duration::from_millis(CURSOR_ANIMATE_MS)).expect("Failed to make cursor animation");
+pub fn make_cursor<C>(world: &mut World, assets: &mut AssetManager) -> Entity where C: Default + Component {
+  let sheet = assets.sheet.load(CURSOR_PATH, &mut assets.texture).expect("Failed to load cursor sheet");
+  let sheet = assets.sheet.get(sheet).expect("Failed to get cursor sheet");
+  let animation = Animation::from_clip(sheet, CURSOR_CLIP).expect("Failed to make cursor animation");
   world.add((
     C::default(),
     Cursor,
     Position::default(),
     Sticky2::default(),
-    Sprite::new(texture, SrcRect::new(Vec2::default(), CURSOR_DIMENSIONS)),
+    Sprite::from_clip(sheet, CURSOR_CLIP).expect("Failed to make cursor sprite"),
     animation.start(),
   ))
 }
@@ -61,8 +64,9 @@ fn get_selection(world: &mut World) -> Option<(Entity, Entity)> {
 }
 
 fn place_cursor(world: &mut World, cursor: Entity, selected: Entity) -> Result<(), String> {
-  let selection_position = world.get_component::<Position>(selected)?;
+  let selection_position = world.get_component::<Position>(selected)?.0;
+  let cursor_width = world.get_component::<Sprite>(cursor)?.src.size.x as f32;
   let mut cursor_position = world.get_component_mut::<Position>(cursor)?;
-  cursor_position.0 = selection_position.0 + CURSOR_OFFSET;
+  cursor_position.0 = selection_position + Vec2::new(-cursor_width - CURSOR_MARGIN, CURSOR_OFFSET_Y);
   Ok(())
 }
\ No newline at end of file
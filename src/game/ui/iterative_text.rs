@@ -12,8 +12,10 @@ use crate::engine::ecs::world::World;
 use crate::engine::utility::time::{ConsumeAction, Timer};
 
 /// Manage the iteration of characters in text components
+///
+/// Characters are revealed rather than appended, so laid out text does not reflow as it is iterated
 pub struct IterativeText {
-  text: String,
+  length: usize,
   current_character: usize,
   delay: Timer,
   timer: Timer,
@@ -21,9 +23,9 @@ pub struct IterativeText {
 
 impl IterativeText {
   /// Instantiate a new iterative text component
-  fn new(duration: Duration, text: String) -> Self {
+  fn new(duration: Duration, length: usize) -> Self {
     Self {
-      text,
+      length,
       current_character: 0,
       timer: Timer::new(duration, true),
       delay: Timer::default(),
@@ -39,26 +41,19 @@ impl Systemize for IterativeText {
       .filter_map(|(entity, (animated_text, text))| {
         // check start condition
         if !animated_text.delay.done() {
-          if !text.get_text().is_empty() {
-            text.set_content("");
-          }
+          text.set_reveal(Some(0));
           return None;
         }
 
         let index = &mut animated_text.current_character;
 
         if *index == 0 || animated_text.timer.consume(ConsumeAction::Restart) {
-          let content = animated_text
-            .text
-            .chars()
-            .take(*index)
-            .collect::<String>();
-
-          text.set_content(content);
+          text.set_reveal(Some(*index));
           *index += 1;
 
           // check for completion
-          return if *index == animated_text.text.len() + 1 {
+          return if *index == animated_text.length + 1 {
+            text.set_reveal(None);
             Some(entity)
           } else {
             None
@@ -98,21 +93,20 @@ pub struct IterativeTextBuilder<'a> {
 }
 
 impl<'a> IterativeTextBuilder<'a> {
-  /// Get the text component from the world
-  fn extract_text(world: &mut World, entity: Entity) -> Result<String, String> {
+  /// Hide the text component in the world and get its visible length
+  fn extract_text(world: &mut World, entity: Entity) -> Result<usize, String> {
     world
       .get_component_mut::<Text>(entity)
       .map_err(|_| "Failed to get text component".to_string())
       .map(|mut text| {
-        let content = text.get_text().clone();
-        text.set_content("");
-        content
+        text.set_reveal(Some(0));
+        text.get_visible_len()
       })
   }
 
   pub fn build(world: &'a mut World, text_entity: Entity) -> Result<Self, String> {
-    let content = Self::extract_text(world, text_entity)?;
-    let iterator = IterativeText::new(Duration::default(), content);
+    let length = Self::extract_text(world, text_entity)?;
+    let iterator = IterativeText::new(Duration::default(), length);
     Ok(Self { world, text: text_entity, iterator })
   }
   /// Set the duration between character iterations
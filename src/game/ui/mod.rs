@@ -3,3 +3,4 @@ pub mod iterative_text;
 pub mod modal;
 pub mod selection;
 pub mod text_builder;
+pub mod typography;
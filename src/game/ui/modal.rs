@@ -13,8 +13,11 @@ use crate::engine::render::camera::{Sticky1, Sticky2};
 use crate::engine::utility::alias::Size2;
 use crate::engine::utility::alignment::{Align, Aligner, Alignment};
 use crate::engine::utility::color::color;
+use crate::engine::utility::state::State;
 use crate::game::constant::WINDOW;
+use crate::game::sound::{SoundEvent, use_sounds};
 use crate::game::ui::text_builder::TextBuilder;
+use crate::game::ui::typography::heading_font;
 use crate::game::utility::controls::{Behaviour, Control, is_control};
 
 const MODAL_MARGIN: f32 = 8.0;
@@ -28,15 +31,13 @@ pub fn make_modal<'m, 'a>(
   world: &'m mut World,
   events: &mut EventStore,
   asset: &'a mut AssetManager,
+  state: &mut State,
   title: String,
   size: Size2,
   background: TextureKey,
 ) -> (Aligner, TextBuilder<'a, 'a, Sticky2>, ) where {
   let loader = &mut asset.texture;
-  let typeface = asset.typeface
-    .use_store()
-    .get("typeface")
-    .expect("Failed to get typeface");
+  let typefaces = &asset.typeface;
 
   let position = WINDOW.center(size);
 
@@ -48,11 +49,14 @@ pub fn make_modal<'m, 'a>(
   ));
 
   let aligner = Aligner::new(Rec2::new(Vec2::<i32>::from(position), size));
-  let mut builder: TextBuilder<'a, 'a, Sticky2> = TextBuilder::new(typeface, loader, color::TEXT, aligner);
+  let mut builder: TextBuilder<'a, 'a, Sticky2> = TextBuilder::new(typefaces, loader, color::TEXT, aligner);
 
+  builder.set_font(Some(heading_font()));
   world.add(builder.make_text::<Modal>(title.clone(), Alignment::new(Align::Center(0.0), Align::Start(MODAL_MARGIN))));
+  builder.set_font(None);
 
   events.queue_pause();
+  use_sounds(state).play(SoundEvent::ModalOpen);
 
   (aligner, builder)
 }
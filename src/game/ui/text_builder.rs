@@ -5,25 +5,51 @@
 use std::marker::PhantomData;
 
 use hecs::{Component, DynamicBundle};
-use sdl2::ttf::Font;
 
 use crate::engine::asset::texture::TextureLoader;
+use crate::engine::asset::typography::{FontHandle, TypefaceLoader};
 use crate::engine::component::position::Position;
-use crate::engine::component::text::Text;
+use crate::engine::component::text::{Text, TextLayout};
 use crate::engine::render::camera::Sticky1;
 use crate::engine::utility::alignment::{Aligner, Alignment};
 use crate::engine::utility::color::RGBA;
 
+/// Instantiate an empty text in `color` and `font`
+fn make_styled(color: RGBA, font: Option<FontHandle>) -> Text {
+  let text = Text::new(color);
+  if let Some(font) = font { text.with_font(font) } else { text }
+}
+
 /// Helper function to assemble the components for a text entity
-pub fn make_text<'font, 'app, Meta, Layer>(
+///
+/// Renders in the default typeface unless `font` is given
+pub fn make_text<Meta, Layer>(
+  content: impl Into<String>,
+  position: Alignment,
+  aligner: &Aligner,
+  color: RGBA,
+  font: Option<FontHandle>,
+  typefaces: &TypefaceLoader,
+  texture_loader: &mut TextureLoader,
+) -> impl DynamicBundle where Meta: Component + Default, Layer: Component + Default {
+  let text = make_styled(color, font).with_content(content, typefaces, texture_loader);
+  let position = aligner.align(position, text.get_dimensions());
+
+  (Position(position), text, Layer::default(), Meta::default(), )
+}
+
+/// Helper function to assemble the components for a paragraph of text laid out into a box
+pub fn make_paragraph<Meta, Layer>(
   content: impl Into<String>,
   position: Alignment,
+  layout: TextLayout,
   aligner: &Aligner,
   color: RGBA,
-  typeface: &Font<'font, 'app>,
+  font: Option<FontHandle>,
+  typefaces: &TypefaceLoader,
   texture_loader: &mut TextureLoader,
 ) -> impl DynamicBundle where Meta: Component + Default, Layer: Component + Default {
-  let text = Text::new(color).with_content(content, &typeface, texture_loader);
+  let text = make_styled(color, font).with_layout(layout).with_content(content, typefaces, texture_loader);
   let position = aligner.align(position, text.get_dimensions());
 
   (Position(position), text, Layer::default(), Meta::default(), )
@@ -31,28 +57,38 @@ pub fn make_text<'font, 'app, Meta, Layer>(
 
 /// Helper struct for creating multiple text entities
 pub struct TextBuilder<'fonts, 'app, Layer = Sticky1> {
-  typeface: &'app Font<'fonts, 'app>,
+  typefaces: &'app TypefaceLoader<'fonts, 'fonts>,
   texture_loader: &'app mut TextureLoader,
   color: RGBA,
+  font: Option<FontHandle>,
   aligner: Aligner,
   layer: PhantomData<Layer>,
 }
 
 impl<'app, 'fonts, Layer> TextBuilder<'app, 'fonts, Layer> where Layer: Default + Component {
   /// Instantiate a new text builder
-  pub fn new(typeface: &'app Font<'fonts, 'app>, texture_loader: &'app mut TextureLoader, color: RGBA, aligner: Aligner) -> Self {
+  pub fn new(typefaces: &'app TypefaceLoader<'fonts, 'fonts>, texture_loader: &'app mut TextureLoader, color: RGBA, aligner: Aligner) -> Self {
     Self {
-      typeface,
+      typefaces,
       texture_loader,
       color,
+      font: None,
       aligner,
       layer: PhantomData,
     }
   }
+  /// Render subsequent text in a loaded typeface, or the default typeface with `None`
+  pub fn set_font(&mut self, font: Option<FontHandle>) { self.font = font; }
   /// Assemble the components for a text entity
   pub fn make_text<Meta>(&mut self, content: impl Into<String>, position: Alignment) -> impl DynamicBundle
     where Meta: Component + Default + 'static
   {
-    make_text::<Meta, Layer>(content, position, &self.aligner, self.color, self.typeface, self.texture_loader)
+    make_text::<Meta, Layer>(content, position, &self.aligner, self.color, self.font.clone(), self.typefaces, self.texture_loader)
+  }
+  /// Assemble the components for a paragraph of text laid out into a box
+  pub fn make_paragraph<Meta>(&mut self, content: impl Into<String>, position: Alignment, layout: TextLayout) -> impl DynamicBundle
+    where Meta: Component + Default + 'static
+  {
+    make_paragraph::<Meta, Layer>(content, position, layout, &self.aligner, self.color, self.font.clone(), self.typefaces, self.texture_loader)
   }
 }
@@ -0,0 +1,38 @@
+/**
+ * Typefaces used by the game UI
+ */
+
+use std::path::Path;
+
+use crate::engine::asset::asset::AssetManager;
+use crate::engine::asset::typography::FontHandle;
+
+pub const TYPEFACE_PATH: &str = "asset/typography/typeface.ttf";
+/// Bitmap font of digits drawn at twice the scale of the typeface
+pub const NUMERAL_PATH: &str = "asset/typography/numerals.fnt";
+
+/// Point size of the typeface for body copy, the default typeface
+pub const BODY_SIZE: u16 = 5;
+/// Point size of the typeface for modal titles
+pub const HEADING_SIZE: u16 = 10;
+/// Size the numerals were drawn at
+pub const NUMERAL_SIZE: u16 = 10;
+
+/// The typeface for body copy
+pub fn body_font() -> FontHandle { FontHandle::new(family(TYPEFACE_PATH), BODY_SIZE) }
+/// The typeface for titles
+pub fn heading_font() -> FontHandle { FontHandle::new(family(TYPEFACE_PATH), HEADING_SIZE) }
+/// The bitmap font for numbers on the HUD
+pub fn numeral_font() -> FontHandle { FontHandle::new(family(NUMERAL_PATH), NUMERAL_SIZE) }
+
+/// The family a font is stored under, derived from its filename
+fn family(path: &'static str) -> &'static str {
+  Path::new(path).file_stem().and_then(|stem| stem.to_str()).expect("Font path has a filename")
+}
+
+/// Load every typeface used by the game, using the body typeface by default
+pub fn load_typefaces(asset: &mut AssetManager) -> Result<(), String> {
+  asset.typeface.load_sizes(Path::new(TYPEFACE_PATH), &[BODY_SIZE, HEADING_SIZE])?;
+  asset.typeface.load_bitmap(Path::new(NUMERAL_PATH), &mut asset.texture)?;
+  asset.typeface.set_default(&body_font())
+}
@@ -0,0 +1,54 @@
+/**
+ * Debug drawing categories and controls
+ */
+
+use sdl2::keyboard::Keycode;
+
+use crate::engine::asset::texture::TextureLoader;
+use crate::engine::core::event::EventStore;
+use crate::engine::geometry::shape::Vec2;
+use crate::engine::render::camera::Camera;
+use crate::engine::render::debug::{DebugCategory, DebugDraw};
+use crate::engine::utility::color::color;
+use crate::game::utility::controls::{Behaviour, Control, is_control};
+
+/// Collision boxes of entities
+pub const DEBUG_COLLIDER: DebugCategory = "collider";
+/// Colliders that start room transitions
+pub const DEBUG_ROOM: DebugCategory = "room";
+/// Collision edges of tiles
+pub const DEBUG_TILE: DebugCategory = "tile";
+/// Tile queries and targeting of creatures
+pub const DEBUG_CREATURE: DebugCategory = "creature";
+/// Textures held by the texture loader
+pub const DEBUG_TEXTURE: DebugCategory = "texture";
+
+/// Distance of debug statistics from the top left of the screen
+pub const DEBUG_STATS_MARGIN: f32 = 4.0;
+
+/// Categories toggled by the number keys in order while debugging
+pub const DEBUG_CATEGORIES: [(Keycode, DebugCategory); 5] = [
+  (Keycode::Num1, DEBUG_COLLIDER),
+  (Keycode::Num2, DEBUG_ROOM),
+  (Keycode::Num3, DEBUG_TILE),
+  (Keycode::Num4, DEBUG_CREATURE),
+  (Keycode::Num5, DEBUG_TEXTURE),
+];
+
+/// Toggle debug drawing, and its categories while it is enabled
+pub fn use_debug_controls(event: &EventStore, debug: &mut DebugDraw) {
+  if is_control(Control::Debug, Behaviour::Pressed, event) {
+    debug.set_enabled(!debug.is_enabled());
+  }
+  if !debug.is_enabled() { return; }
+  for (key, category) in DEBUG_CATEGORIES {
+    if event.is_key_pressed(key) { debug.toggle_visible(category); }
+  }
+}
+
+/// Show the textures held by the texture loader in the top left of the screen
+pub fn draw_texture_stats(debug: &mut DebugDraw, camera: &Camera, textures: &TextureLoader) {
+  if !debug.is_visible(DEBUG_TEXTURE) { return; }
+  let position = Vec2::<f32>::from(camera.get_viewport().origin) + DEBUG_STATS_MARGIN;
+  debug.text(DEBUG_TEXTURE, position, textures.get_stats().to_string(), color::TEXT);
+}
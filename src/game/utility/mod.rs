@@ -1,3 +1,4 @@
 pub mod controls;
+pub mod debug;
 pub mod math;
-pub mod path;
\ No newline at end of file
+pub mod path;
@@ -5,13 +5,17 @@
  * Build and run the application
  */
 
-use std::path::Path;
-
 use crate::engine::application::Application;
 use crate::engine::core::lifecycle::{Lifecycle, LifecycleArgs};
+use crate::engine::ecs::system::{Schedule, Systemize, SystemTag};
+use crate::engine::render::presentation::Presentation;
 use crate::engine::render::renderer::Properties;
-use crate::game::constant::{LOGICAL_SIZE, TYPEFACE_PATH, TYPEFACE_SIZE, WINDOW_SIZE, WINDOW_TITLE};
+use crate::game::constant::{LOGICAL_SIZE, PREFERENCES_FILE, PRESENTATIONS, WINDOW_SIZE, WINDOW_TITLE};
+use crate::game::ui::typography::load_typefaces;
+use crate::game::music::{MUSIC_DATA_PATH, MusicDirector};
 use crate::game::preferences::Preferences;
+use crate::game::sound::{SOUND_DATA_PATH, SoundBank, SYNTH_DATA_PATH};
+use crate::game::scene::loading::scene::LoadingScene;
 use crate::game::scene::main_menu::scene::MenuScene;
 
 pub mod engine;
@@ -25,6 +29,7 @@ fn main() -> Result<(), String> {
       dimensions: WINDOW_SIZE,
       logical: Some(LOGICAL_SIZE),
       fullscreen: false,
+      presentation: PRESENTATIONS[0],
       show_cursor: true,
       vsync: true,
       opengl: true,
@@ -36,12 +41,41 @@ fn main() -> Result<(), String> {
       destroy,
     },
     MenuScene,
+    LoadingScene,
   )
 }
 
-fn setup(LifecycleArgs { asset, state, .. }: LifecycleArgs) {
-  asset.typeface.load(Path::new(TYPEFACE_PATH), TYPEFACE_SIZE).expect("Failed to load typeface");
-  state.add::<Preferences>(Preferences::default()).expect("Failed to add preferences");
+/// Capture every Nth frame to numbered files, eg: `--capture-every 4`
+const CAPTURE_FLAG: &str = "--capture-every";
+/// Scale the window by a presentation, remembered for later sessions, eg: `--presentation integer`
+const PRESENTATION_FLAG: &str = "--presentation";
+
+fn setup(LifecycleArgs { asset, state, render, system, .. }: LifecycleArgs) {
+  load_typefaces(asset).expect("Failed to load typefaces");
+
+  let args = std::env::args().collect::<Vec<_>>();
+  let flag = |flag: &str| args.iter().position(|arg| arg == flag).and_then(|index| args.get(index + 1));
+  if let Some(interval) = flag(CAPTURE_FLAG) {
+    let interval = interval.parse::<u32>().expect("Capture interval must be a positive number");
+    render.capture.set_interval(Some(interval));
+  }
+
+  let mut preferences = Preferences::from_file(PREFERENCES_FILE).unwrap_or_default();
+  if let Some(presentation) = flag(PRESENTATION_FLAG) {
+    preferences.presentation = presentation.parse::<Presentation>().expect("Failed to parse presentation");
+    preferences.to_file(PREFERENCES_FILE).map_err(|error| eprintln!("Failed to save preferences: {}", error)).ok();
+  }
+  render.set_presentation(preferences.presentation).expect("Failed to set presentation");
+  asset.audio.set_volumes(preferences.volumes);
+  state.add::<Preferences>(preferences).expect("Failed to add preferences");
+
+  asset.audio.load_synths(SYNTH_DATA_PATH).expect("Failed to synthesize sounds");
+  let sounds = SoundBank::load(SOUND_DATA_PATH, &mut asset.audio).expect("Failed to load sounds");
+  state.add::<SoundBank>(sounds).expect("Failed to add sound bank");
+  system.add(Schedule::PostUpdate, SystemTag::Internal, SoundBank::system).expect("Failed to add sound system");
+
+  let music = MusicDirector::load(MUSIC_DATA_PATH, SYNTH_DATA_PATH, &mut asset.audio).expect("Failed to load music");
+  state.add::<MusicDirector>(music).expect("Failed to add music director");
   println!("Game Initialized.");
 }
 